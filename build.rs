@@ -0,0 +1,56 @@
+// With the chrono-tz feature, write the time zones offered for chrono_tz::Tz fields as consts, since
+// YFormSelect needs its values and labels at compile time and chrono-tz only lists its zones in a
+// static
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    #[cfg(feature = "chrono-tz")]
+    time_zones::write();
+}
+
+#[cfg(feature = "chrono-tz")]
+mod time_zones {
+    use std::{env, fs, path::Path};
+
+    // The regions zones are grouped by. Zones in any other, i.e. the `US/Eastern` style aliases
+    // and `Etc/GMT+5`, aren't offered, though fields can still hold them.
+    const REGIONS: [&str; 10] = [
+        "Africa",
+        "America",
+        "Antarctica",
+        "Arctic",
+        "Asia",
+        "Atlantic",
+        "Australia",
+        "Europe",
+        "Indian",
+        "Pacific",
+    ];
+
+    pub fn write() {
+        let mut zones = chrono_tz::TZ_VARIANTS
+            .iter()
+            .map(|tz| tz.name())
+            .filter(|name| {
+                *name == "UTC"
+                    || name
+                        .split_once('/')
+                        .is_some_and(|(region, _)| REGIONS.contains(&region))
+            })
+            .collect::<Vec<_>>();
+        zones.sort_unstable();
+        // A zone is labelled by what follows its region, i.e. `Argentina / Buenos Aires`
+        let labels = zones.iter().map(|name| {
+            name.split_once('/')
+                .map_or(*name, |(_, place)| place)
+                .replace('_', " ")
+                .replace('/', " / ")
+        });
+        let code = format!(
+            "const ZONE_VALUES: &[&str] = &{:?};\nconst ZONE_LABELS: &[&str] = &{:?};\n",
+            zones,
+            labels.collect::<Vec<_>>(),
+        );
+        let out = Path::new(&env::var("OUT_DIR").unwrap()).join("time_zones.rs");
+        fs::write(out, code).unwrap();
+    }
+}
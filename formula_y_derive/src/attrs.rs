@@ -0,0 +1,1483 @@
+use convert_case::{Case, Casing};
+use syn::{Lit, Meta, NestedMeta};
+
+use crate::util::{
+    check_field_type, field_is_bool, field_is_date, field_is_enum, field_is_float, field_is_number,
+    field_is_option, field_is_option_bool, field_is_option_date, field_is_option_enum,
+    field_is_option_number, field_is_option_string, field_is_string, field_is_string_vec,
+    field_is_time_zone, field_is_vec,
+};
+
+/// Built in transforms which can be applied to text values with `#[yform(transform = "...")]`
+pub enum Transform {
+    Lowercase,
+    Uppercase,
+}
+
+/// How a float field is rounded to its precision, set with `#[yform(rounding = "...")]`
+#[derive(Clone, Copy)]
+pub enum Rounding {
+    HalfUp,
+    HalfEven,
+    Down,
+    Up,
+}
+
+/// How a number field's value is displayed while its input isn't focused, set with
+/// `#[yform(format = "...")]`
+pub enum NumberFormat {
+    Thousands,
+}
+
+/// The event a checkbox, radio or select stores its value on, set with `#[yform(event = "...")]`
+/// on the field, or for each kind of input with `checkbox_event`, `radio_event` and
+/// `select_event` on the struct
+#[derive(Clone, Copy, Default, PartialEq)]
+pub enum WidgetEvent {
+    #[default]
+    Change,
+    Input,
+    Click,
+}
+
+impl WidgetEvent {
+    /// The event's name in the DOM
+    pub fn name(self) -> &'static str {
+        match self {
+            WidgetEvent::Change => "change",
+            WidgetEvent::Input => "input",
+            WidgetEvent::Click => "click",
+        }
+    }
+}
+
+/// Where the submit button is placed, set with `#[yform(button_position = "...")]`
+#[derive(Default, PartialEq)]
+pub enum ButtonPosition {
+    Top,
+    #[default]
+    Bottom,
+    Both,
+}
+
+/// A ready made layout and behaviour for a common kind of form, set with `#[yform(preset = "...")]`
+#[derive(PartialEq)]
+pub enum Preset {
+    Search,
+    Login,
+}
+
+/// The input types which hold free text, and so can stand in for `type="text"`
+const TEXT_INPUT_TYPES: [&str; 6] = ["text", "email", "password", "search", "tel", "url"];
+
+/// The options a user can set on a field with `#[yform(...)]`
+#[derive(Default)]
+pub struct FieldAttrs {
+    /// Render the field as a textarea which resizes to fit its content
+    pub autogrow: bool,
+    /// Render the field as a textarea
+    pub textarea: bool,
+    /// The number of lines a textarea shows
+    pub rows: Option<u32>,
+    /// Strip leading and trailing whitespace before the value is stored
+    pub trim: bool,
+    /// A built in transform applied to the value on change
+    pub transform: Option<Transform>,
+    /// A user provided `fn(String) -> String` applied to the value on change
+    pub transform_with: Option<syn::Path>,
+    /// The title of a collapsible section the field is grouped into
+    pub section: Option<String>,
+    /// Start the field's section closed
+    pub collapsed: bool,
+    /// Render a select whose `(value, label)` choices come from a `<field>_options` prop
+    pub options_prop: bool,
+    /// Take the options as `(group, value, label)` entries rendered in optgroups
+    pub grouped: bool,
+    /// The fields this field is computed from, and the function computing it from their values
+    pub computed: Option<(Vec<syn::Ident>, syn::Path)>,
+    /// Render the computed value in an `<output>` element pointing at the fields it comes from
+    pub output: bool,
+    /// A user provided `fn(T) -> T` converting the field's value in what is submitted
+    pub submit_with: Option<syn::Path>,
+    /// A user provided `fn(&str) -> String` encrypting or tokenizing a text field's value in what
+    /// is submitted
+    pub encrypt_with: Option<syn::Path>,
+    /// Replaces the Title Cased field name as the label
+    pub label: Option<String>,
+    /// Placeholder text shown in the empty input
+    pub placeholder: Option<String>,
+    /// The text of a select's empty first choice, i.e. "-- select --"
+    pub empty_label: Option<String>,
+    /// The input's `name` attribute, in place of the field's name
+    pub name_attr: Option<String>,
+    /// The type of a text input, i.e. "email" or "password"
+    pub input_type: Option<String>,
+    /// A pattern the whole value must match to submit, anchored with `^(?:...)$`
+    pub regex: Option<String>,
+    /// The message shown while the value doesn't match the regex
+    pub error: Option<String>,
+    /// The message shown while a required value is missing after a submit attempt
+    pub required_msg: Option<String>,
+    /// A user provided `fn(&T) -> Result<(), String>` checking the field's value
+    pub validate: Option<syn::Path>,
+    /// How a number is displayed while its input isn't focused
+    pub format: Option<NumberFormat>,
+    /// Render an enum field, or a field with choices, as a group of radio buttons rather than a
+    /// select
+    pub radio: bool,
+    /// The only values a String field can hold, offered as radios or a select
+    pub choices: Vec<String>,
+    /// Render the field's own YForm struct inside a fieldset
+    pub nested: bool,
+    /// Only require a value while this other field is filled in or checked
+    pub required_if: Option<syn::Ident>,
+    /// Leave the field out of the rendered form, submitting its initial value
+    pub skip: bool,
+    /// Render the field as an `<input type="hidden">` holding its value
+    pub hidden: bool,
+    /// Render the field's input without letting it be edited
+    pub readonly: bool,
+    /// The step of a multi-step form the field is shown on
+    pub step: Option<u32>,
+    /// A user provided `fn(&T, Callback<T>) -> Html` rendering the field's input
+    pub custom: Option<syn::Path>,
+    /// A user provided `fn(String) -> String` cleaning up text pasted into the input
+    pub on_paste: Option<syn::Path>,
+    /// Keep characters which can't be part of the number from being entered at all
+    pub strict_numeric: bool,
+    /// Render a number as a text input with a numeric keyboard, which has no spin buttons
+    pub no_spinner: bool,
+    /// Show a value the user hasn't changed masked, i.e. `••••1234`, in place of its input
+    pub masked_display: bool,
+    /// The decimal places a float field is rounded to as it changes and is submitted, and how
+    pub precision: Option<(u32, Rounding)>,
+    /// The literal the field starts out holding in `new()`, in place of its empty value
+    pub default: Option<Lit>,
+    /// The event the field's checkbox, radios or select store its value on
+    pub event: Option<WidgetEvent>,
+    /// Render a number as a slider, storing its value as it moves
+    pub range: bool,
+    /// The lowest value a slider goes down to
+    pub min: Option<String>,
+    /// The highest value a slider goes up to
+    pub max: Option<String>,
+    /// The fewest milliseconds between updates from a field updated as it changes, i.e. a slider
+    pub throttle_ms: Option<u32>,
+    /// The units a float can be entered in, picked from a select beside its input. The value is
+    /// stored in the first of them.
+    pub unit_options: Vec<String>,
+    /// How many of the first unit each of the units is
+    pub unit_factors: Vec<f64>,
+    /// Render a date as a text input, typed in the order of the browser's locale
+    pub date_text: bool,
+    /// The fewest items a Vec field can hold, below which Remove is disabled and submitting blocked
+    pub min_items: Option<usize>,
+    /// The most items a Vec field can hold, at which Add is disabled
+    pub max_items: Option<usize>,
+    /// Options which have no effect on the field, as the span to warn at and why they are ignored
+    pub warnings: Vec<(proc_macro2::Span, String)>,
+}
+
+impl FieldAttrs {
+    /// Collect every `#[yform(...)]` attribute on the field into one set of options. Malformed
+    /// options are reported with the span of the offending tokens.
+    pub fn parse(field: &syn::Field) -> syn::Result<FieldAttrs> {
+        check_field_type(field)?;
+        let mut attrs = FieldAttrs::default();
+        let mut collapsed_path = None;
+        let mut grouped_path = None;
+        let mut input_type_meta = None;
+        let mut regex_meta = None;
+        let mut error_meta = None;
+        let mut required_msg_meta = None;
+        let mut computed_from = None;
+        let mut output_path = None;
+        let mut compute_with = None;
+        let mut rows_meta = None;
+        let mut empty_label_meta = None;
+        let mut choices_list = None;
+        let mut string_widget = None;
+        let mut omitted_path = None;
+        let mut custom_meta = None;
+        let mut on_paste_meta = None;
+        let mut name_attr_meta = None;
+        let mut masked_display_path = None;
+        let mut precision = None;
+        let mut rounding = None;
+        let mut placeholder_meta = None;
+        let mut event_meta = None;
+        let mut max_items_meta = None;
+        let mut range_meta = None;
+        let mut bound_meta = None;
+        let mut throttle_meta = None;
+        let mut unit_options_list = None;
+        let mut unit_factors_list = None;
+        let nested_metas = yform_nested_metas(&field.attrs)?;
+        // Options which only change how text is entered have nothing to act on in other fields
+        let text_only = |option: &dyn quote::ToTokens, name: &str| {
+            if field_is_string(field) || field_is_option_string(field) {
+                Ok(())
+            } else {
+                Err(syn::Error::new_spanned(
+                    option,
+                    format!("{} only applies to String and Option<String> fields", name),
+                ))
+            }
+        };
+        for nested in nested_metas.iter() {
+            match nested {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("autogrow") => {
+                    text_only(path, "autogrow")?;
+                    attrs.autogrow = true;
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("trim") => {
+                    text_only(path, "trim")?;
+                    attrs.trim = true;
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip") => {
+                    attrs.skip = true;
+                    omitted_path = Some(path.clone());
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("hidden") => {
+                    if !field_is_string(field)
+                        && !field_is_option_string(field)
+                        && !field_is_bool(field)
+                        && !field_is_option_bool(field)
+                        && !field_is_number(field)
+                        && !field_is_option_number(field)
+                    {
+                        return Err(syn::Error::new_spanned(
+                            path,
+                            "hidden only applies to String, bool and number fields, and Options of them",
+                        ));
+                    }
+                    attrs.hidden = true;
+                    omitted_path = Some(path.clone());
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("readonly") => {
+                    attrs.readonly = true;
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("output") => {
+                    attrs.output = true;
+                    output_path = Some(path.clone());
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("masked_display") => {
+                    if !field_is_string(field) && !field_is_option_string(field) {
+                        return Err(syn::Error::new_spanned(
+                            path,
+                            "masked_display only applies to String and Option<String> fields",
+                        ));
+                    }
+                    attrs.masked_display = true;
+                    masked_display_path = Some(path.clone());
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("transform") => {
+                    text_only(nv, "transform")?;
+                    attrs.transform =
+                        match lit_str(&nv.lit)?.as_str() {
+                            "lowercase" => Some(Transform::Lowercase),
+                            "uppercase" => Some(Transform::Uppercase),
+                            _ => return Err(syn::Error::new_spanned(
+                                &nv.lit,
+                                "expected transform to be one of \"lowercase\" or \"uppercase\"",
+                            )),
+                        };
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("transform_with") => {
+                    text_only(nv, "transform_with")?;
+                    attrs.transform_with = Some(lit_path(&nv.lit)?);
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("section") => {
+                    attrs.section = Some(lit_str(&nv.lit)?);
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("options_prop") => {
+                    if !field_is_string(field) && !field_is_option_string(field) {
+                        return Err(syn::Error::new_spanned(
+                            path,
+                            "options_prop only applies to String and Option<String> fields",
+                        ));
+                    }
+                    attrs.options_prop = true;
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("label") => {
+                    attrs.label = Some(lit_str(&nv.lit)?);
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("empty_label") => {
+                    attrs.empty_label = Some(lit_str(&nv.lit)?);
+                    empty_label_meta = Some(nv.clone());
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("placeholder") => {
+                    attrs.placeholder = Some(lit_str(&nv.lit)?);
+                    placeholder_meta = Some(nv.clone());
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("name_attr") => {
+                    let name = lit_str(&nv.lit)?;
+                    if name.is_empty() || name.contains(char::is_whitespace) {
+                        return Err(syn::Error::new_spanned(
+                            &nv.lit,
+                            "name_attr needs a name without any whitespace",
+                        ));
+                    }
+                    attrs.name_attr = Some(name);
+                    name_attr_meta = Some(nv.clone());
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("input_type") => {
+                    if !field_is_string(field) && !field_is_option_string(field) {
+                        return Err(syn::Error::new_spanned(
+                            nv,
+                            "input_type only applies to String and Option<String> fields",
+                        ));
+                    }
+                    let input_type = lit_str(&nv.lit)?;
+                    if !TEXT_INPUT_TYPES.contains(&input_type.as_str()) {
+                        return Err(syn::Error::new_spanned(
+                            &nv.lit,
+                            format!(
+                                "expected input_type to be one of {}",
+                                TEXT_INPUT_TYPES.map(|ty| format!("{:?}", ty)).join(", ")
+                            ),
+                        ));
+                    }
+                    attrs.input_type = Some(input_type);
+                    input_type_meta = Some(nv.clone());
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("regex") => {
+                    if !field_is_string(field) && !field_is_option_string(field) {
+                        return Err(syn::Error::new_spanned(
+                            nv,
+                            "regex only applies to String and Option<String> fields",
+                        ));
+                    }
+                    let pattern = lit_str(&nv.lit)?;
+                    // Bad patterns are caught here rather than panicking in the browser
+                    if let Err(error) = regex::Regex::new(&pattern) {
+                        return Err(syn::Error::new_spanned(&nv.lit, error));
+                    }
+                    attrs.regex = Some(pattern);
+                    regex_meta = Some(nv.clone());
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("error") => {
+                    attrs.error = Some(lit_str(&nv.lit)?);
+                    error_meta = Some(nv.clone());
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("required_msg") => {
+                    attrs.required_msg = Some(lit_str(&nv.lit)?);
+                    required_msg_meta = Some(nv.clone());
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("nested") => {
+                    if field_is_string(field)
+                        || field_is_bool(field)
+                        || field_is_number(field)
+                        || field_is_option(field)
+                        || field_is_string_vec(field)
+                    {
+                        return Err(syn::Error::new_spanned(
+                            path,
+                            "nested only applies to fields holding a struct which derives YForm, or a Vec of them",
+                        ));
+                    }
+                    attrs.nested = true;
+                }
+                NestedMeta::Meta(Meta::NameValue(nv))
+                    if nv.path.is_ident("widget")
+                        && (field_is_string(field) || field_is_option_string(field)) =>
+                {
+                    let widget = lit_str(&nv.lit)?;
+                    if !["textarea", "select", "radio"].contains(&widget.as_str()) {
+                        return Err(syn::Error::new_spanned(
+                            &nv.lit,
+                            "expected widget to be one of \"textarea\", \"select\" or \"radio\"",
+                        ));
+                    }
+                    attrs.textarea = widget == "textarea";
+                    string_widget = Some((widget, nv.clone()));
+                }
+                NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("choices") => {
+                    if !field_is_string(field) && !field_is_option_string(field) {
+                        return Err(syn::Error::new_spanned(
+                            list,
+                            "choices only applies to String and Option<String> fields",
+                        ));
+                    }
+                    if list.nested.is_empty() {
+                        return Err(syn::Error::new_spanned(
+                            list,
+                            "expected at least one choice, i.e. choices(\"small\")",
+                        ));
+                    }
+                    for choice in list.nested.iter() {
+                        let value = match choice {
+                            NestedMeta::Lit(lit) => lit_str(lit)?,
+                            _ => {
+                                return Err(syn::Error::new_spanned(
+                                    choice,
+                                    "expected a string literal",
+                                ))
+                            }
+                        };
+                        if value.is_empty() || attrs.choices.contains(&value) {
+                            return Err(syn::Error::new_spanned(
+                                choice,
+                                "choices have to be distinct and not empty",
+                            ));
+                        }
+                        attrs.choices.push(value);
+                    }
+                    choices_list = Some(list.clone());
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("step") => {
+                    attrs.step = match &nv.lit {
+                        Lit::Int(lit_int) => Some(lit_int.base10_parse()?),
+                        lit => {
+                            return Err(syn::Error::new_spanned(
+                                lit,
+                                "expected a step number, i.e. step = 2",
+                            ))
+                        }
+                    };
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("precision") => {
+                    if !field_is_float(field) {
+                        return Err(syn::Error::new_spanned(
+                            nv,
+                            "precision only applies to f32 and f64 fields",
+                        ));
+                    }
+                    precision = match &nv.lit {
+                        Lit::Int(lit_int) if lit_int.base10_parse::<u32>()? <= 15 => {
+                            Some(lit_int.base10_parse()?)
+                        }
+                        lit => {
+                            return Err(syn::Error::new_spanned(
+                                lit,
+                                "expected a number of decimal places from 0 to 15",
+                            ))
+                        }
+                    };
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("rounding") => {
+                    rounding = match lit_str(&nv.lit)?.as_str() {
+                        "half_up" => Some((Rounding::HalfUp, nv.clone())),
+                        "half_even" => Some((Rounding::HalfEven, nv.clone())),
+                        "down" => Some((Rounding::Down, nv.clone())),
+                        "up" => Some((Rounding::Up, nv.clone())),
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                &nv.lit,
+                                "expected rounding to be one of \"half_up\", \"half_even\", \"down\" or \"up\"",
+                            ))
+                        }
+                    };
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("default") => {
+                    check_default(field, &nv.lit)?;
+                    attrs.default = Some(nv.lit.clone());
+                }
+                NestedMeta::Meta(Meta::NameValue(nv))
+                    if nv.path.is_ident("min_items") || nv.path.is_ident("max_items") =>
+                {
+                    let name = if nv.path.is_ident("min_items") {
+                        "min_items"
+                    } else {
+                        "max_items"
+                    };
+                    if !field_is_vec(field) {
+                        return Err(syn::Error::new_spanned(
+                            nv,
+                            format!("{} only applies to Vec fields", name),
+                        ));
+                    }
+                    let count = match &nv.lit {
+                        Lit::Int(lit_int) => lit_int.base10_parse()?,
+                        lit => {
+                            return Err(syn::Error::new_spanned(
+                                lit,
+                                format!("expected a number of items, i.e. {} = 3", name),
+                            ))
+                        }
+                    };
+                    if name == "min_items" {
+                        attrs.min_items = Some(count);
+                    } else {
+                        attrs.max_items = Some(count);
+                        max_items_meta = Some(nv.clone());
+                    }
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("rows") => {
+                    attrs.rows = match &nv.lit {
+                        Lit::Int(lit_int) if lit_int.base10_parse::<u32>()? > 0 => {
+                            Some(lit_int.base10_parse()?)
+                        }
+                        lit => {
+                            return Err(syn::Error::new_spanned(
+                                lit,
+                                "expected a positive number of rows",
+                            ))
+                        }
+                    };
+                    rows_meta = Some(nv.clone());
+                }
+                NestedMeta::Meta(Meta::NameValue(nv))
+                    if nv.path.is_ident("widget") && field_is_number(field) =>
+                {
+                    if lit_str(&nv.lit)? != "range" {
+                        return Err(syn::Error::new_spanned(
+                            &nv.lit,
+                            "expected widget to be \"range\" on a number field",
+                        ));
+                    }
+                    attrs.range = true;
+                    range_meta = Some(nv.clone());
+                }
+                NestedMeta::Meta(Meta::NameValue(nv))
+                    if nv.path.is_ident("min") || nv.path.is_ident("max") =>
+                {
+                    let bound =
+                        match &nv.lit {
+                            Lit::Int(lit_int) => lit_int.base10_digits().to_string(),
+                            Lit::Float(lit_float) => lit_float.base10_digits().to_string(),
+                            // Negative numbers aren't literals in attributes, so are given as strings
+                            Lit::Str(lit_str) if lit_str.value().parse::<f64>().is_ok() => {
+                                lit_str.value()
+                            }
+                            lit => return Err(syn::Error::new_spanned(
+                                lit,
+                                "expected a number, or a string holding one, i.e. min = \"-10\"",
+                            )),
+                        };
+                    if nv.path.is_ident("min") {
+                        attrs.min = Some(bound);
+                    } else {
+                        attrs.max = Some(bound);
+                    }
+                    bound_meta = Some(nv.clone());
+                }
+                NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("unit_options") => {
+                    if !field_is_float(field) {
+                        return Err(syn::Error::new_spanned(
+                            list,
+                            "unit_options only applies to f32 and f64 fields, and Options of them",
+                        ));
+                    }
+                    for unit in list.nested.iter() {
+                        let value = match unit {
+                            NestedMeta::Lit(lit) => lit_str(lit)?,
+                            _ => {
+                                return Err(syn::Error::new_spanned(
+                                    unit,
+                                    "expected a string literal",
+                                ))
+                            }
+                        };
+                        if value.is_empty() || attrs.unit_options.contains(&value) {
+                            return Err(syn::Error::new_spanned(
+                                unit,
+                                "unit_options have to be distinct and not empty",
+                            ));
+                        }
+                        attrs.unit_options.push(value);
+                    }
+                    unit_options_list = Some(list.clone());
+                }
+                NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("unit_factors") => {
+                    for factor in list.nested.iter() {
+                        let value: f64 = match factor {
+                            NestedMeta::Lit(Lit::Int(lit_int)) => lit_int.base10_parse()?,
+                            NestedMeta::Lit(Lit::Float(lit_float)) => lit_float.base10_parse()?,
+                            _ => 0.0,
+                        };
+                        if !(value > 0.0 && value.is_finite()) {
+                            return Err(syn::Error::new_spanned(
+                                factor,
+                                "expected a positive number",
+                            ));
+                        }
+                        attrs.unit_factors.push(value);
+                    }
+                    unit_factors_list = Some(list.clone());
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("throttle_ms") => {
+                    attrs.throttle_ms = match &nv.lit {
+                        Lit::Int(lit_int) if lit_int.base10_parse::<u32>()? > 0 => {
+                            Some(lit_int.base10_parse()?)
+                        }
+                        lit => {
+                            return Err(syn::Error::new_spanned(
+                                lit,
+                                "expected a positive number of milliseconds",
+                            ))
+                        }
+                    };
+                    throttle_meta = Some(nv.clone());
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("widget") => {
+                    if !field_is_enum(field) && !field_is_option_enum(field) {
+                        return Err(syn::Error::new_spanned(
+                            nv,
+                            "widget only applies to enum, String and Option<String> fields, and \"range\" to number fields",
+                        ));
+                    }
+                    attrs.radio = match lit_str(&nv.lit)?.as_str() {
+                        "select" => false,
+                        "radio" if field_is_time_zone(field) => {
+                            return Err(syn::Error::new_spanned(
+                                &nv.lit,
+                                "Tz fields are offered as a select, there being hundreds of time zones",
+                            ))
+                        }
+                        "radio" => true,
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                &nv.lit,
+                                "expected widget to be \"select\" or \"radio\"",
+                            ))
+                        }
+                    };
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("custom") => {
+                    if field_is_vec(field) {
+                        return Err(syn::Error::new_spanned(
+                            nv,
+                            "custom doesn't apply to Vec fields",
+                        ));
+                    }
+                    attrs.custom = Some(lit_path(&nv.lit)?);
+                    custom_meta = Some(nv.clone());
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("date_text") => {
+                    if !field_is_date(field) && !field_is_option_date(field) {
+                        return Err(syn::Error::new_spanned(
+                            path,
+                            "date_text only applies to NaiveDate and NaiveDateTime fields, and Options of them",
+                        ));
+                    }
+                    attrs.date_text = true;
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("strict_numeric") => {
+                    if !field_is_number(field) && !field_is_option_number(field) {
+                        return Err(syn::Error::new_spanned(
+                            path,
+                            "strict_numeric only applies to number fields",
+                        ));
+                    }
+                    attrs.strict_numeric = true;
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("event") => {
+                    attrs.event = Some(widget_event(&nv.lit, "event")?);
+                    event_meta = Some(nv.clone());
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("no_spinner") => {
+                    if !field_is_number(field) && !field_is_option_number(field) {
+                        return Err(syn::Error::new_spanned(
+                            path,
+                            "no_spinner only applies to number fields",
+                        ));
+                    }
+                    attrs.no_spinner = true;
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("on_paste") => {
+                    if !field_is_string(field)
+                        && !field_is_option_string(field)
+                        && !field_is_number(field)
+                        && !field_is_option_number(field)
+                    {
+                        return Err(syn::Error::new_spanned(
+                            nv,
+                            "on_paste only applies to String, Option<String> and number fields",
+                        ));
+                    }
+                    attrs.on_paste = Some(lit_path(&nv.lit)?);
+                    on_paste_meta = Some(nv.clone());
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("submit_with") => {
+                    attrs.submit_with = Some(lit_path(&nv.lit)?);
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("encrypt_with") => {
+                    if !field_is_string(field) && !field_is_option_string(field) {
+                        return Err(syn::Error::new_spanned(
+                            nv,
+                            "encrypt_with only applies to String and Option<String> fields",
+                        ));
+                    }
+                    attrs.encrypt_with = Some(lit_path(&nv.lit)?);
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("validate") => {
+                    attrs.validate = Some(lit_path(&nv.lit)?);
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("format") => {
+                    if !field_is_number(field) && !field_is_option_number(field) {
+                        return Err(syn::Error::new_spanned(
+                            nv,
+                            "format only applies to number fields",
+                        ));
+                    }
+                    attrs.format = match lit_str(&nv.lit)?.as_str() {
+                        "thousands" => Some(NumberFormat::Thousands),
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                &nv.lit,
+                                "expected format to be \"thousands\"",
+                            ))
+                        }
+                    };
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("required_if") => {
+                    let lit_str = match &nv.lit {
+                        Lit::Str(lit_str) => lit_str,
+                        lit => {
+                            return Err(syn::Error::new_spanned(lit, "expected a string literal"))
+                        }
+                    };
+                    // Keep the span of the literal so that a misspelled field points back at it
+                    let mut other: syn::Ident = lit_str
+                        .parse()
+                        .map_err(|_| syn::Error::new_spanned(lit_str, "expected a field name"))?;
+                    other.set_span(lit_str.span());
+                    attrs.required_if = Some(other);
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("computed_from") => {
+                    computed_from = Some((lit_idents(&nv.lit)?, nv.clone()));
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("compute_with") => {
+                    compute_with = Some((lit_path(&nv.lit)?, nv.clone()));
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("grouped") => {
+                    attrs.grouped = true;
+                    grouped_path = Some(path.clone());
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("collapsed") => {
+                    attrs.collapsed = true;
+                    collapsed_path = Some(path.clone());
+                }
+                _ => return Err(unrecognized(nested)),
+            }
+        }
+        // Fields which aren't shown have no input for any other option to act on, though a hidden
+        // input still takes a name
+        let hidden_name = attrs.hidden && name_attr_meta.is_some();
+        if let Some(path) = omitted_path.filter(|_| nested_metas.len() > 1 + hidden_name as usize) {
+            return Err(syn::Error::new_spanned(
+                path,
+                "skip and hidden can't be combined with other yform options",
+            ));
+        }
+        if let (Some(path), None) = (collapsed_path, &attrs.section) {
+            return Err(syn::Error::new_spanned(
+                path,
+                "collapsed only applies to fields with a section",
+            ));
+        }
+        attrs.computed = match (computed_from, compute_with) {
+            (Some((dependencies, _)), Some((path, _))) => Some((dependencies, path)),
+            (Some((_, nv)), None) => {
+                return Err(syn::Error::new_spanned(
+                    nv,
+                    "computed_from needs a compute_with function to compute the field",
+                ))
+            }
+            (None, Some((_, nv))) => {
+                return Err(syn::Error::new_spanned(
+                    nv,
+                    "compute_with needs the computed_from fields to compute the field from",
+                ))
+            }
+            (None, None) => None,
+        };
+        if let (Some(path), None) = (&output_path, &attrs.computed) {
+            return Err(syn::Error::new_spanned(
+                path,
+                "output only applies to fields with computed_from and compute_with",
+            ));
+        }
+        // A custom renderer draws the whole input, so nothing else can change how it renders
+        if let (Some(nv), true) = (&name_attr_meta, attrs.nested) {
+            return Err(syn::Error::new_spanned(
+                nv,
+                "name_attr only applies to fields rendered as inputs, not nested forms",
+            ));
+        }
+        if let Some(nv) = custom_meta {
+            if attrs.is_textarea()
+                || attrs.options_prop
+                || !attrs.choices.is_empty()
+                || string_widget.is_some()
+                || attrs.radio
+                || input_type_meta.is_some()
+                || attrs.format.is_some()
+                || attrs.placeholder.is_some()
+                || attrs.strict_numeric
+                || attrs.no_spinner
+                || attrs.range
+                || !attrs.unit_options.is_empty()
+                || attrs.date_text
+                || attrs.output
+                || event_meta.is_some()
+                || attrs.nested
+                || name_attr_meta.is_some()
+            {
+                return Err(syn::Error::new_spanned(
+                    nv,
+                    "custom can't be combined with options which change how the input renders",
+                ));
+            }
+        }
+        // Pastes are only cleaned up on the way into something typed in
+        if let Some(nv) = on_paste_meta {
+            if attrs.options_prop || !attrs.choices.is_empty() || attrs.custom.is_some() {
+                return Err(syn::Error::new_spanned(
+                    nv,
+                    "on_paste only applies to text and number inputs, so can't be combined with choices, options_prop or custom",
+                ));
+            }
+        }
+        if let Some(list) = choices_list {
+            if attrs.is_textarea()
+                || attrs.options_prop
+                || attrs.regex.is_some()
+                || input_type_meta.is_some()
+            {
+                return Err(syn::Error::new_spanned(
+                    list,
+                    "choices can't be combined with textareas, options_prop, regex or input_type",
+                ));
+            }
+        }
+        match string_widget {
+            Some((widget, nv)) if widget != "textarea" && attrs.choices.is_empty() => {
+                return Err(syn::Error::new_spanned(
+                    nv,
+                    "widget = \"select\" or \"radio\" on a String field needs choices to offer",
+                ));
+            }
+            Some((widget, _)) => attrs.radio = widget == "radio",
+            // Choices are offered as radios unless a select is asked for, while enums keep their
+            // own widget option
+            None if !attrs.choices.is_empty() => attrs.radio = true,
+            None => {}
+        }
+        if let Some(nv) = input_type_meta.filter(|_| attrs.is_textarea() || attrs.options_prop) {
+            return Err(syn::Error::new_spanned(
+                nv,
+                "input_type can't be combined with textareas or options_prop",
+            ));
+        }
+        // Only selects which can be left empty start with an empty choice
+        let empty_choice = !attrs.radio
+            && (attrs.options_prop || !attrs.choices.is_empty() || field_is_option_enum(field));
+        if let (Some(nv), false) = (empty_label_meta, empty_choice) {
+            return Err(syn::Error::new_spanned(
+                nv,
+                "empty_label only applies to selects with an empty choice, i.e. on an Option<enum> field or with choices or options_prop",
+            ));
+        }
+        if let (Some(nv), Some(min_items), Some(max_items)) =
+            (max_items_meta, attrs.min_items, attrs.max_items)
+        {
+            if min_items > max_items {
+                return Err(syn::Error::new_spanned(
+                    nv,
+                    "max_items can't be fewer than min_items",
+                ));
+            }
+        }
+        if let (Some(nv), false) = (rows_meta, attrs.is_textarea()) {
+            return Err(syn::Error::new_spanned(
+                nv,
+                "rows only applies to textareas, i.e. with widget = \"textarea\" or autogrow",
+            ));
+        }
+        attrs.precision = match (precision, rounding) {
+            (Some(precision), Some((rounding, _))) => Some((precision, rounding)),
+            (Some(precision), None) => Some((precision, Rounding::HalfUp)),
+            (None, Some((_, nv))) => {
+                return Err(syn::Error::new_spanned(
+                    nv,
+                    "rounding needs a precision to round to",
+                ))
+            }
+            (None, None) => None,
+        };
+        // Only inputs which are typed into show a placeholder
+        if let Some(nv) = placeholder_meta {
+            let typed = field_is_string(field)
+                || field_is_option_string(field)
+                || field_is_string_vec(field)
+                || field_is_number(field)
+                || field_is_option_number(field)
+                || field_is_date(field)
+                || field_is_option_date(field);
+            if !typed || attrs.options_prop || !attrs.choices.is_empty() || attrs.nested {
+                attrs.placeholder = None;
+                attrs.warnings.push((
+                    syn::spanned::Spanned::span(&nv),
+                    "placeholder only applies to fields typed into, i.e. not checkboxes, choices or nested forms, so it is ignored".to_string(),
+                ));
+            }
+        }
+        // The mask takes the place of a single input, which choices and custom renderers don't have
+        if let Some(path) = masked_display_path {
+            if attrs.options_prop || !attrs.choices.is_empty() || attrs.custom.is_some() {
+                return Err(syn::Error::new_spanned(
+                    path,
+                    "masked_display can't be combined with choices, options_prop or custom",
+                ));
+            }
+        }
+        // Each unit needs a factor to convert it into the first one, which the value is stored in
+        match (unit_options_list, unit_factors_list) {
+            (Some(list), None) => {
+                return Err(syn::Error::new_spanned(
+                    list,
+                    "unit_options needs unit_factors saying how many of the first unit each unit is, i.e. unit_factors(1, 0.45359237)",
+                ))
+            }
+            (None, Some(list)) => {
+                return Err(syn::Error::new_spanned(
+                    list,
+                    "unit_factors needs unit_options naming the units",
+                ))
+            }
+            (Some(_), Some(list)) if list.nested.len() != attrs.unit_options.len() => {
+                return Err(syn::Error::new_spanned(
+                    list,
+                    "unit_factors needs one factor for each of the unit_options",
+                ))
+            }
+            (Some(_), Some(list)) if attrs.unit_factors[0] != 1.0 => {
+                return Err(syn::Error::new_spanned(
+                    list,
+                    "the first of unit_factors has to be 1, as values are stored in the first unit",
+                ))
+            }
+            (Some(list), Some(_)) if attrs.range || attrs.format.is_some() => {
+                return Err(syn::Error::new_spanned(
+                    list,
+                    "unit_options can't be combined with widget = \"range\" or format",
+                ))
+            }
+            _ => {}
+        }
+        // A slider has no text to format, type or paste, and only a slider has bounds to slide between
+        if let Some(nv) = range_meta {
+            if attrs.format.is_some()
+                || attrs.no_spinner
+                || attrs.strict_numeric
+                || attrs.on_paste.is_some()
+                || attrs.placeholder.is_some()
+            {
+                return Err(syn::Error::new_spanned(
+                    nv,
+                    "widget = \"range\" can't be combined with format, no_spinner, strict_numeric, on_paste or placeholder",
+                ));
+            }
+        }
+        if let (Some(nv), false) = (bound_meta, attrs.range) {
+            return Err(syn::Error::new_spanned(
+                nv,
+                "min and max only apply to fields with widget = \"range\"",
+            ));
+        }
+        // Only sliders and inputs storing their value on input events update as they change
+        if let (Some(nv), false) = (
+            throttle_meta,
+            attrs.range || attrs.event == Some(WidgetEvent::Input),
+        ) {
+            return Err(syn::Error::new_spanned(
+                nv,
+                "throttle_ms only applies to fields updated as they change, i.e. with widget = \"range\" or event = \"input\"",
+            ));
+        }
+        // Only inputs picked from are given an event, and a select has no click for each choice
+        if let Some(nv) = event_meta {
+            let checkbox = field_is_bool(field) || field_is_option_bool(field);
+            let select = !attrs.radio
+                && (attrs.options_prop
+                    || !attrs.choices.is_empty()
+                    || field_is_enum(field)
+                    || field_is_option_enum(field));
+            if !checkbox && !attrs.radio && !select {
+                return Err(syn::Error::new_spanned(
+                    nv,
+                    "event only applies to checkboxes, radios and selects",
+                ));
+            }
+            if select && attrs.event == Some(WidgetEvent::Click) {
+                return Err(syn::Error::new_spanned(
+                    &nv.lit,
+                    "a select has no click for each choice, so expected event to be \"change\" or \"input\"",
+                ));
+            }
+        }
+        // Option and Vec fields are only ever required through required_if
+        if let Some(nv) = required_msg_meta.filter(|_| {
+            attrs.required_if.is_none() && (field_is_option(field) || field_is_vec(field))
+        }) {
+            return Err(syn::Error::new_spanned(
+                nv,
+                "required_msg only applies to required fields, so an Option or Vec needs required_if",
+            ));
+        }
+        if let (Some(nv), None) = (error_meta, &attrs.regex) {
+            return Err(syn::Error::new_spanned(
+                nv,
+                "error needs a regex for the value to match",
+            ));
+        }
+        if let (Some(nv), true) = (regex_meta, attrs.options_prop) {
+            return Err(syn::Error::new_spanned(
+                nv,
+                "regex can't be combined with options_prop",
+            ));
+        }
+        if let (Some(path), false) = (grouped_path, attrs.options_prop) {
+            return Err(syn::Error::new_spanned(
+                path,
+                "grouped only applies to fields with options_prop",
+            ));
+        }
+        Ok(attrs)
+    }
+
+    /// Whether the field is rendered as a textarea, either growing to fit or not
+    pub fn is_textarea(&self) -> bool {
+        self.autogrow || self.textarea
+    }
+
+    /// Get the options for a field whose attributes have already been checked with `parse`
+    pub fn from_field(field: &syn::Field) -> FieldAttrs {
+        Self::parse(field).expect("yform attributes are validated before expansion")
+    }
+}
+
+/// Anchor a regex so that it has to match the whole value, as with the HTML pattern attribute
+pub fn anchored_pattern(pattern: &str) -> String {
+    format!("^(?:{})$", pattern)
+}
+
+/// The options a user can set on the struct itself with `#[yform(...)]`
+#[derive(Default)]
+pub struct StructAttrs {
+    /// Generate wasm-bindgen tests which exercise the form in a browser
+    pub wasm_tests: bool,
+    /// Generate a component which mounts the form with sample values
+    pub preview: bool,
+    /// Generate a `use_<name>_form` hook alongside the struct component, with `emit = "both"`
+    pub hook: bool,
+    /// Keep the struct behind an Rc<RefCell<..>> rather than cloning it in and out of the form
+    pub shared_state: bool,
+    /// Compare props by hand so the struct doesn't need to implement PartialEq
+    pub no_partial_eq: bool,
+    /// Use this name for the component instead of appending Form to the struct name
+    pub name: Option<syn::Ident>,
+    /// Show a read only summary of the values to confirm before onsubmit is called
+    pub review: bool,
+    /// Named submit actions, each rendered as its own button, as (variant, button label) pairs
+    pub actions: Vec<(syn::Ident, String)>,
+    /// Show labels as placeholders on screens narrower than this width, i.e. "600px"
+    pub label_placeholders_below: Option<String>,
+    /// Whether the buttons go above the fields, below them, or both
+    pub button_position: ButtonPosition,
+    /// How the buttons are aligned, one of "left", "center" or "right"
+    pub align: Option<String>,
+    /// Mark the rows of buttons as action bars, for keeping them in view with position: sticky
+    pub action_bar: bool,
+    /// Save each field on its own as its inline edit is confirmed, rather than submitting the form
+    pub save_individually: bool,
+    /// A user provided function checking the whole struct before it is submitted, which gives the
+    /// errors to show by each field
+    pub validate: Option<syn::Path>,
+    /// A ready made layout and behaviour the form follows
+    pub preset: Option<Preset>,
+    /// Implement Default for the struct with the same values as `new()`
+    pub derive_default: bool,
+    /// Leave fields of unsupported types out of the form, with a warning, rather than failing
+    pub skip_unsupported: bool,
+    /// Render every computed field in an `<output>` element
+    pub computed_outputs: bool,
+    /// The struct implements serde::Serialize, so the debug panel and copy button can show it as
+    /// JSON
+    pub serialize: bool,
+    /// The event checkboxes store their value on, unless a field sets its own
+    pub checkbox_event: WidgetEvent,
+    /// The event radios store their value on, unless a field sets its own
+    pub radio_event: WidgetEvent,
+    /// The event selects store their value on, unless a field sets its own
+    pub select_event: WidgetEvent,
+}
+
+impl StructAttrs {
+    /// Whether the form is a compact search form, updating as the user types
+    pub fn is_search(&self) -> bool {
+        self.preset == Some(Preset::Search)
+    }
+
+    /// Whether the form is a login form, marked up for password managers
+    pub fn is_login(&self) -> bool {
+        self.preset == Some(Preset::Login)
+    }
+
+    /// The event a checkbox, radio or select field stores its value on
+    pub fn widget_event(&self, field: &syn::Field, attrs: &FieldAttrs) -> WidgetEvent {
+        attrs
+            .event
+            .unwrap_or(if field_is_bool(field) || field_is_option_bool(field) {
+                self.checkbox_event
+            } else if attrs.radio {
+                self.radio_event
+            } else {
+                self.select_event
+            })
+    }
+
+    /// Collect every `#[yform(...)]` attribute on the struct into one set of options. Malformed
+    /// options are reported with the span of the offending tokens.
+    pub fn from_ast(ast: &syn::DeriveInput) -> syn::Result<StructAttrs> {
+        let mut attrs = StructAttrs::default();
+        let mut save_individually_path = None;
+        let mut preset_lit = None;
+        for nested in yform_nested_metas(&ast.attrs)? {
+            match &nested {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("save_individually") => {
+                    attrs.save_individually = true;
+                    save_individually_path = Some(path.clone());
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("wasm_tests") => {
+                    attrs.wasm_tests = true;
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("derive_default") => {
+                    attrs.derive_default = true;
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("computed_outputs") => {
+                    attrs.computed_outputs = true;
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip_unsupported") => {
+                    attrs.skip_unsupported = true;
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("serialize") => {
+                    attrs.serialize = true;
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("checkbox_event") => {
+                    attrs.checkbox_event = widget_event(&nv.lit, "checkbox_event")?;
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("radio_event") => {
+                    attrs.radio_event = widget_event(&nv.lit, "radio_event")?;
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("select_event") => {
+                    attrs.select_event = match widget_event(&nv.lit, "select_event")? {
+                        WidgetEvent::Click => {
+                            return Err(syn::Error::new_spanned(
+                                &nv.lit,
+                                "a select has no click for each choice, so expected select_event to be \"change\" or \"input\"",
+                            ))
+                        }
+                        event => event,
+                    };
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("preview") => {
+                    attrs.preview = true;
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("shared_state") => {
+                    attrs.shared_state = true;
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("review") => {
+                    attrs.review = true;
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("action_bar") => {
+                    attrs.action_bar = true;
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("no_partial_eq") => {
+                    attrs.no_partial_eq = true;
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("name") => {
+                    attrs.name = Some(lit_ident(&nv.lit)?);
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("validate") => {
+                    attrs.validate = Some(lit_path(&nv.lit)?);
+                }
+                NestedMeta::Meta(Meta::NameValue(nv))
+                    if nv.path.is_ident("label_placeholders_below") =>
+                {
+                    let width = lit_str(&nv.lit)?;
+                    // The width is pasted into a media query, so it mustn't be able to end it
+                    if width.trim().is_empty() || width.contains(['{', '}', ';', ')']) {
+                        return Err(syn::Error::new_spanned(
+                            &nv.lit,
+                            "expected a CSS width, i.e. \"600px\"",
+                        ));
+                    }
+                    attrs.label_placeholders_below = Some(width);
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("preset") => {
+                    attrs.preset = match lit_str(&nv.lit)?.as_str() {
+                        "search" => Some(Preset::Search),
+                        "login" => Some(Preset::Login),
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                &nv.lit,
+                                "expected preset to be one of \"search\" or \"login\"",
+                            ))
+                        }
+                    };
+                    preset_lit = Some(nv.lit.clone());
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("emit") => {
+                    attrs.hook = match lit_str(&nv.lit)?.as_str() {
+                        "struct" => false,
+                        "both" => true,
+                        "hook" => {
+                            return Err(syn::Error::new_spanned(
+                                &nv.lit,
+                                "the hook renders the struct component, so it can't be emitted on its own, use emit = \"both\"",
+                            ))
+                        }
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                &nv.lit,
+                                "expected emit to be one of \"struct\" or \"both\"",
+                            ))
+                        }
+                    };
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("button_position") => {
+                    attrs.button_position = match lit_str(&nv.lit)?.as_str() {
+                        "top" => ButtonPosition::Top,
+                        "bottom" => ButtonPosition::Bottom,
+                        "both" => ButtonPosition::Both,
+                        _ => return Err(syn::Error::new_spanned(
+                            &nv.lit,
+                            "expected button_position to be one of \"top\", \"bottom\" or \"both\"",
+                        )),
+                    };
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("align") => {
+                    let align = lit_str(&nv.lit)?;
+                    if !["left", "center", "right"].contains(&align.as_str()) {
+                        return Err(syn::Error::new_spanned(
+                            &nv.lit,
+                            "expected align to be one of \"left\", \"center\" or \"right\"",
+                        ));
+                    }
+                    attrs.align = Some(align);
+                }
+                NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("actions") => {
+                    if list.nested.is_empty() {
+                        return Err(syn::Error::new_spanned(
+                            list,
+                            "expected at least one action, i.e. actions(\"Approve\")",
+                        ));
+                    }
+                    for action in list.nested.iter() {
+                        let (variant, label) = action_variant(action)?;
+                        if attrs
+                            .actions
+                            .iter()
+                            .any(|(existing, _)| *existing == variant)
+                        {
+                            return Err(syn::Error::new_spanned(action, "duplicate action"));
+                        }
+                        attrs.actions.push((variant, label));
+                    }
+                }
+                _ => return Err(unrecognized(&nested)),
+            }
+        }
+        if let Some(path) = save_individually_path {
+            if attrs.review || !attrs.actions.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    path,
+                    "save_individually can't be combined with review or actions, which submit the whole form",
+                ));
+            }
+        }
+        if let Some(lit) = preset_lit {
+            if attrs.is_search()
+                && (attrs.review || !attrs.actions.is_empty() || attrs.save_individually)
+            {
+                return Err(syn::Error::new_spanned(
+                    lit,
+                    "the search preset can't be combined with review, actions or save_individually",
+                ));
+            }
+        }
+        Ok(attrs)
+    }
+}
+
+/// The options a user can set on a variant of a `YFormSelect` enum with `#[yform(...)]`
+#[derive(Default)]
+pub struct VariantAttrs {
+    /// Replaces the Title Cased variant name as the label
+    pub label: Option<String>,
+}
+
+impl VariantAttrs {
+    /// Collect every `#[yform(...)]` attribute on the variant into one set of options
+    pub fn parse(variant: &syn::Variant) -> syn::Result<VariantAttrs> {
+        let mut attrs = VariantAttrs::default();
+        for nested in yform_nested_metas(&variant.attrs)? {
+            match &nested {
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("label") => {
+                    attrs.label = Some(lit_str(&nv.lit)?);
+                }
+                _ => return Err(unrecognized(&nested)),
+            }
+        }
+        Ok(attrs)
+    }
+}
+
+// Flatten every `#[yform(a, b = "c")]` attribute into its list of items, rejecting any option given
+// more than once
+fn yform_nested_metas(attrs: &[syn::Attribute]) -> syn::Result<Vec<NestedMeta>> {
+    let mut nested_metas = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("yform")) {
+        match attr.parse_meta()? {
+            Meta::List(list) => nested_metas.extend(list.nested),
+            meta => {
+                return Err(syn::Error::new_spanned(
+                    meta,
+                    "expected yform attribute of the form #[yform(...)]",
+                ))
+            }
+        }
+    }
+    check_duplicates(&nested_metas)?;
+    Ok(nested_metas)
+}
+
+// Get the enum variant and button label for one of `actions("Approve", "Request changes")`
+fn action_variant(action: &NestedMeta) -> syn::Result<(syn::Ident, String)> {
+    let label = match action {
+        NestedMeta::Lit(lit) => lit_str(lit)?,
+        _ => return Err(syn::Error::new_spanned(action, "expected a string literal")),
+    };
+    let variant = syn::parse_str(&label.to_case(Case::Pascal)).map_err(|_| {
+        syn::Error::new_spanned(
+            action,
+            "expected an action name which makes a valid identifier",
+        )
+    })?;
+    Ok((variant, label))
+}
+
+fn unrecognized(nested: &NestedMeta) -> syn::Error {
+    syn::Error::new_spanned(nested, "unrecognized yform option")
+}
+
+// Check that a field's default is a literal of its type, i.e. a string for a String field or the
+// value of one of the variants for an enum. Dates are given as strings, and parsed in new().
+fn check_default(field: &syn::Field, lit: &Lit) -> syn::Result<()> {
+    let fits = match lit {
+        Lit::Str(_) => {
+            field_is_string(field)
+                || field_is_option_string(field)
+                || field_is_enum(field)
+                || field_is_option_enum(field)
+                || field_is_date(field)
+                || field_is_option_date(field)
+        }
+        Lit::Bool(_) => field_is_bool(field) || field_is_option_bool(field),
+        Lit::Int(_) => field_is_number(field) || field_is_option_number(field),
+        Lit::Float(_) => field_is_float(field),
+        _ => false,
+    };
+    if fits {
+        Ok(())
+    } else {
+        Err(syn::Error::new_spanned(
+            lit,
+            "default has to be a literal of the field's type, i.e. a string for String, enum and date fields",
+        ))
+    }
+}
+
+// Report options given more than once, which would otherwise have the last one silently win, i.e.
+// across two #[yform(...)] attributes on the same field
+fn check_duplicates(nested_metas: &[NestedMeta]) -> syn::Result<()> {
+    let mut seen = Vec::new();
+    for nested in nested_metas {
+        let path = match nested {
+            NestedMeta::Meta(meta) => meta.path(),
+            NestedMeta::Lit(_) => continue,
+        };
+        if seen.contains(&path) {
+            return Err(syn::Error::new_spanned(
+                nested,
+                format!(
+                    "{} is given more than once",
+                    quote::ToTokens::to_token_stream(path)
+                ),
+            ));
+        }
+        seen.push(path);
+    }
+    Ok(())
+}
+
+// Get the value of a string literal, i.e. the `lowercase` in `transform = "lowercase"`
+fn lit_str(lit: &Lit) -> syn::Result<String> {
+    match lit {
+        Lit::Str(lit_str) => Ok(lit_str.value()),
+        _ => Err(syn::Error::new_spanned(lit, "expected a string literal")),
+    }
+}
+
+// Parse the event an input stores its value on, i.e. `event = "click"`, naming the option in the
+// error
+fn widget_event(lit: &Lit, option: &str) -> syn::Result<WidgetEvent> {
+    match lit_str(lit)?.as_str() {
+        "change" => Ok(WidgetEvent::Change),
+        "input" => Ok(WidgetEvent::Input),
+        "click" => Ok(WidgetEvent::Click),
+        _ => Err(syn::Error::new_spanned(
+            lit,
+            format!(
+                "expected {} to be one of \"change\", \"input\" or \"click\"",
+                option
+            ),
+        )),
+    }
+}
+
+// Parse a string literal holding an identifier, i.e. `name = "SignupForm"`
+fn lit_ident(lit: &Lit) -> syn::Result<syn::Ident> {
+    match lit {
+        Lit::Str(lit_str) => lit_str
+            .parse()
+            .map_err(|_| syn::Error::new_spanned(lit, "expected an identifier")),
+        _ => Err(syn::Error::new_spanned(lit, "expected a string literal")),
+    }
+}
+
+// Parse a string literal holding a comma separated list of identifiers, i.e.
+// `computed_from = "quantity, unit_price"`. The identifiers keep the span of the literal so that
+// misspelled field names point back at it.
+fn lit_idents(lit: &Lit) -> syn::Result<Vec<syn::Ident>> {
+    let lit_str = match lit {
+        Lit::Str(lit_str) => lit_str,
+        _ => return Err(syn::Error::new_spanned(lit, "expected a string literal")),
+    };
+    let idents = lit_str
+        .value()
+        .split(',')
+        .map(|name| syn::parse_str::<syn::Ident>(name.trim()))
+        .collect::<syn::Result<Vec<_>>>()
+        .map_err(|_| syn::Error::new_spanned(lit, "expected a comma separated list of fields"))?;
+    Ok(idents
+        .into_iter()
+        .map(|mut ident| {
+            ident.set_span(lit_str.span());
+            ident
+        })
+        .collect())
+}
+
+// Parse a string literal holding a path, i.e. `transform_with = "path::to::fn"`
+fn lit_path(lit: &Lit) -> syn::Result<syn::Path> {
+    match lit {
+        Lit::Str(lit_str) => lit_str
+            .parse()
+            .map_err(|_| syn::Error::new_spanned(lit, "expected a path to a function")),
+        _ => Err(syn::Error::new_spanned(lit, "expected a string literal")),
+    }
+}
@@ -0,0 +1,15 @@
+use proc_macro::TokenStream;
+use quote::quote;
+
+/// What the given derive generates for the struct, pretty printed into a string literal. Errors
+/// the derive reports are printed along with everything else, so they can be pinned too.
+pub fn expand(input: TokenStream, derive: fn(TokenStream) -> TokenStream) -> TokenStream {
+    let expanded = proc_macro2::TokenStream::from(derive(input));
+    match syn::parse2::<syn::File>(expanded) {
+        Ok(file) => {
+            let text = prettyplease::unparse(&file);
+            quote! { #text }.into()
+        }
+        Err(error) => error.to_compile_error().into(),
+    }
+}
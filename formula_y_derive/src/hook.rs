@@ -0,0 +1,87 @@
+use convert_case::{Case, Casing};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Ident;
+
+use crate::attrs::StructAttrs;
+use crate::get_state_type;
+
+/// Generate a use_<name>_form hook for function components, which keeps the form's value in a
+/// use_state and renders the struct component controlled by it, so code bases moving from struct
+/// to function components can use the same form from both while they migrate.
+pub fn hook(
+    input_struct_ident: &Ident,
+    component_ident: &Ident,
+    struct_attrs: &StructAttrs,
+) -> TokenStream {
+    let state_type = get_state_type(input_struct_ident, struct_attrs);
+    let hook_ident = Ident::new(
+        &format!("use_{}", component_ident).to_case(Case::Snake),
+        component_ident.span(),
+    );
+    let handle_ident = Ident::new(
+        &format!("Use{}Handle", component_ident),
+        component_ident.span(),
+    );
+    let hook_doc = format!(
+        "Keep a `{}`'s value in the calling function component, starting from `init`. Render the form with the handle's `view()`.",
+        component_ident
+    );
+    let handle_doc = format!(
+        "The value behind a `{}` rendered from a function component, from `{}`",
+        component_ident, hook_ident
+    );
+
+    quote! {
+        #[doc = #handle_doc]
+        #[derive(Clone)]
+        pub struct #handle_ident {
+            value: UseStateHandle<#state_type>,
+            submitted: UseStateHandle<Option<#state_type>>,
+        }
+
+        impl #handle_ident {
+            /// The form's value as it stands
+            pub fn value(&self) -> #state_type {
+                (*self.value).clone()
+            }
+
+            /// Replace the value the form shows
+            pub fn set(&self, value: #state_type) {
+                self.value.set(value);
+            }
+
+            /// The value last submitted, if the form has been
+            pub fn submitted(&self) -> Option<#state_type> {
+                (*self.submitted).clone()
+            }
+
+            /// Keeps the handle's value up to date, for rendering the form with props of your own
+            pub fn onchange(&self) -> Callback<#state_type> {
+                let value = self.value.clone();
+                Callback::from(move |new_value| value.set(new_value))
+            }
+
+            /// Keeps the value last submitted, for rendering the form with props of your own
+            pub fn onsubmit(&self) -> Callback<#state_type> {
+                let submitted = self.submitted.clone();
+                Callback::from(move |value| submitted.set(Some(value)))
+            }
+
+            /// The form, showing the handle's value
+            pub fn view(&self) -> Html {
+                html! {
+                    <#component_ident value={self.value()} onchange={self.onchange()} onsubmit={self.onsubmit()} />
+                }
+            }
+        }
+
+        #[doc = #hook_doc]
+        pub fn #hook_ident(init: impl FnOnce() -> #state_type) -> #handle_ident {
+            #handle_ident {
+                value: use_state(init),
+                submitted: use_state(|| None),
+            }
+        }
+    }
+}
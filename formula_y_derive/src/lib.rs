@@ -0,0 +1,5595 @@
+//! The derive macro behind `formula_y`. Depend on `formula_y` itself rather than this crate, since
+//! the generated code relies on the items it re-exports from `formula_y::prelude`.
+
+use attrs::{ButtonPosition, FieldAttrs, Rounding, StructAttrs, Transform, WidgetEvent};
+use convert_case::{Case, Casing};
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, DeriveInput};
+use util::{
+    append_to_ident, check_field_type, enum_type, field_is_bool, field_is_date, field_is_datetime,
+    field_is_enum, field_is_float, field_is_nested, field_is_nested_vec, field_is_number,
+    field_is_option, field_is_option_bool, field_is_option_date, field_is_option_enum,
+    field_is_option_number, field_is_option_string, field_is_string, field_is_string_vec,
+    field_is_time_zone, field_is_vec, field_name, get_struct_fields, item_type,
+};
+
+// Parsing for the #[yform(...)] helper attribute
+mod attrs;
+
+// Generated helpers for testing derived forms
+mod testing;
+
+// Generated preview components for form catalogs
+mod preview;
+
+// Generated hooks rendering the form from function components
+mod hook;
+
+// Generated review step shown before submitting
+mod review;
+
+// The companion derive for enums offered as choices
+mod select;
+
+// Generated walks over a struct's fields and values
+mod visit;
+
+// Printing what the derive generates, for snapshot tests of its output
+#[cfg(feature = "expand")]
+mod expand;
+
+// Utilities
+mod util;
+
+// Generate the variant naming each field in the field enum passed to field_policy.
+// first_name -> FirstName
+fn get_field_variant_ident(field: &syn::Field) -> syn::Ident {
+    let field_ident = field.ident.clone().unwrap();
+    syn::Ident::new(
+        &field_name(&field_ident).to_case(Case::UpperCamel),
+        field_ident.span(),
+    )
+}
+
+// Generate the prop holding the choices for a field with #[yform(options_prop)].
+// project -> project_options
+fn get_options_prop_ident(field: &syn::Field) -> syn::Ident {
+    let field_ident = field.ident.clone().unwrap();
+    syn::Ident::new(
+        &format!("{}_options", field_name(&field_ident)),
+        field_ident.span(),
+    )
+}
+
+// Generate the method recalculating a computed field.
+// total -> compute_total
+fn get_compute_method_ident(field_ident: &syn::Ident) -> syn::Ident {
+    syn::Ident::new(
+        &format!("compute_{}", field_name(field_ident)),
+        field_ident.span(),
+    )
+}
+
+// Generate the method giving the error shown while a field doesn't match its regex.
+// postcode -> postcode_regex_error
+fn get_regex_error_method_ident(field: &syn::Field, span_ident: &syn::Ident) -> syn::Ident {
+    let field_ident = field.ident.clone().unwrap();
+    let method_name = format!("{}_regex_error", field_name(&field_ident));
+    syn::Ident::new(&method_name, span_ident.span())
+}
+
+// Generate the name of the fn holding a field's compiled regex. zip -> zip_regex
+fn get_regex_ident(field: &syn::Field) -> syn::Ident {
+    let field_ident = field.ident.clone().unwrap();
+    syn::Ident::new(
+        &format!("{}_regex", field_name(&field_ident)),
+        field_ident.span(),
+    )
+}
+
+// Generate the method giving the error a field's validate fn returns for its value.
+// password -> password_validate_error
+fn get_validate_error_method_ident(field: &syn::Field, span_ident: &syn::Ident) -> syn::Ident {
+    let field_ident = field.ident.clone().unwrap();
+    let method_name = format!("{}_validate_error", field_name(&field_ident));
+    syn::Ident::new(&method_name, span_ident.span())
+}
+
+// Generate the names of the methods telling whether a field is flagged as required or invalid.
+// first_name -> first_name_is_flagged
+fn get_flagged_method_ident(field: &syn::Field, span_ident: &syn::Ident) -> syn::Ident {
+    let field_ident = field.ident.clone().unwrap();
+    let method_name = format!("{}_is_flagged", field_name(&field_ident));
+    syn::Ident::new(&method_name, span_ident.span())
+}
+
+// Generate the MSG variants responsible for updating each field.
+// first_name -> UpdateFirstName
+fn get_update_field_msg_variant_ident(field: &syn::Field, span_ident: &syn::Ident) -> syn::Ident {
+    let field_ident = field.ident.clone().unwrap();
+    let msg_variant = format!("update_{}", field_name(&field_ident)).to_case(Case::UpperCamel);
+    syn::Ident::new(&msg_variant, span_ident.span())
+}
+
+// Generate the MSG variants responsible for updating a single item of each Vec field.
+// tags -> UpdateTagsItem
+fn get_update_item_msg_variant_ident(field: &syn::Field, span_ident: &syn::Ident) -> syn::Ident {
+    let field_ident = field.ident.clone().unwrap();
+    let msg_variant = format!("update_{}_item", field_name(&field_ident)).to_case(Case::UpperCamel);
+    syn::Ident::new(&msg_variant, span_ident.span())
+}
+
+// Generate the names of the methods converting an input's value into its update msg.
+// first_name -> msg_for_first_name
+fn get_msg_method_ident(field: &syn::Field, span_ident: &syn::Ident) -> syn::Ident {
+    let field_ident = field.ident.clone().unwrap();
+    let method_name = format!("msg_for_{}", field_name(&field_ident));
+    syn::Ident::new(&method_name, span_ident.span())
+}
+
+// The classes naming a field's label and input, i.e. first_name -> first-name-label and
+// first-name-input, which are also exported as constants on the component
+fn get_field_classes(field_ident: &syn::Ident) -> (String, String) {
+    (
+        format!("{}-label", field_name(field_ident)).to_case(Case::Kebab),
+        format!("{}-input", field_name(field_ident)).to_case(Case::Kebab),
+    )
+}
+
+// This function returns the class values for labels and inputs of both String and bool fields
+fn get_label_and_input_classes(field_ident: &syn::Ident) -> (String, String, String, String) {
+    let (label_class, input_class) = get_field_classes(field_ident);
+    let txt_label_class = format!("{} formula-y-txt-label", label_class);
+    let txt_input_class = format!("{} formula-y-txt-input", input_class);
+    let bool_label_class = format!("{} formula-y-checkbox-label", label_class);
+    let bool_input_class = format!("{} formula-y-checkbox", input_class);
+    (
+        txt_label_class,
+        txt_input_class,
+        bool_label_class,
+        bool_input_class,
+    )
+}
+
+fn get_class_getter_method_idents(
+    field_ident: &syn::Ident,
+    span_ident: &syn::Ident,
+) -> (syn::Ident, syn::Ident) {
+    let method_name_label = format!("get_class_for_{}_label", field_name(field_ident));
+    let method_name_label_ident = syn::Ident::new(&method_name_label, span_ident.span());
+    let method_name_input = format!("get_class_for_{}", field_name(field_ident));
+    let method_name_input_ident = syn::Ident::new(&method_name_input, span_ident.span());
+    (method_name_label_ident, method_name_input_ident)
+}
+
+// Text values pass through the field's transforms before they are stored, so the required checks
+// and the onsubmit callback only ever see the cleaned up value.
+fn get_string_value_transform(attrs: &FieldAttrs) -> proc_macro2::TokenStream {
+    let trim = if attrs.trim {
+        quote! { let new_value = new_value.trim().to_string(); }
+    } else {
+        quote! {}
+    };
+
+    let transform = match attrs.transform {
+        Some(Transform::Lowercase) => quote! { let new_value = new_value.to_lowercase(); },
+        Some(Transform::Uppercase) => quote! { let new_value = new_value.to_uppercase(); },
+        None => quote! {},
+    };
+
+    let transform_with = match &attrs.transform_with {
+        Some(transform_fn) => quote! { let new_value: String = #transform_fn(new_value); },
+        None => quote! {},
+    };
+
+    quote! {
+        #trim
+        #transform
+        #transform_with
+    }
+}
+
+// The expression checking whether a required String field has been left empty. Trimmed fields
+// also count whitespace only values (i.e. from init) as empty.
+fn get_string_is_empty(field_ident: &syn::Ident, attrs: &FieldAttrs) -> proc_macro2::TokenStream {
+    if attrs.trim {
+        quote! { self.inner().#field_ident.trim() == "" }
+    } else {
+        quote! { self.inner().#field_ident == "" }
+    }
+}
+
+// The variant of the generated field enum naming a field, i.e. DataField::Email
+fn get_field_key(field: &syn::Field, field_enum_ident: &syn::Ident) -> proc_macro2::TokenStream {
+    let field_variant = get_field_variant_ident(field);
+    quote! { #field_enum_ident::#field_variant }
+}
+
+// The name attribute of a field's input, from #[yform(name_attr = "...")] or else the field's
+// name, which browsers use to guess what to autofill and forms submitted without JS are keyed by
+fn get_name_attr(field: &syn::Field, attrs: &FieldAttrs) -> String {
+    attrs
+        .name_attr
+        .clone()
+        .unwrap_or_else(|| field_name(field.ident.as_ref().unwrap()))
+}
+
+// The text of a field's label, from #[yform(label = "...")] or else the Title Cased field name
+fn get_label(field: &syn::Field) -> String {
+    FieldAttrs::from_field(field)
+        .label
+        .unwrap_or_else(|| field_name(field.ident.as_ref().unwrap()).to_case(Case::Title))
+}
+
+// Whether a field is typed into as text which is parsed into its value, i.e. a number or a date.
+// Text which doesn't parse is kept, so the input can be flagged without losing what was typed.
+fn field_is_parsed(field: &syn::Field) -> bool {
+    field_is_number(field)
+        || field_is_option_number(field)
+        || field_is_date(field)
+        || field_is_option_date(field)
+}
+
+// The format of the text in date and datetime-local inputs
+fn get_date_format(field: &syn::Field) -> &'static str {
+    if field_is_datetime(field) {
+        "%Y-%m-%dT%H:%M"
+    } else {
+        "%Y-%m-%d"
+    }
+}
+
+// The text shown in the input of a parsed field, given the expression for its value
+fn get_parsed_text(
+    field: &syn::Field,
+    value: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    // Dates typed as text are shown in the order of the browser's locale
+    let date_format = if !FieldAttrs::from_field(field).date_text {
+        let date_format = get_date_format(field);
+        quote! { #date_format }
+    } else if field_is_datetime(field) {
+        quote! { DateOrder::detect().datetime_format() }
+    } else {
+        quote! { DateOrder::detect().date_format() }
+    };
+    if field_is_number(field) {
+        quote! { #value.to_string() }
+    } else if field_is_option_number(field) {
+        quote! { #value.map(|number| number.to_string()).unwrap_or_default() }
+    } else if field_is_date(field) {
+        quote! { #value.format(#date_format).to_string() }
+    } else {
+        quote! { #value.map(|date| date.format(#date_format).to_string()).unwrap_or_default() }
+    }
+}
+
+// Round a float field's value, given the expression for it, to the precision it was given with
+// #[yform(precision = ...)], or leave it as is without one
+fn get_rounded(field: &syn::Field, value: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let Some((precision, rounding)) = FieldAttrs::from_field(field).precision else {
+        return value;
+    };
+    let rounding = match rounding {
+        Rounding::HalfUp => quote! { Rounding::HalfUp },
+        Rounding::HalfEven => quote! { Rounding::HalfEven },
+        Rounding::Down => quote! { Rounding::Down },
+        Rounding::Up => quote! { Rounding::Up },
+    };
+    if field_is_option(field) {
+        let ty = item_type(field);
+        quote! { #value.map(|number| #rounding.round(number as f64, #precision) as #ty) }
+    } else {
+        let ty = &field.ty;
+        quote! { #rounding.round(#value as f64, #precision) as #ty }
+    }
+}
+
+// The value a field given #[yform(default = ...)] starts out holding, from the literal
+fn get_default_value(field: &syn::Field, default: &syn::Lit) -> proc_macro2::TokenStream {
+    let value = if field_is_string(field) || field_is_option_string(field) {
+        quote! { #default.to_string() }
+    } else if field_is_enum(field) || field_is_option_enum(field) {
+        let enum_type = enum_type(field);
+        let message = format!("{} isn't the value of a variant", quote! { #default });
+        quote! { <#enum_type as YFormSelect>::from_value(#default).expect(#message) }
+    } else if field_is_date(field) || field_is_option_date(field) {
+        let message = format!("{} isn't a date", quote! { #default });
+        let parsed = get_parse(field, quote! { #default });
+        quote! { #parsed.expect(#message) }
+    } else if let (syn::Lit::Int(int), true) = (default, field_is_float(field)) {
+        // Whole numbers are written without a point, which a float can't be assigned from
+        let float = syn::LitFloat::new(&format!("{}.0", int.base10_digits()), int.span());
+        quote! { #float }
+    } else {
+        quote! { #default }
+    };
+    if field_is_option(field) {
+        quote! { Some(#value) }
+    } else {
+        value
+    }
+}
+
+// Parse the text of a parsed field's input, which is never empty. Browsers leave the seconds out of
+// datetime-local values when they are zero. Dates typed as text are read in the order of the
+// browser's locale.
+fn get_parse(field: &syn::Field, text: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let date_text = FieldAttrs::from_field(field).date_text;
+    if date_text && field_is_datetime(field) {
+        quote! { DateOrder::detect().parse_datetime(#text).ok_or(()) }
+    } else if date_text {
+        quote! { DateOrder::detect().parse_date(#text).ok_or(()) }
+    } else if field_is_datetime(field) {
+        quote! {
+            chrono::NaiveDateTime::parse_from_str(#text, "%Y-%m-%dT%H:%M:%S")
+                .or_else(|_| chrono::NaiveDateTime::parse_from_str(#text, "%Y-%m-%dT%H:%M"))
+        }
+    } else {
+        quote! { #text.parse() }
+    }
+}
+
+// Check whether a field is missing a value, i.e. an empty string or an unchecked checkbox
+fn get_is_missing(field: &syn::Field) -> proc_macro2::TokenStream {
+    let field_ident = field.ident.clone().unwrap();
+    if field_is_string(field) {
+        get_string_is_empty(&field_ident, &FieldAttrs::from_field(field))
+    } else if field_is_bool(field) {
+        quote! { !self.inner().#field_ident }
+    } else if field_is_option_bool(field) {
+        quote! { !self.inner().#field_ident.unwrap_or_default() }
+    } else if field_is_number(field)
+        || field_is_date(field)
+        || field_is_enum(field)
+        || field_is_nested(field)
+    {
+        // A number or date always has a value, and text which doesn't parse is caught separately.
+        // An enum always has one of its variants chosen, and a nested struct checks its own fields.
+        quote! { false }
+    } else if field_is_vec(field) {
+        quote! { self.inner().#field_ident.is_empty() }
+    } else {
+        quote! { self.inner().#field_ident.is_none() }
+    }
+}
+
+// The step numbers the fields of a multi-step form are split into, in order, or none for a form
+// shown all at once
+fn get_steps(fields: &syn::punctuated::Punctuated<syn::Field, syn::token::Comma>) -> Vec<u32> {
+    let mut steps = fields
+        .iter()
+        .filter_map(|field| FieldAttrs::from_field(field).step)
+        .collect::<Vec<_>>();
+    steps.sort_unstable();
+    steps.dedup();
+    steps
+}
+
+// Which of the steps a field is shown on, counting from 0. Fields without a step go on the first.
+fn get_step_index(field: &syn::Field, steps: &[u32]) -> usize {
+    FieldAttrs::from_field(field)
+        .step
+        .and_then(|step| steps.iter().position(|other| *other == step))
+        .unwrap_or(0)
+}
+
+// A login form's bool fields, i.e. remember me, can be left unchecked
+fn field_is_remember_me(field: &syn::Field, struct_attrs: &StructAttrs) -> bool {
+    struct_attrs.is_login() && field_is_bool(field)
+}
+
+// Whether a field is entered into a password input, either by asking for one with input_type or,
+// in a login form, by being named password
+fn field_is_password(field: &syn::Field, attrs: &FieldAttrs, struct_attrs: &StructAttrs) -> bool {
+    match &attrs.input_type {
+        Some(input_type) => input_type == "password",
+        None => {
+            struct_attrs.is_login()
+                && (field_is_string(field) || field_is_option_string(field))
+                && field
+                    .ident
+                    .as_ref()
+                    .is_some_and(|ident| ident == "password")
+        }
+    }
+}
+
+// Check whether a field is missing a value it requires, or None if the field is never required.
+// Plain fields are always required, Option and Vec fields are optional, and either kind is required only
+// while the field named by required_if is filled in when that is set.
+fn get_required_missing(
+    field: &syn::Field,
+    fields: &syn::punctuated::Punctuated<syn::Field, syn::token::Comma>,
+    struct_attrs: &StructAttrs,
+) -> Option<proc_macro2::TokenStream> {
+    let attrs = FieldAttrs::from_field(field);
+    // Fields the user can't see or fill in are never required, and neither is a login form's
+    // remember me checkbox
+    if attrs.skip || attrs.hidden || field_is_remember_me(field, struct_attrs) {
+        return None;
+    }
+    let missing = get_is_missing(field);
+    match attrs.required_if {
+        Some(other_ident) => {
+            let other_field = fields
+                .iter()
+                .find(|other| other.ident.as_ref() == Some(&other_ident))?;
+            let other_missing = get_is_missing(other_field);
+            Some(quote! { (!(#other_missing) && #missing) })
+        }
+        None if field_is_option(field) || field_is_vec(field) => None,
+        None => Some(missing),
+    }
+}
+
+// The condition under which a field requires a value, or None if the field is never required
+fn get_required(
+    field: &syn::Field,
+    fields: &syn::punctuated::Punctuated<syn::Field, syn::token::Comma>,
+    struct_attrs: &StructAttrs,
+) -> Option<proc_macro2::TokenStream> {
+    let attrs = FieldAttrs::from_field(field);
+    if attrs.skip || attrs.hidden || field_is_remember_me(field, struct_attrs) {
+        return None;
+    }
+    match attrs.required_if {
+        Some(other_ident) => {
+            let other_field = fields
+                .iter()
+                .find(|other| other.ident.as_ref() == Some(&other_ident))?;
+            let other_missing = get_is_missing(other_field);
+            Some(quote! { !(#other_missing) })
+        }
+        None if field_is_option(field) || field_is_vec(field) => None,
+        None => Some(quote! { true }),
+    }
+}
+
+// Generate a method telling whether a field is flagged, i.e. missing a required value after a submit
+// attempt or held by the invalid check, which sets aria-invalid on its input
+fn get_flagged_method(
+    method_ident: &syn::Ident,
+    field: &syn::Field,
+    fields: &syn::punctuated::Punctuated<syn::Field, syn::token::Comma>,
+    struct_attrs: &StructAttrs,
+    invalid: Option<proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    let mut checks = Vec::new();
+    if let Some(missing) = get_required_missing(field, fields, struct_attrs) {
+        checks.push(quote! { (self.display_required_warnings && #missing) });
+    }
+    checks.extend(invalid);
+    let flagged = if checks.is_empty() {
+        quote! { false }
+    } else {
+        quote! { #(#checks)||* }
+    };
+    quote! {
+        pub fn #method_ident(&self) -> bool {
+            #flagged
+        }
+    }
+}
+
+// Generate a method giving a label or input its classes, with "required" appended while the field
+// is missing a required value after a submit attempt, and "invalid" while the invalid check holds
+fn get_class_method(
+    method_ident: &syn::Ident,
+    class: &str,
+    field: &syn::Field,
+    fields: &syn::punctuated::Punctuated<syn::Field, syn::token::Comma>,
+    struct_attrs: &StructAttrs,
+    invalid: Option<proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    let required = get_required_missing(field, fields, struct_attrs).map(|missing| {
+        quote! {
+            if self.display_required_warnings && #missing {
+                base_name.push_str(" required");
+            }
+        }
+    });
+    let invalid = invalid.map(|invalid| {
+        quote! {
+            if #invalid {
+                base_name.push_str(" invalid");
+            }
+        }
+    });
+    if required.is_none() && invalid.is_none() {
+        quote! {
+            pub fn #method_ident(&self) -> String {
+                #class.to_string()
+            }
+        }
+    } else {
+        quote! {
+            pub fn #method_ident(&self) -> String {
+                let mut base_name = String::from(#class);
+                #required
+                #invalid
+                base_name
+            }
+        }
+    }
+}
+
+// The type the component keeps its state in, which is also what gets passed to onsubmit
+fn get_state_type(
+    input_struct_ident: &syn::Ident,
+    struct_attrs: &StructAttrs,
+) -> proc_macro2::TokenStream {
+    if struct_attrs.shared_state {
+        quote! { std::rc::Rc<std::cell::RefCell<#input_struct_ident>> }
+    } else {
+        quote! { #input_struct_ident }
+    }
+}
+
+// Wrap an expression producing the input struct into the state type
+fn wrap_state(
+    value: proc_macro2::TokenStream,
+    struct_attrs: &StructAttrs,
+) -> proc_macro2::TokenStream {
+    if struct_attrs.shared_state {
+        quote! { std::rc::Rc::new(std::cell::RefCell::new(#value)) }
+    } else {
+        value
+    }
+}
+
+// The type returned when borrowing the input struct out of the state
+fn get_inner_ref_type(
+    input_struct_ident: &syn::Ident,
+    struct_attrs: &StructAttrs,
+) -> proc_macro2::TokenStream {
+    if struct_attrs.shared_state {
+        quote! { std::cell::Ref<'_, #input_struct_ident> }
+    } else {
+        quote! { &#input_struct_ident }
+    }
+}
+
+// Autogrowing textareas are resized to fit their content on every keystroke. Resetting the
+// height first lets the textarea shrink again when content is deleted.
+fn get_autogrow_callback() -> proc_macro2::TokenStream {
+    quote! {
+        Callback::from(|event: InputEvent| {
+            let textarea = event
+                .target()
+                .unwrap()
+                .unchecked_into::<HtmlTextAreaElement>();
+            let _ = textarea.set_attribute("style", "height: auto;");
+            let height = format!("height: {}px;", textarea.scroll_height());
+            let _ = textarea.set_attribute("style", &height);
+        })
+    }
+}
+
+// Text pasted into a field with #[yform(on_paste = "...")] goes through the field's function
+// before it reaches the input. The cleaned up text replaces the selection as the paste would have,
+// or the whole value of a number input, which has no selection. An input event follows, so the
+// paste is treated like typing, i.e. by search forms and autogrow.
+fn get_paste_listener(attrs: &FieldAttrs) -> proc_macro2::TokenStream {
+    let on_paste = match &attrs.on_paste {
+        Some(on_paste) => on_paste,
+        None => return quote! {},
+    };
+    let element = if attrs.is_textarea() {
+        quote! { HtmlTextAreaElement }
+    } else {
+        quote! { HtmlInputElement }
+    };
+    quote! {
+        onpaste={Callback::from(|event: Event| {
+            let event = event.unchecked_into::<web_sys::ClipboardEvent>();
+            let pasted = event
+                .clipboard_data()
+                .and_then(|data| data.get_data("text/plain").ok());
+            let pasted = match pasted {
+                Some(pasted) => pasted,
+                None => return,
+            };
+            event.prevent_default();
+            let pasted: String = #on_paste(pasted);
+            let input = event.target().unwrap().unchecked_into::<#element>();
+            let selection = input
+                .selection_start()
+                .ok()
+                .flatten()
+                .zip(input.selection_end().ok().flatten());
+            match selection {
+                Some((start, end)) => {
+                    let _ = input.set_range_text_with_start_and_end(&pasted, start, end);
+                    let caret = start + pasted.encode_utf16().count() as u32;
+                    let _ = input.set_selection_range(caret, caret);
+                }
+                None => input.set_value(&pasted),
+            }
+            let init = web_sys::InputEventInit::new();
+            init.set_bubbles(true);
+            init.set_input_type("insertFromPaste");
+            if let Ok(input_event) = web_sys::InputEvent::new_with_event_init_dict("input", &init) {
+                let _ = input.dispatch_event(&input_event);
+            }
+        })}
+    }
+}
+
+// Number fields with #[yform(strict_numeric)] list the characters their input takes in a data
+// attribute: digits, a minus sign for signed types, a decimal point for floats, and separators for
+// formatted numbers. Anything else typed, pasted or dropped in is turned away before it appears.
+fn get_allowed_number_chars(field: &syn::Field, attrs: &FieldAttrs) -> proc_macro2::TokenStream {
+    if !attrs.strict_numeric {
+        return quote! {};
+    }
+    let number_type = if field_is_option(field) {
+        item_type(field)
+    } else {
+        field.ty.clone()
+    };
+    let number_type = quote! { #number_type }.to_string();
+    let mut allowed = String::from("0123456789");
+    if !number_type.starts_with('u') {
+        allowed.push('-');
+    }
+    if number_type.starts_with('f') {
+        allowed.push('.');
+    }
+    if attrs.format.is_some() {
+        allowed.push(',');
+    }
+    quote! { data-formula-y-allowed=#allowed }
+}
+
+// The listener and event type a checkbox, radio or select stores its value with
+fn get_widget_listener(event: WidgetEvent) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    match event {
+        WidgetEvent::Change => (quote! { onchange }, quote! { Event }),
+        WidgetEvent::Input => (quote! { oninput }, quote! { InputEvent }),
+        WidgetEvent::Click => (quote! { onclick }, quote! { MouseEvent }),
+    }
+}
+
+// The listener turning away input strict_numeric fields don't allow. yew can't listen for
+// beforeinput, which is the one event covering typing, pasting and dropping text alike, so it is
+// listened for on the document. Text being composed through an IME can't be turned away, and is
+// left to fail to parse as usual.
+fn get_strict_numeric_listener(
+    fields: &syn::punctuated::Punctuated<syn::Field, syn::token::Comma>,
+) -> proc_macro2::TokenStream {
+    if !fields
+        .iter()
+        .any(|field| FieldAttrs::from_field(field).strict_numeric)
+    {
+        return quote! {};
+    }
+    quote! {
+        let id_prefix = self.id_prefix.clone();
+        let options = gloo_events::EventListenerOptions::enable_prevent_default();
+        self.document_listeners.push(gloo_events::EventListener::new_with_options(&document, "beforeinput", options, move |event| {
+            let event = event.unchecked_ref::<InputEvent>();
+            let target = event.target().and_then(|target| target.dyn_into::<web_sys::Element>().ok());
+            let allowed = target.as_ref().and_then(|target| target.get_attribute("data-formula-y-allowed"));
+            let form = web_sys::window()
+                .and_then(|window| window.document())
+                .and_then(|document| document.get_element_by_id(&id_prefix));
+            if let (Some(form), Some(target), Some(allowed)) = (form, target, allowed) {
+                if event.is_composing() || !form.contains(Some(&target)) {
+                    return;
+                }
+                let text = event
+                    .data()
+                    .or_else(|| event.data_transfer().and_then(|data| data.get_data("text/plain").ok()));
+                if text.is_some_and(|text| text.chars().any(|c| !allowed.contains(c))) {
+                    event.prevent_default();
+                }
+            }
+        }));
+    }
+}
+
+// With the keyboard_navigation prop, the form listens for keys bubbling up from its inputs. Enter
+// moves focus to the next input or button, rather than submitting from a text input, while
+// textareas keep it for new lines and buttons for clicking. The arrow keys move between the
+// radios of a group, choosing each one as they go, and between the form's checkboxes. Keys an
+// input has already handled, i.e. Enter confirming an inline edit, are left alone.
+fn get_keyboard_navigation_callback() -> proc_macro2::TokenStream {
+    quote! {
+        Callback::from(|event: KeyboardEvent| {
+            if event.default_prevented()
+                || event.alt_key()
+                || event.ctrl_key()
+                || event.meta_key()
+                || event.shift_key()
+                // Enter picks the text while composing through an IME. Safari has ended the
+                // composition by then, but still reports the key as 229.
+                || event.is_composing()
+                || event.key_code() == 229
+            {
+                return;
+            }
+            let target = event.target().unwrap().unchecked_into::<web_sys::HtmlElement>();
+            let form = event.current_target().unwrap().unchecked_into::<web_sys::Element>();
+            let tag = target.tag_name();
+            let input_type = target.get_attribute("type").unwrap_or_default();
+            let name = target.get_attribute("name").unwrap_or_default();
+            // Elements in a collapsed section have no offset parent, and can't take focus
+            let focusable = |selector: &str| {
+                let nodes = form.query_selector_all(selector).ok();
+                (0..nodes.as_ref().map_or(0, |nodes| nodes.length()))
+                    .filter_map(|index| nodes.as_ref()?.item(index))
+                    .map(|node| node.unchecked_into::<web_sys::HtmlElement>())
+                    .filter(|element| element.offset_parent().is_some())
+                    .collect::<Vec<_>>()
+            };
+            let (candidates, step, radio) = match event.key().as_str() {
+                "Enter" if tag != "TEXTAREA" && tag != "BUTTON" => {
+                    // The rest of a radio group is skipped, as the arrow keys move within it
+                    let candidates = focusable("input:not(:disabled), select:not(:disabled), textarea:not(:disabled), button:not(:disabled)")
+                        .into_iter()
+                        .filter(|element| {
+                            input_type != "radio"
+                                || element.is_same_node(Some(&target))
+                                || element.get_attribute("name").unwrap_or_default() != name
+                        })
+                        .collect();
+                    (candidates, 1, false)
+                }
+                key @ ("ArrowUp" | "ArrowLeft" | "ArrowDown" | "ArrowRight")
+                    if input_type == "radio" || input_type == "checkbox" =>
+                {
+                    let step = if key == "ArrowUp" || key == "ArrowLeft" { -1 } else { 1 };
+                    if input_type == "radio" {
+                        let selector = format!("input[type=radio][name=\"{}\"]:not(:disabled)", name);
+                        (focusable(&selector), step, true)
+                    } else {
+                        (focusable("input[type=checkbox]:not(:disabled)"), step, false)
+                    }
+                }
+                _ => return,
+            };
+            let position = match candidates.iter().position(|element| element.is_same_node(Some(&target))) {
+                Some(position) => position,
+                None => return,
+            };
+            // Radio groups wrap around, as they do in the browser
+            let next = RovingFocus::new(Orientation::Both)
+                .wrapping(radio)
+                .step(position, step, candidates.len());
+            if let Some(next) = next.and_then(|next| candidates.get(next)) {
+                event.prevent_default();
+                let _ = next.focus();
+                if radio {
+                    next.click();
+                }
+            }
+        })
+    }
+}
+
+// With the escape_reverts prop, focusing an input snapshots its field and Escape reverts the
+// field to that snapshot. The input is first put back to the value it was rendered with, since
+// text typed since the last change event would otherwise be left behind whenever the snapshot
+// matches the rendered value, leaving the rerender nothing to update.
+fn get_escape_revert_listeners(
+    field: &syn::Field,
+    attrs: &FieldAttrs,
+    field_enum_ident: &syn::Ident,
+    component_msg_ident: &syn::Ident,
+) -> proc_macro2::TokenStream {
+    let field_ident = field.ident.clone().unwrap();
+    if !field_has_single_input(field, attrs) {
+        return quote! {};
+    }
+    let element = if attrs.options_prop
+        || !attrs.choices.is_empty()
+        || field_is_enum(field)
+        || field_is_option_enum(field)
+    {
+        quote! { HtmlSelectElement }
+    } else if attrs.is_textarea() {
+        quote! { HtmlTextAreaElement }
+    } else {
+        quote! { HtmlInputElement }
+    };
+    let (shown, reset) = if field_is_string(field) {
+        (
+            quote! { self.inner().#field_ident.clone() },
+            quote! { set_value(&shown) },
+        )
+    } else if field_is_option_string(field) {
+        (
+            quote! { self.inner().#field_ident.clone().unwrap_or_default() },
+            quote! { set_value(&shown) },
+        )
+    } else if field_is_bool(field) {
+        (
+            quote! { self.inner().#field_ident },
+            quote! { set_checked(shown) },
+        )
+    } else if field_is_option_bool(field) {
+        (
+            quote! { self.inner().#field_ident.unwrap_or_default() },
+            quote! { set_checked(shown) },
+        )
+    } else if field_is_parsed(field) {
+        let field_key = get_field_key(field, field_enum_ident);
+        let parsed_text = get_parsed_text(field, quote! { self.inner().#field_ident });
+        (
+            quote! { self.invalid_numbers.get(&#field_key).cloned().unwrap_or_else(|| #parsed_text) },
+            quote! { set_value(&shown) },
+        )
+    } else if field_is_enum(field) {
+        (
+            quote! { self.inner().#field_ident.value() },
+            quote! { set_value(shown) },
+        )
+    } else {
+        (
+            quote! { self.inner().#field_ident.as_ref().map_or("", YFormSelect::value) },
+            quote! { set_value(shown) },
+        )
+    };
+    let field_variant = get_field_variant_ident(field);
+    quote! {
+        let (onfocus, onkeydown) = if ctx.props().escape_reverts.unwrap_or_default() && !read_only {
+            let shown = #shown;
+            (
+                Some(ctx.link().callback(|_: FocusEvent| {
+                    #component_msg_ident::FocusField(#field_enum_ident::#field_variant)
+                })),
+                Some(ctx.link().batch_callback(move |event: KeyboardEvent| {
+                    // Escape while composing through an IME cancels the composition instead
+                    if event.key() != "Escape" || event.is_composing() {
+                        return None;
+                    }
+                    event.target().unwrap().unchecked_into::<#element>().#reset;
+                    Some(#component_msg_ident::RevertField(#field_enum_ident::#field_variant))
+                })),
+            )
+        } else {
+            (None, None)
+        };
+    }
+}
+
+// Numbers with #[yform(format = "...")] show their plain number while focused, so focusing and
+// leaving the input are passed on to the form, along with any listeners already on it
+fn get_format_listeners(
+    field: &syn::Field,
+    attrs: &FieldAttrs,
+    field_enum_ident: &syn::Ident,
+    component_msg_ident: &syn::Ident,
+) -> proc_macro2::TokenStream {
+    if attrs.format.is_none() {
+        return quote! {};
+    }
+    let field_variant = get_field_variant_ident(field);
+    quote! {
+        let onfocus = {
+            let onfocus = onfocus;
+            Some(ctx.link().batch_callback(move |event: FocusEvent| {
+                if let Some(onfocus) = &onfocus {
+                    onfocus.emit(event);
+                }
+                Some(#component_msg_ident::FocusFormatted(#field_enum_ident::#field_variant, true))
+            }))
+        };
+        let onblur = {
+            let onblur = onblur;
+            Some(ctx.link().batch_callback(move |event: FocusEvent| {
+                if let Some(onblur) = &onblur {
+                    onblur.emit(event);
+                }
+                Some(#component_msg_ident::FocusFormatted(#field_enum_ident::#field_variant, false))
+            }))
+        };
+    }
+}
+
+// Whether a field is edited through a single input element, as opposed to a group of radios, a
+// custom renderer or a nested form. Only these can be reverted with Escape or edited inline.
+fn field_has_single_input(field: &syn::Field, attrs: &FieldAttrs) -> bool {
+    attrs.custom.is_none()
+        && (field_is_bool(field)
+            || field_is_option_bool(field)
+            || field_is_parsed(field)
+            || ((field_is_string(field)
+                || field_is_option_string(field)
+                || field_is_enum(field)
+                || field_is_option_enum(field))
+                && !attrs.radio))
+}
+
+// The check for a field with #[yform(choices(...))] holding a value which isn't one of them, i.e.
+// from the init prop. Empty values are left to the required field checks.
+fn get_unchosen(field: &syn::Field, attrs: &FieldAttrs) -> Option<proc_macro2::TokenStream> {
+    if attrs.choices.is_empty() {
+        return None;
+    }
+    let field_ident = field.ident.clone().unwrap();
+    let choices = &attrs.choices;
+    // With shared_state, inner() is a Ref guard, which has to outlive the borrowed value
+    let value = if field_is_string(field) {
+        quote! { inner.#field_ident.as_str() }
+    } else {
+        quote! { inner.#field_ident.as_deref().unwrap_or_default() }
+    };
+    Some(quote! {{
+        let inner = self.inner();
+        let value = #value;
+        !value.is_empty() && ![#(#choices),*].contains(&value)
+    }})
+}
+
+// A Vec field with min_items or max_items holding too few or too many items says how many it
+// needs, which flags it and blocks submitting like any other error
+fn get_item_count_error(
+    field: &syn::Field,
+    attrs: &FieldAttrs,
+) -> Option<proc_macro2::TokenStream> {
+    let items = |count: usize| if count == 1 { "item" } else { "items" };
+    let field_ident = field.ident.clone().unwrap();
+    let mut checks = Vec::new();
+    if let Some(min_items) = attrs.min_items.filter(|min_items| *min_items > 0) {
+        let error = format!("Add at least {} {}", min_items, items(min_items));
+        checks.push(quote! { if len < #min_items { return Some(#error.to_string()); } });
+    }
+    if let Some(max_items) = attrs.max_items {
+        let error = format!("Remove items to leave at most {}", max_items);
+        checks.push(quote! { if len > #max_items { return Some(#error.to_string()); } });
+    }
+    if checks.is_empty() {
+        return None;
+    }
+    Some(quote! {
+        (|| {
+            let len = self.inner().#field_ident.len();
+            #(#checks)*
+            None::<String>
+        })()
+    })
+}
+
+// With the inline_edit prop, fields with a single input are shown as text with an edit button,
+// which swaps in the input. Losing focus confirms the edit, and so does Enter, which blurs the
+// input rather than submitting the form. Textareas keep Enter for new lines.
+fn get_inline_edit_listeners(
+    field: &syn::Field,
+    attrs: &FieldAttrs,
+    field_enum_ident: &syn::Ident,
+    component_msg_ident: &syn::Ident,
+) -> proc_macro2::TokenStream {
+    let field_variant = get_field_variant_ident(field);
+    let on_enter = if attrs.is_textarea() {
+        quote! {}
+    } else {
+        quote! {
+            if event.key() == "Enter" && !event.is_composing() {
+                event.prevent_default();
+                let _ = event.target().unwrap().unchecked_into::<web_sys::HtmlElement>().blur();
+                return;
+            }
+        }
+    };
+    quote! {
+        let onblur = inline.then(|| {
+            ctx.link().callback(|_: FocusEvent| {
+                #component_msg_ident::ConfirmInline(#field_enum_ident::#field_variant)
+            })
+        });
+        let onkeydown = if inline {
+            let escape = onkeydown;
+            Some(Callback::from(move |event: KeyboardEvent| {
+                #on_enter
+                if let Some(escape) = &escape {
+                    escape.emit(event);
+                }
+            }))
+        } else {
+            onkeydown
+        };
+    }
+}
+
+#[proc_macro_derive(YFormSelect, attributes(yform))]
+pub fn derive_select(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    match select::derive_select(&ast) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+// Stable proc macros can't emit warnings of their own, so a deprecated item named after the warning
+// is used at the span to warn at, and the compiler reports the deprecation along with its note.
+fn warning(span: proc_macro2::Span, name: &str, note: &str) -> proc_macro2::TokenStream {
+    let name = syn::Ident::new(name, span);
+    quote::quote_spanned! { span =>
+        const _: () = {
+            #[deprecated(note = #note)]
+            struct #name;
+            let _ = #name;
+        };
+    }
+}
+
+/// Expands to a string holding what `#[derive(YForm)]` generates for the struct, pretty printed,
+/// i.e. for pinning the output in a snapshot test.
+#[cfg(feature = "expand")]
+#[proc_macro]
+pub fn expand(input: TokenStream) -> TokenStream {
+    expand::expand(input, derive)
+}
+
+#[proc_macro_derive(YForm, attributes(yform))]
+pub fn derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+
+    // We are producing a yew component based on the input struct, so we will need
+    // idents for the component struct, its msg enum, and its prop struct.
+    let input_struct_ident = &ast.ident;
+    // The component name can be overridden with #[yform(name = "...")], i.e. to avoid clashing
+    // with an existing type.
+    let struct_attrs = match StructAttrs::from_ast(&ast) {
+        Ok(struct_attrs) => struct_attrs,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let component_ident = match &struct_attrs.name {
+        Some(name) => name.clone(),
+        None => append_to_ident(input_struct_ident, "Form"), // Data -> DataForm
+    };
+    let component_msg_ident = append_to_ident(&component_ident, "Msg"); // Data -> DataFormMsg
+    let component_prop_ident = append_to_ident(&component_ident, "Props"); // Data -> DataFormProps
+    let snapshot_ident = append_to_ident(&component_ident, "State"); // Data -> DataFormState
+    let field_enum_ident = append_to_ident(input_struct_ident, "Field"); // Data -> DataField
+
+    // The component and its props name the struct as is, without any type parameters
+    if !ast.generics.params.is_empty() {
+        return syn::Error::new_spanned(
+            &ast.generics,
+            "YForm can't be derived for generic structs",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    // Get the fields of the struct (Not implemented for Enums or TupleStructs)
+    let fields = match get_struct_fields(&ast) {
+        Ok(fields) => fields,
+        Err(error) => return error.to_compile_error().into(),
+    };
+
+    // With #[yform(skip_unsupported)], fields of a type the form can't render are left out of it
+    // rather than failing the build. They start out from Default, and a warning points at each one
+    // so it isn't dropped unnoticed.
+    let (fields, unsupported): (Vec<_>, Vec<_>) = fields
+        .into_iter()
+        .partition(|field| !struct_attrs.skip_unsupported || check_field_type(field).is_ok());
+    let fields: syn::punctuated::Punctuated<syn::Field, syn::token::Comma> =
+        fields.into_iter().collect();
+    let unsupported_inits = unsupported
+        .iter()
+        .map(|field| {
+            let field_ident = field.ident.clone().unwrap();
+            quote! { #field_ident: Default::default() }
+        })
+        .collect::<Vec<_>>();
+    let unsupported_warnings = unsupported
+        .iter()
+        .map(|field| {
+            let note = format!(
+                "`{}` has a type YForm can't render, so skip_unsupported leaves it out of the form and starts it out from Default",
+                field_name(field.ident.as_ref().unwrap())
+            );
+            warning(
+                syn::spanned::Spanned::span(&field.ty),
+                "UnsupportedFieldSkipped",
+                &note,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    // Check every field's attributes up front so that all the malformed options are reported at
+    // once, each pointing at the offending tokens.
+    let attr_errors = fields
+        .iter()
+        .filter_map(|field| FieldAttrs::parse(field).err())
+        .reduce(|mut errors, error| {
+            errors.combine(error);
+            errors
+        });
+    if let Some(errors) = attr_errors {
+        return errors.to_compile_error().into();
+    }
+    // Options which would have no effect are ignored with a warning rather than failing the build
+    let option_warnings = fields
+        .iter()
+        .flat_map(|field| FieldAttrs::from_field(field).warnings)
+        .map(|(span, note)| warning(span, "InapplicableOption", &note))
+        .collect::<Vec<_>>();
+
+    let save_individually = struct_attrs.save_individually;
+    // Search forms show their labels as placeholders unless the label_placeholders prop says otherwise
+    let search = struct_attrs.is_search();
+
+    // Fields with a step split the form into pages, which have to be submitted one at a time
+    let steps = get_steps(&fields);
+    if !steps.is_empty() && (save_individually || search) {
+        return syn::Error::new_spanned(
+            input_struct_ident,
+            "steps can't be combined with save_individually or the search preset",
+        )
+        .to_compile_error()
+        .into();
+    }
+    let last_step = steps.len().saturating_sub(1);
+
+    // submit_with and encrypt_with convert a copy of the struct, and shared_state hands out the
+    // struct itself
+    if struct_attrs.shared_state {
+        let submit_with_errors = fields
+            .iter()
+            .flat_map(|field| {
+                let attrs = FieldAttrs::from_field(field);
+                let submit_with = attrs.submit_with.map(|submit_with| {
+                    syn::Error::new_spanned(
+                        submit_with,
+                        "submit_with can't be used with shared_state, which submits the struct itself",
+                    )
+                });
+                let encrypt_with = attrs.encrypt_with.map(|encrypt_with| {
+                    syn::Error::new_spanned(
+                        encrypt_with,
+                        "encrypt_with can't be used with shared_state, which submits the struct itself",
+                    )
+                });
+                submit_with.into_iter().chain(encrypt_with)
+            })
+            .reduce(|mut errors, error| {
+                errors.combine(error);
+                errors
+            });
+        if let Some(errors) = submit_with_errors {
+            return errors.to_compile_error().into();
+        }
+    }
+
+    // required_if names another field, which has to exist for the check to be generated
+    let required_if_errors = fields
+        .iter()
+        .filter_map(|field| {
+            let other_ident = FieldAttrs::from_field(field).required_if?;
+            if fields
+                .iter()
+                .any(|other| other.ident.as_ref() == Some(&other_ident))
+            {
+                None
+            } else {
+                Some(syn::Error::new(
+                    other_ident.span(),
+                    format!("no field named `{}` for required_if", other_ident),
+                ))
+            }
+        })
+        .reduce(|mut errors, error| {
+            errors.combine(error);
+            errors
+        });
+    if let Some(errors) = required_if_errors {
+        return errors.to_compile_error().into();
+    }
+
+    // For convenience, we generate a standard new() method for the struct.
+    // To do so, we iterate over the supported types and produce the appropriate line.
+    let component_field_inits = fields.iter().map(|field| {
+        let field_ident = field.ident.clone().unwrap();
+        if let Some(default) = FieldAttrs::from_field(field).default {
+            let value = get_default_value(field, &default);
+            quote! { #field_ident: #value }
+        } else if field_is_string(field) {
+            quote! { #field_ident: String::new() }
+        } else if field_is_bool(field) {
+            quote! { #field_ident: false }
+        } else if field_is_number(field) || field_is_date(field) {
+            quote! { #field_ident: Default::default() }
+        } else if field_is_option(field) {
+            quote! { #field_ident: None }
+        } else if field_is_vec(field) {
+            quote! { #field_ident: Vec::new() }
+        } else if field_is_nested(field) {
+            let field_type = &field.ty;
+            quote! { #field_ident: <#field_type>::new() }
+        } else {
+            let enum_type = enum_type(field);
+            quote! { #field_ident: <#enum_type as YFormSelect>::first() }
+        }
+    });
+
+    // Create the msg variants for updating each field, and each item of Vec fields by its index
+    let msg_variants = fields.iter().map(|field| {
+        let field_type = field.ty.clone();
+        let msg_variant_ident = get_update_field_msg_variant_ident(field, input_struct_ident);
+        if field_is_vec(field) {
+            let item_type = item_type(field);
+            let item_msg_variant_ident =
+                get_update_item_msg_variant_ident(field, input_struct_ident);
+            quote! { #msg_variant_ident(#field_type), #item_msg_variant_ident(usize, #item_type) }
+        } else {
+            quote! { #msg_variant_ident(#field_type) }
+        }
+    });
+
+    // Fields with #[yform(computed_from = "...", compute_with = "...")] are recalculated whenever
+    // one of the fields they depend on changes, and when the form is created.
+    let computed_fields: Vec<(syn::Ident, Vec<syn::Ident>)> = fields
+        .iter()
+        .filter_map(|field| {
+            let (dependencies, _) = FieldAttrs::from_field(field).computed?;
+            Some((field.ident.clone().unwrap(), dependencies))
+        })
+        .collect();
+    let compute_methods = fields.iter().filter_map(|field| {
+        let field_ident = field.ident.clone().unwrap();
+        let (dependencies, compute_with) = FieldAttrs::from_field(field).computed?;
+        let compute_ident = get_compute_method_ident(&field_ident);
+        Some(quote! {
+            fn #compute_ident(&mut self) {
+                let value = #compute_with(#(&self.inner().#dependencies),*);
+                self.inner_mut().#field_ident = value;
+            }
+        })
+    });
+    let compute_all = computed_fields.iter().map(|(field_ident, _)| {
+        let compute_ident = get_compute_method_ident(field_ident);
+        quote! { form.#compute_ident(); }
+    });
+
+    // Each item of a Vec field is given a key which stays the same as items before it are removed,
+    // so that yew keeps the right inputs and nested forms when the list changes
+    let vec_fields = fields
+        .iter()
+        .filter(|field| field_is_vec(field))
+        .collect::<Vec<_>>();
+    // A Vec replaced with one of a different length gets new keys for all of its items
+    let sync_item_keys = vec_fields
+        .iter()
+        .map(|field| {
+            let field_ident = field.ident.clone().unwrap();
+            let field_variant = get_field_variant_ident(field);
+            quote! {
+                if self.item_keys.get(&#field_enum_ident::#field_variant).map_or(0, Vec::len) != self.inner().#field_ident.len() {
+                    self.reset_item_keys(#field_enum_ident::#field_variant);
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+    let reset_all_item_keys = vec_fields.iter().map(|field| {
+        let field_variant = get_field_variant_ident(field);
+        quote! { form.reset_item_keys(#field_enum_ident::#field_variant); }
+    });
+
+    let compute_on_create = if computed_fields.is_empty() && vec_fields.is_empty() {
+        quote! { form }
+    } else {
+        quote! {
+            let mut form = form;
+            #(#reset_all_item_keys)*
+            #(#compute_all)*
+            form
+        }
+    };
+
+    // Create the match arms for the update fn for updating each field
+    let match_arms_update = fields.iter().map(|field| {
+        let field_ident = field.ident.clone().unwrap();
+        let msg_variant_ident = get_update_field_msg_variant_ident(field, input_struct_ident);
+
+        // Every field update rerenders, so what yew last rendered always matches the form's state.
+        // Otherwise a later change back to an earlier value (i.e. a reset) would leave the text the
+        // user typed in the input.
+        let computes = computed_fields
+            .iter()
+            .filter(|(_, dependencies)| dependencies.contains(&field_ident))
+            .map(|(computed_ident, _)| get_compute_method_ident(computed_ident))
+            .collect::<Vec<_>>();
+        // A number or date which parses clears any earlier invalid text
+        let clear_invalid = if field_is_parsed(field) {
+            let field_key = get_field_key(field, &field_enum_ident);
+            quote! { self.invalid_numbers.remove(&#field_key); }
+        } else {
+            quote! {}
+        };
+        let field_variant = get_field_variant_ident(field);
+        if field_is_vec(field) {
+            // Replacing the whole list replaces every item, which rerenders them all
+            let item_msg_variant_ident =
+                get_update_item_msg_variant_ident(field, input_struct_ident);
+            return quote! {
+                #component_msg_ident::#msg_variant_ident(items) => {
+                    self.inner_mut().#field_ident = items;
+                    self.edited.insert(#field_enum_ident::#field_variant);
+                    self.reset_item_keys(#field_enum_ident::#field_variant);
+                    #(self.#computes();)*
+                    self.after_field_update();
+                    true
+                },
+                #component_msg_ident::#item_msg_variant_ident(index, item) => {
+                    if let Some(existing) = self.inner_mut().#field_ident.get_mut(index) {
+                        *existing = item;
+                    }
+                    self.edited.insert(#field_enum_ident::#field_variant);
+                    #(self.#computes();)*
+                    self.after_field_update();
+                    true
+                }
+            };
+        }
+        quote! { #component_msg_ident::#msg_variant_ident(item) => {
+            self.inner_mut().#field_ident = item;
+            self.edited.insert(#field_enum_ident::#field_variant);
+            #clear_invalid
+            #(self.#computes();)*
+            self.after_field_update();
+            true
+        } }
+    });
+
+    // We need to have a way to check if the required fields have all been provided, so we generate
+    // a series of if checks to confirm string fields are not empty strings and checkboxes are
+    // checked. Fields with #[yform(required_if = "...")] are only checked while the field they
+    // name is filled in or checked.
+    let checks = fields.iter().filter_map(|field| {
+        let missing = get_required_missing(field, &fields, &struct_attrs)?;
+        Some(quote! {
+            if #missing {
+                return false;
+            }
+        })
+    });
+
+    // Each input converts the value read from the DOM into an update msg with one of these methods,
+    // applying any transforms along the way. Keeping this out of the view means the same conversion
+    // can be driven without a browser.
+    let msg_methods = fields.iter().map(|field| {
+        let msg_variant_ident = get_update_field_msg_variant_ident(field, input_struct_ident);
+        let msg_method_ident = get_msg_method_ident(field, input_struct_ident);
+        let value_transform = get_string_value_transform(&FieldAttrs::from_field(field));
+
+        if field_is_string(field) {
+            quote! {
+                fn #msg_method_ident(new_value: String) -> #component_msg_ident {
+                    #value_transform
+                    #component_msg_ident::#msg_variant_ident(new_value)
+                }
+            }
+        } else if field_is_bool(field) {
+            quote! {
+                fn #msg_method_ident(new_value: bool) -> #component_msg_ident {
+                    #component_msg_ident::#msg_variant_ident(new_value)
+                }
+            }
+        } else if field_is_option_string(field) {
+            quote! {
+                fn #msg_method_ident(new_value: String) -> #component_msg_ident {
+                    #value_transform
+                    if new_value == "" {
+                        #component_msg_ident::#msg_variant_ident(None)
+                    } else {
+                        #component_msg_ident::#msg_variant_ident(Some(new_value))
+                    }
+                }
+            }
+        } else if field_is_option_bool(field) {
+            quote! {
+                fn #msg_method_ident(new_value: bool) -> #component_msg_ident {
+                    #component_msg_ident::#msg_variant_ident(Some(new_value))
+                }
+            }
+        } else if field_is_parsed(field) {
+            // Text which doesn't parse is kept as is, so the input can be flagged without losing
+            // what the user typed
+            let field_key = get_field_key(field, &field_enum_ident);
+            // Formatted numbers may be typed with their separators
+            let text = if FieldAttrs::from_field(field).format.is_some() {
+                quote! { new_value.replace(',', "") }
+            } else {
+                quote! { new_value }
+            };
+            let parsed = if field_is_number(field) || field_is_date(field) {
+                get_parse(field, quote! { #text.trim() })
+            } else {
+                let parse = get_parse(field, quote! { trimmed });
+                quote! {
+                    match #text.trim() {
+                        "" => Ok(None),
+                        trimmed => #parse.map(Some),
+                    }
+                }
+            };
+            let rounded = get_rounded(field, quote! { value });
+            let field_type = &field.ty;
+            quote! {
+                fn #msg_method_ident(new_value: String) -> #component_msg_ident {
+                    let parsed: Result<#field_type, _> = #parsed;
+                    match parsed {
+                        Ok(value) => #component_msg_ident::#msg_variant_ident(#rounded),
+                        Err(_) => #component_msg_ident::InvalidNumber(#field_key, new_value),
+                    }
+                }
+            }
+        } else if field_is_enum(field) || field_is_option_enum(field) {
+            // The value of the chosen variant is turned back into the variant, with None for a
+            // value which doesn't name one
+            let enum_type = enum_type(field);
+            let chosen = if field_is_enum(field) {
+                quote! { <#enum_type as YFormSelect>::from_value(&new_value) }
+            } else {
+                quote! {
+                    if new_value.is_empty() {
+                        Some(None)
+                    } else {
+                        <#enum_type as YFormSelect>::from_value(&new_value).map(Some)
+                    }
+                }
+            };
+            quote! {
+                fn #msg_method_ident(new_value: String) -> Option<#component_msg_ident> {
+                    #chosen.map(#component_msg_ident::#msg_variant_ident)
+                }
+            }
+        } else {
+            quote! {}
+        }
+    });
+
+    // Fields with #[yform(regex = "...")] get a method giving their error message while a value is
+    // entered which doesn't match. Empty values are left to the required field checks. Each regex
+    // is compiled once, the first time it is needed.
+    let regex_fields = fields
+        .iter()
+        .filter_map(|field| {
+            let attrs = FieldAttrs::from_field(field);
+            Some((field, attrs.regex?, attrs.error))
+        })
+        .collect::<Vec<_>>();
+    let regex_error_methods = regex_fields.iter().map(|(field, pattern, error)| {
+        let field_ident = field.ident.clone().unwrap();
+        let method_ident = get_regex_error_method_ident(field, input_struct_ident);
+        let regex_ident = get_regex_ident(field);
+        let pattern = attrs::anchored_pattern(pattern);
+        let error = error.clone().unwrap_or_else(|| "Invalid value".to_string());
+        let value = if field_is_string(field) {
+            quote! { inner.#field_ident.as_str() }
+        } else {
+            quote! { inner.#field_ident.as_deref().unwrap_or_default() }
+        };
+        quote! {
+            // The field's compiled regex, or None if the regex crate the app is built with turns
+            // the pattern down, i.e. for going over its size limit. The form reports that when it
+            // is created, and lets values through rather than blocking every submit.
+            fn #regex_ident() -> Option<&'static regex::Regex> {
+                static REGEX: std::sync::OnceLock<Option<regex::Regex>> = std::sync::OnceLock::new();
+                REGEX.get_or_init(|| regex::Regex::new(#pattern).ok()).as_ref()
+            }
+
+            pub fn #method_ident(&self) -> Option<&'static str> {
+                let regex = Self::#regex_ident()?;
+                let inner = self.inner();
+                let value = #value;
+                if value.is_empty() || regex.is_match(value) {
+                    None
+                } else {
+                    Some(#error)
+                }
+            }
+        }
+    });
+    let regex_compile_checks = regex_fields.iter().map(|(field, _, _)| {
+        let regex_ident = get_regex_ident(field);
+        let label = get_label(field);
+        quote! {
+            if Self::#regex_ident().is_none() {
+                ctx.link().send_message(#component_msg_ident::InternalError(format!(
+                    "The regex for {} doesn't compile, so it isn't checked",
+                    #label
+                )));
+            }
+        }
+    });
+    let regex_checks = regex_fields.iter().map(|(field, _, _)| {
+        let method_ident = get_regex_error_method_ident(field, input_struct_ident);
+        quote! { self.#method_ident().is_none() }
+    });
+
+    let choice_checks = fields.iter().filter_map(|field| {
+        let unchosen = get_unchosen(field, &FieldAttrs::from_field(field))?;
+        Some(quote! { !#unchosen })
+    });
+    let item_count_checks = fields.iter().filter_map(|field| {
+        let error = get_item_count_error(field, &FieldAttrs::from_field(field))?;
+        Some(quote! { #error.is_none() })
+    });
+
+    // Fields with #[yform(validate = "...")] get a method giving the error their fn returns for
+    // the current value, which is shown while it lasts and blocks submitting
+    let validate_fields = fields
+        .iter()
+        .filter_map(|field| Some((field, FieldAttrs::from_field(field).validate?)))
+        .collect::<Vec<_>>();
+    let validate_error_methods = validate_fields.iter().map(|(field, validate)| {
+        let field_ident = field.ident.clone().unwrap();
+        let method_ident = get_validate_error_method_ident(field, input_struct_ident);
+        quote! {
+            pub fn #method_ident(&self) -> Option<String> {
+                #validate(&self.inner().#field_ident).err()
+            }
+        }
+    });
+    let validate_checks = validate_fields.iter().map(|(field, _)| {
+        let method_ident = get_validate_error_method_ident(field, input_struct_ident);
+        quote! { self.#method_ident().is_none() }
+    });
+
+    // Now we are generating methods thats give us the class attributes text for each field. If a form submit occurs
+    // and a required field is empty/unchecked, it gets a class of required appended to it.
+    let get_class_methods = fields.iter().map(|field| {
+        let field_ident = field.ident.clone().unwrap();
+
+        let (txt_label_class, txt_input_class, bool_label_class, bool_input_class) =
+            get_label_and_input_classes(&field_ident);
+        let (label_class, input_class) = if field_is_bool(field) || field_is_option_bool(field) {
+            (bool_label_class, bool_input_class)
+        } else {
+            (txt_label_class, txt_input_class)
+        };
+
+        let (method_name_label_ident, method_name_input_ident) =
+            get_class_getter_method_idents(&field_ident, input_struct_ident);
+
+        // Number and date inputs holding text which doesn't parse are flagged as invalid, as are values
+        // which don't match their regex, and values with errors from a validate fn
+        let attrs = FieldAttrs::from_field(field);
+        let mut invalid_checks = Vec::new();
+        if field_is_parsed(field) {
+            let field_key = get_field_key(field, &field_enum_ident);
+            invalid_checks.push(quote! { self.invalid_numbers.contains_key(&#field_key) });
+        }
+        if attrs.regex.is_some() {
+            let regex_error_method_ident = get_regex_error_method_ident(field, input_struct_ident);
+            invalid_checks.push(quote! { self.#regex_error_method_ident().is_some() });
+        }
+        if attrs.validate.is_some() {
+            let validate_error_method_ident =
+                get_validate_error_method_ident(field, input_struct_ident);
+            invalid_checks.push(quote! { self.#validate_error_method_ident().is_some() });
+        }
+        invalid_checks.extend(get_unchosen(field, &attrs));
+        invalid_checks.extend(
+            get_item_count_error(field, &attrs).map(|error| quote! { #error.is_some() }),
+        );
+        if struct_attrs.validate.is_some() {
+            let field_variant = get_field_variant_ident(field);
+            invalid_checks.push(quote! {
+                self.validation_errors.iter().any(|(field, _)| *field == #field_enum_ident::#field_variant)
+            });
+        }
+        let invalid = (!invalid_checks.is_empty()).then(|| quote! { #(#invalid_checks)||* });
+
+        let label_class_method =
+            get_class_method(&method_name_label_ident, &label_class, field, &fields, &struct_attrs, None);
+        let input_class_method = get_class_method(
+            &method_name_input_ident,
+            &input_class,
+            field,
+            &fields,
+            &struct_attrs,
+            invalid.clone(),
+        );
+        let flagged_method_ident = get_flagged_method_ident(field, input_struct_ident);
+        let flagged_method = get_flagged_method(&flagged_method_ident, field, &fields, &struct_attrs, invalid);
+        quote! {
+            #label_class_method
+
+            #input_class_method
+
+            #flagged_method
+        }
+    });
+
+    // A login form's username is its first text field which isn't a password
+    let username_ident = fields
+        .iter()
+        .filter(|_| struct_attrs.is_login())
+        .find(|field| {
+            (field_is_string(field) || field_is_option_string(field))
+                && !field_is_password(field, &FieldAttrs::from_field(field), &struct_attrs)
+        })
+        .and_then(|field| field.ident.clone());
+
+    // The class names of each rendered field as constants, so stylesheets built in Rust and tests
+    // can refer to them, i.e. DataForm::EMAIL_INPUT_CLASS
+    let classed_fields = fields.iter().filter(|field| {
+        let attrs = FieldAttrs::from_field(field);
+        !attrs.skip && !attrs.hidden
+    });
+    let class_consts = classed_fields.clone().map(|field| {
+        let field_ident = field.ident.clone().unwrap();
+        let (label_class, input_class) = get_field_classes(&field_ident);
+        let upper = field_name(&field_ident).to_case(Case::UpperSnake);
+        let label_const =
+            syn::Ident::new(&format!("{}_LABEL_CLASS", upper), input_struct_ident.span());
+        let input_const =
+            syn::Ident::new(&format!("{}_INPUT_CLASS", upper), input_struct_ident.span());
+        let label_doc = format!(
+            "The class on the `{}` field's label",
+            field_name(&field_ident)
+        );
+        let input_doc = format!(
+            "The class on the `{}` field's input",
+            field_name(&field_ident)
+        );
+        quote! {
+            #[doc = #label_doc]
+            pub const #label_const: &'static str = #label_class;
+            #[doc = #input_doc]
+            pub const #input_const: &'static str = #input_class;
+        }
+    });
+    let class_table = classed_fields.map(|field| {
+        let field_ident = field.ident.clone().unwrap();
+        let name = field_name(&field_ident);
+        let (label_class, input_class) = get_field_classes(&field_ident);
+        quote! { (#name, #label_class, #input_class) }
+    });
+
+    // Create the actual html elements for the inside of the form for the view fn
+    let form_fields = fields.iter().map(|field| {
+        let field_ident = field.ident.clone().unwrap();
+
+        // The label is looked up as the field renders, since the labels prop can replace it
+        let label_text = get_label(field);
+        let label = quote! { field_label.clone() };
+
+        let (method_name_label_ident, method_name_input_ident) = get_class_getter_method_idents(&field_ident, input_struct_ident);
+
+        let attrs = FieldAttrs::from_field(field);
+        // Skipped fields aren't rendered at all, and hidden ones only carry their value
+        if attrs.skip {
+            return (attrs, quote! {});
+        }
+        if attrs.hidden {
+            let value = if field_is_string(field) {
+                quote! { self.inner().#field_ident.clone() }
+            } else if field_is_option_string(field) {
+                quote! { self.inner().#field_ident.clone().unwrap_or_default() }
+            } else if field_is_option(field) {
+                quote! { self.inner().#field_ident.map(|value| value.to_string()).unwrap_or_default() }
+            } else {
+                quote! { self.inner().#field_ident.to_string() }
+            };
+            let field_id_suffix = field_name(&field_ident).to_case(Case::Kebab);
+            let name = get_name_attr(field, &attrs);
+            return (attrs, quote! {
+                <input id={self.field_id(#field_id_suffix)} type="hidden" name={#name} value={#value} />
+            });
+        }
+        // Textareas get the same classes as inputs, and grow to fit their content with autogrow
+        let rows = attrs.rows.map(|rows| {
+            let rows = rows.to_string();
+            quote! { rows=#rows }
+        });
+        let textarea_attrs = if attrs.autogrow {
+            let autogrow = get_autogrow_callback();
+            quote! { class={format!("{} formula-y-autogrow", input_class)} oninput={#autogrow} #rows }
+        } else {
+            quote! { class={input_class.clone()} #rows }
+        };
+        let onpaste = get_paste_listener(&attrs);
+        let name = get_name_attr(field, &attrs);
+        // Radios are grouped by name, so without a name_attr they keep their id as one, telling
+        // apart the groups of a Vec of nested forms
+        let radio_name = match &attrs.name_attr {
+            Some(name) => quote! { #name },
+            None => quote! { field_id.clone() },
+        };
+        // Checkboxes, radios and selects store their value on the event the field or struct asks
+        // for, which all give the input as their target
+        let (listener, event_type) = get_widget_listener(struct_attrs.widget_event(field, &attrs));
+        // Text which is trimmed or transformed is put back into the input as the form keeps it. The
+        // rerender can't be relied on for this, as the value may be the same as the one last shown.
+        let show_transformed = if attrs.trim || attrs.transform.is_some() || attrs.transform_with.is_some() {
+            let value_transform = get_string_value_transform(&attrs);
+            quote! {
+                let shown = {
+                    let new_value = new_value.clone();
+                    #value_transform
+                    new_value
+                };
+                if shown != new_value {
+                    element.set_value(&shown);
+                }
+            }
+        } else {
+            quote! {}
+        };
+        let msg_method_ident = get_msg_method_ident(field, input_struct_ident);
+        let field_id_suffix = field_name(&field_ident).to_case(Case::Kebab);
+        let input_type = attrs.input_type.clone().unwrap_or_else(|| "text".to_string());
+        // Login forms fill in the autocomplete values password managers look for, and give the
+        // password a button revealing it as text
+        let password = field_is_password(field, &attrs, &struct_attrs);
+        let (text_type, autocomplete, password_toggle) = if struct_attrs.is_login() && password {
+            (
+                quote! { if self.password_revealed { "text" } else { "password" } },
+                quote! { autocomplete="current-password" },
+                quote! {
+                    <button type="button" class="formula-y-password-toggle" aria-controls={self.field_id(#field_id_suffix)} aria-pressed={self.password_revealed.to_string()} onclick={ctx.link().callback(|_| #component_msg_ident::TogglePasswordReveal)}>
+                        { if self.password_revealed { "Hide" } else { "Show" } }
+                    </button>
+                },
+            )
+        } else if password {
+            (quote! { "password" }, quote! {}, quote! {})
+        } else if username_ident.as_ref() == Some(&field_ident) {
+            (quote! { #input_type }, quote! { autocomplete="username" }, quote! {})
+        } else {
+            (quote! { #input_type }, quote! {}, quote! {})
+        };
+        // Text inputs without a placeholder of their own can show their label as one instead
+        let can_show_label_placeholder = attrs.placeholder.is_none()
+            && !attrs.options_prop
+            && attrs.choices.is_empty()
+            && attrs.custom.is_none()
+            && (field_is_string(field)
+                || field_is_option_string(field)
+                || field_is_number(field)
+                || field_is_option_number(field));
+        let placeholder = match &attrs.placeholder {
+            Some(placeholder) => quote! { placeholder=#placeholder },
+            None if can_show_label_placeholder => {
+                quote! { placeholder={label_placeholder.then(|| #label)} }
+            }
+            None => quote! {},
+        };
+        // Selects which can be left empty start with an empty choice, blank unless labelled
+        let empty_label = attrs.empty_label.clone().unwrap_or_default();
+
+        // The error for a value which doesn't match the regex is shown right after the input, as
+        // are errors from validate fns
+        let error_class = format!("{}-error formula-y-error", field_id_suffix);
+        let regex_error = if attrs.regex.is_some() {
+            let regex_error_method_ident = get_regex_error_method_ident(field, input_struct_ident);
+            quote! {
+                { for self.#regex_error_method_ident().map(|error| html! {
+                    <p class={#error_class}>{error}</p>
+                }) }
+            }
+        } else {
+            quote! {}
+        };
+        let validate_error = if attrs.validate.is_some() {
+            let validate_error_method_ident = get_validate_error_method_ident(field, input_struct_ident);
+            quote! {
+                { for self.#validate_error_method_ident().map(|error| html! {
+                    <p class={#error_class}>{error}</p>
+                }) }
+            }
+        } else {
+            quote! {}
+        };
+        let validation_errors = if struct_attrs.validate.is_some() {
+            let field_variant = get_field_variant_ident(field);
+            quote! {
+                { for self.validation_errors.iter().filter(|(field, _)| *field == #field_enum_ident::#field_variant).map(|(_, error)| html! {
+                    <p class={#error_class}>{error.clone()}</p>
+                }) }
+            }
+        } else {
+            quote! {}
+        };
+        let item_count_error = get_item_count_error(field, &attrs).map(|error| {
+            quote! {
+                { for #error.map(|error| html! {
+                    <p class={#error_class}>{error}</p>
+                }) }
+            }
+        });
+        let choice_error = get_unchosen(field, &attrs).map(|unchosen| {
+            quote! {
+                if #unchosen {
+                    <p class={#error_class}>{"Choose one of the options"}</p>
+                }
+            }
+        });
+        // A required field left empty says so once a submit has been attempted, in its own words
+        // with #[yform(required_msg = "...")]
+        let required_error = get_required_missing(field, &fields, &struct_attrs).map(|missing| {
+            let required_error_class = format!("{} formula-y-required-error", error_class);
+            let required_msg = attrs
+                .required_msg
+                .clone()
+                .unwrap_or_else(|| "This field is required".to_string());
+            quote! {
+                if self.display_required_warnings && #missing {
+                    <p class={#required_error_class}>{#required_msg}</p>
+                }
+            }
+        });
+        // Every element of a field is marked with the field's name and what it is, which tests and
+        // stylesheets can rely on staying the same between versions
+        let contract_name = field_name(&field_ident);
+        let contract = quote! { data-formulay-field=#contract_name };
+        // The errors live in a region which screen readers announce as it changes, and which the
+        // input points to with aria-describedby
+        let field_errors = quote! {
+            <div id={error_id.clone()} class={classes!("formula-y-errors", &theme.error)} aria-live="polite" #contract data-formulay-role="error">
+                #required_error #regex_error #choice_error #item_count_error #validate_error #validation_errors
+            </div>
+        };
+        // Inputs tell assistive technology whether they need a value and whether it's flagged. This uses
+        // aria-required rather than required, so the browser's own validation doesn't get in the way
+        let flagged_method_ident = get_flagged_method_ident(field, input_struct_ident);
+        let aria_required = get_required(field, &fields, &struct_attrs).map(|required| {
+            quote! { aria-required={(#required).then(|| "true")} }
+        });
+        let aria = quote! {
+            #aria_required aria-invalid={self.#flagged_method_ident().then(|| "true")} aria-describedby={error_id.clone()} #contract data-formulay-role="input"
+        };
+        // A flagged field is marked with text beside its label as well as by its classes, since
+        // forced colors modes (i.e. Windows High Contrast) drop the colors the classes add. Screen
+        // readers skip it, as they already hear the errors.
+        let indicator_text = match get_required_missing(field, &fields, &struct_attrs) {
+            Some(missing) => quote! {
+                if self.display_required_warnings && #missing { "⚠ Required" } else { "⚠ Invalid" }
+            },
+            None => quote! { "⚠ Invalid" },
+        };
+        let indicator = quote! {
+            if ctx.props().state_indicators.unwrap_or(true) && self.#flagged_method_ident() {
+                <span class="formula-y-indicator" aria-hidden="true" #contract data-formulay-role="indicator">{#indicator_text}</span>
+            }
+        };
+        let inner = if let Some(custom) = &attrs.custom {
+            // A custom renderer draws the input itself, from the value and a callback updating it.
+            // Its output is grouped under the label, since the label can't point into it.
+            let msg_variant_ident = get_update_field_msg_variant_ident(field, input_struct_ident);
+            quote! {
+                <div #contract data-formulay-role="field" class={classes!("formula-y-form-item", theme.item_classes())}>
+                <span id={format!("{}-label", field_id)} class={label_class} #contract data-formulay-role="label">{#label}</span>
+                #indicator
+                <div id={field_id.clone()} class="formula-y-custom" role="group" aria-labelledby={format!("{}-label", field_id)} aria-describedby={error_id.clone()}>
+                    { #custom(&self.inner().#field_ident, ctx.link().callback(#component_msg_ident::#msg_variant_ident)) }
+                </div>
+                #field_errors
+                </div>
+            }
+        } else if let (Some((dependencies, _)), true) = (&attrs.computed, attrs.output || struct_attrs.computed_outputs) {
+            // A computed value is a result rather than something to type, so it can be shown in an
+            // <output> pointing at the inputs it's calculated from, which also announces changes
+            let dependency_suffixes = dependencies
+                .iter()
+                .map(|dependency| field_name(dependency).to_case(Case::Kebab));
+            let value = review::display_value(field, quote! { self.inner().#field_ident });
+            quote! {
+                <div #contract data-formulay-role="field" class={classes!("formula-y-form-item", "formula-y-output", theme.item_classes())}>
+                <label for={field_id.clone()} class={label_class} #contract data-formulay-role="label">{#label}</label>
+                #indicator
+                <output id={field_id.clone()} for={[#(self.field_id(#dependency_suffixes)),*].join(" ")} name={#name} class={input_class.clone()} aria-describedby={error_id.clone()} #contract data-formulay-role="value">{#value}</output>
+                #field_errors
+                </div>
+            }
+        } else if attrs.options_prop {
+            // The options come from a prop, with an empty first choice for when nothing is picked yet
+            let options_ident = get_options_prop_ident(field);
+            let value = if field_is_string(field) {
+                quote! { self.inner().#field_ident.clone() }
+            } else {
+                quote! { self.inner().#field_ident.clone().unwrap_or_default() }
+            };
+            let options = if attrs.grouped {
+                // Entries sharing a group are gathered into one optgroup, in the order the groups
+                // first appear. Entries with an empty group are left outside of any optgroup.
+                quote! {
+                    {{
+                        let mut groups: Vec<(&String, Vec<(&String, &String)>)> = Vec::new();
+                        for (group, value, label) in ctx.props().#options_ident.iter().flatten() {
+                            match groups.iter_mut().find(|(existing, _)| *existing == group) {
+                                Some((_, options)) => options.push((value, label)),
+                                None => groups.push((group, vec![(value, label)])),
+                            }
+                        }
+                        groups.into_iter().map(|(group, options)| {
+                            let options = options.into_iter().map(|(value, label)| html! {
+                                <option value={value.clone()} selected={*value == #value}>{label}</option>
+                            });
+                            if group.is_empty() {
+                                options.collect::<Html>()
+                            } else {
+                                html! { <optgroup label={group.clone()}>{ for options }</optgroup> }
+                            }
+                        }).collect::<Html>()
+                    }
+                }}
+            } else {
+                quote! {
+                    { for ctx.props().#options_ident.iter().flatten().map(|(value, label)| html! {
+                        <option value={value.clone()} selected={*value == #value}>{label}</option>
+                    }) }
+                }
+            };
+            quote! {
+                <div #contract data-formulay-role="field" class={classes!("formula-y-form-item", theme.item_classes())}>
+                <label for={field_id.clone()} class={label_class} #contract data-formulay-role="label">{#label}</label>
+                #indicator
+                <select id={field_id} name={#name} #aria class={input_class.clone()} disabled={read_only} onfocus={onfocus} onkeydown={onkeydown} onblur={onblur} #listener={ctx.link().callback(move |event: #event_type| {
+                    let new_value = event
+                        .target()
+                        .unwrap()
+                        .unchecked_into::<HtmlSelectElement>()
+                        .value();
+
+                    #component_ident::#msg_method_ident(new_value)
+                })}>
+                    <option value="" selected={#value.is_empty()}>{#empty_label}</option>
+                    #options
+                </select>
+                #field_errors
+                </div>
+            }
+        } else if !attrs.choices.is_empty() {
+            // Each choice is offered as it is written, with an empty first choice in a select for
+            // when nothing is picked yet
+            let choices = &attrs.choices;
+            let chosen = if field_is_string(field) {
+                quote! { self.inner().#field_ident.as_str() }
+            } else {
+                quote! { self.inner().#field_ident.as_deref().unwrap_or_default() }
+            };
+            if attrs.radio {
+                // Only the chosen radio, or the first while none is, is in the tab order
+                let current = quote! {
+                    [#(#choices),*].iter().position(|choice| #chosen == *choice).unwrap_or_default()
+                };
+                quote! {
+                    <div #contract data-formulay-role="field" class={classes!("formula-y-form-item", theme.item_classes())}>
+                    <fieldset id={field_id.clone()} role="radiogroup" #aria class={format!("{} formula-y-radio-group", input_class.clone())} disabled={read_only}>
+                        <legend class={label_class} #contract data-formulay-role="label">{#label}</legend>
+                        #indicator
+                        { for [#(#choices),*].into_iter().enumerate().map(|(index, choice)| html! {
+                            <label class="formula-y-radio">
+                                <input type="radio" name={#radio_name} value={choice} checked={#chosen == choice} tabindex={RovingFocus::tabindex(index, #current)} #listener={ctx.link().callback(move |event: #event_type| {
+                                    let new_value = event
+                                        .target()
+                                        .unwrap()
+                                        .unchecked_into::<HtmlInputElement>()
+                                        .value();
+
+                                    #component_ident::#msg_method_ident(new_value)
+                                })} />
+                                {choice}
+                            </label>
+                        }) }
+                    </fieldset>
+                    #field_errors
+                    </div>
+                }
+            } else {
+                quote! {
+                    <div #contract data-formulay-role="field" class={classes!("formula-y-form-item", theme.item_classes())}>
+                    <label for={field_id.clone()} class={label_class} #contract data-formulay-role="label">{#label}</label>
+                    #indicator
+                    <select id={field_id} name={#name} #aria class={input_class.clone()} disabled={read_only} onfocus={onfocus} onkeydown={onkeydown} onblur={onblur} #listener={ctx.link().callback(move |event: #event_type| {
+                        let new_value = event
+                            .target()
+                            .unwrap()
+                            .unchecked_into::<HtmlSelectElement>()
+                            .value();
+
+                        #component_ident::#msg_method_ident(new_value)
+                    })}>
+                        <option value="" selected={#chosen.is_empty()}>{#empty_label}</option>
+                        { for [#(#choices),*].into_iter().map(|choice| html! {
+                            <option value={choice} selected={#chosen == choice}>{choice}</option>
+                        }) }
+                    </select>
+                    #field_errors
+                    </div>
+                }
+            }
+        } else if field_is_string(field) && attrs.is_textarea() {
+            quote! {
+                <div #contract data-formulay-role="field" class={classes!("formula-y-form-item", theme.item_classes())}>
+                <label for={field_id.clone()} class={label_class} #contract data-formulay-role="label">{#label}</label>
+                #indicator
+                <textarea id={field_id} name={#name} #aria readonly={read_only} #placeholder onfocus={onfocus} onkeydown={onkeydown} onblur={onblur} #textarea_attrs value={self.inner().#field_ident.clone()} #onpaste onchange={ctx.link().callback(move |event: Event| {
+                    let element = event.target().unwrap().unchecked_into::<HtmlTextAreaElement>();
+                    let new_value = element.value();
+                    #show_transformed
+
+                    #component_ident::#msg_method_ident(new_value)
+                })} />
+                #field_errors
+                </div>
+            }
+        } else if field_is_string(field) {
+            quote! {
+                <div #contract data-formulay-role="field" class={classes!("formula-y-form-item", theme.item_classes())}>
+                <label for={field_id.clone()} class={label_class} #contract data-formulay-role="label">{#label}</label>
+                #indicator
+                <input id={field_id} name={#name} #aria class={input_class.clone()} type={#text_type} #autocomplete readonly={read_only} #placeholder onfocus={onfocus} onkeydown={onkeydown} onblur={onblur} value={self.inner().#field_ident.clone()} #onpaste onchange={ctx.link().callback(move |event: Event| {
+                    let element = event.target().unwrap().unchecked_into::<HtmlInputElement>();
+                    let new_value = element.value();
+                    #show_transformed
+
+                    #component_ident::#msg_method_ident(new_value)
+                })} />
+                #password_toggle
+                #field_errors
+                </div>
+            }
+        } else if field_is_bool(field) {
+
+            quote! {
+                <div #contract data-formulay-role="field" class={classes!("formula-y-form-item", theme.item_classes())}>
+                <label for={field_id.clone()} class={label_class} #contract data-formulay-role="label">{#label}</label>
+                #indicator
+                <input id={field_id} name={#name} #aria class={input_class.clone()} type="checkbox" disabled={read_only} onfocus={onfocus} onkeydown={onkeydown} onblur={onblur} checked={self.inner().#field_ident} #listener={ctx.link().callback(move |event: #event_type| {
+                    let new_value = event
+                        .target()
+                        .unwrap()
+                        .unchecked_into::<HtmlInputElement>()
+                        .checked();
+
+                    #component_ident::#msg_method_ident(new_value)
+                })} />
+                #field_errors
+                </div>
+            }
+        } else if field_is_option_string(field) && attrs.is_textarea() {
+
+            quote! {
+                <div #contract data-formulay-role="field" class={classes!("formula-y-form-item", theme.item_classes())}>
+                <label for={field_id.clone()} class={label_class} #contract data-formulay-role="label">{#label}</label>
+                #indicator
+                <textarea id={field_id} name={#name} #aria readonly={read_only} #placeholder onfocus={onfocus} onkeydown={onkeydown} onblur={onblur} #textarea_attrs value={self.inner().#field_ident.clone().unwrap_or_default()} #onpaste onchange={ctx.link().callback(move |event: Event| {
+                    let element = event.target().unwrap().unchecked_into::<HtmlTextAreaElement>();
+                    let new_value = element.value();
+                    #show_transformed
+
+                    #component_ident::#msg_method_ident(new_value)
+                })} />
+                #field_errors
+                </div>
+            }
+        } else if field_is_option_string(field) {
+
+            quote! {
+                <div #contract data-formulay-role="field" class={classes!("formula-y-form-item", theme.item_classes())}>
+                <label for={field_id.clone()} class={label_class} #contract data-formulay-role="label">{#label}</label>
+                #indicator
+                <input id={field_id} name={#name} #aria class={input_class.clone()} type={#text_type} #autocomplete readonly={read_only} #placeholder onfocus={onfocus} onkeydown={onkeydown} onblur={onblur} value={self.inner().#field_ident.clone().unwrap_or_default()} #onpaste onchange={ctx.link().callback(move |event: Event| {
+                    let element = event.target().unwrap().unchecked_into::<HtmlInputElement>();
+                    let new_value = element.value();
+                    #show_transformed
+
+                    #component_ident::#msg_method_ident(new_value)
+                })} />
+                #password_toggle
+                #field_errors
+                </div>
+            }
+        } else if field_is_option_bool(field) {
+
+            quote! {
+                <div #contract data-formulay-role="field" class={classes!("formula-y-form-item", theme.item_classes())}>
+                <label for={field_id.clone()} class={label_class} #contract data-formulay-role="label">{#label}</label>
+                #indicator
+                <input id={field_id} name={#name} #aria class={input_class.clone()} type="checkbox" disabled={read_only} onfocus={onfocus} onkeydown={onkeydown} onblur={onblur} checked={self.inner().#field_ident.unwrap_or_default()} #listener={ctx.link().callback(move |event: #event_type| {
+                    let new_value = event
+                        .target()
+                        .unwrap()
+                        .unchecked_into::<HtmlInputElement>()
+                        .checked();
+
+                    #component_ident::#msg_method_ident(new_value)
+                })} />
+                #field_errors
+                </div>
+            }
+        } else if field_is_parsed(field) {
+            // Text which didn't parse is shown as typed rather than the last value that did
+            let field_key = get_field_key(field, &field_enum_ident);
+            let field_variant = get_field_variant_ident(field);
+            let number_text = get_parsed_text(field, quote! { self.inner().#field_ident });
+            // A value with units is shown in the unit picked, apart from the first, which it's
+            // stored in as is
+            let number_text = if attrs.unit_options.is_empty() {
+                number_text
+            } else {
+                let in_unit = if field_is_option(field) {
+                    quote! { self.inner().#field_ident.map(|value| self.in_unit(#field_enum_ident::#field_variant, value as f64)) }
+                } else {
+                    quote! { self.in_unit(#field_enum_ident::#field_variant, self.inner().#field_ident as f64) }
+                };
+                let in_unit_text = get_parsed_text(field, in_unit);
+                quote! {
+                    if self.unit(#field_enum_ident::#field_variant) == 0 {
+                        #number_text
+                    } else {
+                        #in_unit_text
+                    }
+                }
+            };
+            // Formatted numbers are text inputs, since number inputs can't show separators. They
+            // show the plain number while focused, for editing.
+            let (input_type, number_text) = if attrs.range {
+                // Sliders step by whole numbers unless told otherwise, which a float shouldn't be
+                let min = attrs.min.as_ref().map(|min| quote! { min=#min });
+                let max = attrs.max.as_ref().map(|max| quote! { max=#max });
+                let step = field_is_float(field).then(|| quote! { step="any" });
+                (quote! { type="range" #min #max #step }, number_text)
+            } else if attrs.format.is_some() {
+                (
+                    quote! { type="text" inputmode="decimal" },
+                    quote! {
+                        if self.focused_formatted == Some(#field_enum_ident::#field_variant) {
+                            #number_text
+                        } else {
+                            Self::format_thousands(#number_text)
+                        }
+                    },
+                )
+            } else if attrs.no_spinner {
+                // Text inputs have no spin buttons, and inputmode keeps the numeric keyboard
+                let inputmode = if field_is_float(field) {
+                    "decimal"
+                } else {
+                    "numeric"
+                };
+                (quote! { type="text" inputmode=#inputmode }, number_text)
+            } else if attrs.date_text {
+                // The format to type in is hinted at unless the field has a placeholder of its own
+                let hint = if attrs.placeholder.is_some() {
+                    quote! {}
+                } else if field_is_datetime(field) {
+                    quote! { placeholder={format!("{} hh:mm", DateOrder::detect().placeholder())} }
+                } else {
+                    quote! { placeholder={DateOrder::detect().placeholder()} }
+                };
+                (quote! { type="text" inputmode="numeric" #hint }, number_text)
+            } else if field_is_datetime(field) {
+                (quote! { type="datetime-local" }, number_text)
+            } else if field_is_date(field) || field_is_option_date(field) {
+                (quote! { type="date" }, number_text)
+            } else {
+                (quote! { type="number" }, number_text)
+            };
+            let allowed_chars = get_allowed_number_chars(field, &attrs);
+            let input_class = if attrs.no_spinner {
+                quote! { format!("{} formula-y-no-spinner", input_class.clone()) }
+            } else {
+                quote! { input_class.clone() }
+            };
+            // A rounded value is written back to the input, which otherwise keeps showing the
+            // extra places when the rounded value renders the same as the last one
+            let show_rounded = if attrs.precision.is_some()
+                && attrs.format.is_none()
+                && attrs.unit_options.is_empty()
+            {
+                let msg_variant_ident = get_update_field_msg_variant_ident(field, input_struct_ident);
+                let rounded_text = get_parsed_text(field, quote! { value });
+                quote! {
+                    if let #component_msg_ident::#msg_variant_ident(value) = &msg {
+                        element.set_value(&#rounded_text);
+                    }
+                }
+            } else {
+                quote! {}
+            };
+            // Text entered in another unit is converted into the first before it's parsed
+            let msg = if attrs.unit_options.is_empty() {
+                quote! { #component_ident::#msg_method_ident(new_value) }
+            } else {
+                quote! {
+                    #component_msg_ident::EnterInUnit(#field_enum_ident::#field_variant, new_value, #component_ident::#msg_method_ident)
+                }
+            };
+            let unit_select = (!attrs.unit_options.is_empty()).then(|| {
+                let units = &attrs.unit_options;
+                quote! {
+                    <select class="formula-y-unit" aria-label={format!("{} unit", #label)} #contract data-formulay-role="unit" disabled={read_only} onchange={ctx.link().callback(move |event: Event| {
+                        let unit = event.target().unwrap().unchecked_into::<HtmlSelectElement>().selected_index();
+                        #component_msg_ident::ChooseUnit(#field_enum_ident::#field_variant, unit.max(0) as usize)
+                    })}>
+                        { for [#(#units),*].iter().enumerate().map(|(unit, name)| html! {
+                            <option value={*name} selected={self.unit(#field_enum_ident::#field_variant) == unit}>{*name}</option>
+                        }) }
+                    </select>
+                }
+            });
+            // A slider stores its value as it moves, as well as once it's let go
+            let oninput = attrs.range.then(|| {
+                quote! {
+                    oninput={ctx.link().callback(move |event: InputEvent| {
+                        let new_value = event.target().unwrap().unchecked_into::<HtmlInputElement>().value();
+                        #component_ident::#msg_method_ident(new_value)
+                    })}
+                }
+            });
+            quote! {
+                <div #contract data-formulay-role="field" class={classes!("formula-y-form-item", theme.item_classes())}>
+                <label for={field_id.clone()} class={label_class} #contract data-formulay-role="label">{#label}</label>
+                #indicator
+                <input id={field_id} name={#name} #aria class={#input_class} #input_type #allowed_chars readonly={read_only} #placeholder onfocus={onfocus} onkeydown={onkeydown} onblur={onblur} #onpaste #oninput value={self.invalid_numbers.get(&#field_key).cloned().unwrap_or_else(|| #number_text)} onchange={ctx.link().callback(move |event: Event| {
+                    let element = event.target().unwrap().unchecked_into::<HtmlInputElement>();
+                    let new_value = element.value();
+
+                    let msg = #msg;
+                    #show_rounded
+                    msg
+                })} />
+                #unit_select
+                #field_errors
+                </div>
+            }
+        } else if field_is_enum(field) || field_is_option_enum(field) {
+            // Each variant of the enum is a choice, with an empty first choice for an Option which
+            // is None
+            let enum_type = enum_type(field);
+            let chosen = if field_is_enum(field) {
+                quote! { self.inner().#field_ident.value() }
+            } else {
+                quote! { self.inner().#field_ident.as_ref().map_or("", YFormSelect::value) }
+            };
+            let choices = quote! {
+                <#enum_type as YFormSelect>::VALUES.iter().zip(<#enum_type as YFormSelect>::LABELS)
+            };
+            if attrs.radio {
+                // The radios are grouped in a fieldset, whose legend labels the whole group. Only
+                // the chosen radio, or the first while none is, is in the tab order.
+                let current = quote! {
+                    <#enum_type as YFormSelect>::VALUES.iter().position(|value| #chosen == *value).unwrap_or_default()
+                };
+                quote! {
+                    <div #contract data-formulay-role="field" class={classes!("formula-y-form-item", theme.item_classes())}>
+                    <fieldset id={field_id.clone()} role="radiogroup" #aria class={format!("{} formula-y-radio-group", input_class.clone())} disabled={read_only}>
+                        <legend class={label_class} #contract data-formulay-role="label">{#label}</legend>
+                        #indicator
+                        { for #choices.enumerate().map(|(index, (value, label))| html! {
+                            <label class="formula-y-radio">
+                                <input type="radio" name={#radio_name} value={*value} checked={#chosen == *value} tabindex={RovingFocus::tabindex(index, #current)} #listener={ctx.link().batch_callback(move |event: #event_type| {
+                                    let new_value = event
+                                        .target()
+                                        .unwrap()
+                                        .unchecked_into::<HtmlInputElement>()
+                                        .value();
+
+                                    #component_ident::#msg_method_ident(new_value)
+                                })} />
+                                {*label}
+                            </label>
+                        }) }
+                    </fieldset>
+                    #field_errors
+                    </div>
+                }
+            } else {
+                let empty_choice = if field_is_option_enum(field) {
+                    quote! { <option value="" selected={#chosen.is_empty()}>{#empty_label}</option> }
+                } else {
+                    quote! {}
+                };
+                // Time zones are grouped by region, and narrowed down by a search box above the
+                // select. The chosen zone stays whatever is searched for, so the select shows it.
+                let (search, choices) = if field_is_time_zone(field) {
+                    let field_variant = get_field_variant_ident(field);
+                    let search = quote! {
+                        <input type="search" class="formula-y-time-zone-search" #contract data-formulay-role="search" aria-label={format!("Search {}", #label)} placeholder="Search" aria-controls={field_id.clone()} disabled={read_only} value={self.time_zone_searches.get(&#field_enum_ident::#field_variant).cloned().unwrap_or_default()} oninput={ctx.link().callback(move |event: InputEvent| {
+                            let search = event.target().unwrap().unchecked_into::<HtmlInputElement>().value();
+                            #component_msg_ident::SearchTimeZones(#field_enum_ident::#field_variant, search)
+                        })} />
+                    };
+                    let choices = quote! {
+                        { for time_zones_by_region(self.time_zone_searches.get(&#field_enum_ident::#field_variant).map_or("", String::as_str), #chosen).into_iter().map(|(region, zones)| {
+                            let options = zones.into_iter().map(|(value, label)| html! {
+                                <option value={value} selected={#chosen == value}>{label}</option>
+                            });
+                            if region.is_empty() {
+                                options.collect::<Html>()
+                            } else {
+                                html! { <optgroup label={region}>{ for options }</optgroup> }
+                            }
+                        }) }
+                    };
+                    (search, choices)
+                } else {
+                    let choices = quote! {
+                        { for #choices.map(|(value, label)| html! {
+                            <option value={*value} selected={#chosen == *value}>{*label}</option>
+                        }) }
+                    };
+                    (quote! {}, choices)
+                };
+                quote! {
+                    <div #contract data-formulay-role="field" class={classes!("formula-y-form-item", theme.item_classes())}>
+                    <label for={field_id.clone()} class={label_class} #contract data-formulay-role="label">{#label}</label>
+                    #indicator
+                    #search
+                    <select id={field_id} name={#name} #aria class={input_class.clone()} disabled={read_only} onfocus={onfocus} onkeydown={onkeydown} onblur={onblur} #listener={ctx.link().batch_callback(move |event: #event_type| {
+                        let new_value = event
+                            .target()
+                            .unwrap()
+                            .unchecked_into::<HtmlSelectElement>()
+                            .value();
+
+                        #component_ident::#msg_method_ident(new_value)
+                    })}>
+                        #empty_choice
+                        #choices
+                    </select>
+                    #field_errors
+                    </div>
+                }
+            }
+        } else if field_is_string_vec(field) || field_is_nested_vec(field) {
+            // Each item gets its own input, or nested form, with a button removing it, and a
+            // button after the last adds another
+            let field_variant = get_field_variant_ident(field);
+            let item_msg_variant_ident = get_update_item_msg_variant_ident(field, input_struct_ident);
+            let item = if field_is_string_vec(field) {
+                let item_class = format!("{}-item formula-y-txt-input", field_id_suffix);
+                quote! {
+                    <input id={format!("{}-{}", field_id, index)} name={#name} class={#item_class} #contract data-formulay-role="item" type="text" #placeholder aria-label={format!("{} {}", #label, index + 1)} value={item} onchange={ctx.link().callback(move |event: Event| {
+                        let new_value = event
+                            .target()
+                            .unwrap()
+                            .unchecked_into::<HtmlInputElement>()
+                            .value();
+
+                        #component_msg_ident::#item_msg_variant_ident(index, new_value)
+                    })} />
+                }
+            } else {
+                let item_type = item_type(field);
+                quote! {
+                    { <#item_type as YFormNested>::nested_view(item, format!("{}-{}", field_id, key), NestedLink {
+                        onchange: ctx.link().callback(move |item| #component_msg_ident::#item_msg_variant_ident(index, item)),
+                        onvalidity: ctx.link().callback(move |valid| #component_msg_ident::NestedValidity(#field_enum_ident::#field_variant, key, valid)),
+                        display_required_warnings: self.display_required_warnings,
+                        enforce_required_fields: ctx.props().enforce_required_fields.unwrap_or_default().blocks_submit(),
+                    }) }
+                }
+            };
+            // Add is disabled once the field holds max_items, and Remove once it holds min_items
+            let add_disabled = attrs.max_items.map(|max_items| {
+                quote! { disabled={self.inner().#field_ident.len() >= #max_items} }
+            });
+            let remove_disabled = attrs.min_items.map(|min_items| {
+                quote! { disabled={self.inner().#field_ident.len() <= #min_items} }
+            });
+            quote! {
+                <div #contract data-formulay-role="field" class={classes!("formula-y-form-item", theme.item_classes())}>
+                <fieldset id={field_id.clone()} class={format!("{} formula-y-repeat", input_class.clone())} #contract data-formulay-role="input" disabled={read_only}>
+                    <legend class={label_class} #contract data-formulay-role="label">{#label}</legend>
+                    #indicator
+                    { for self.inner().#field_ident.clone().into_iter().enumerate().map(|(index, item)| {
+                        let key = self.item_key(#field_enum_ident::#field_variant, index);
+                        html! {
+                            <div {key} class="formula-y-repeat-item">
+                                #item
+                                <button type="button" class={classes!("formula-y-remove", &theme.button)} #contract data-formulay-role="remove" #remove_disabled onclick={ctx.link().callback(move |_| #component_msg_ident::RemoveItem(#field_enum_ident::#field_variant, index))}>{"Remove"}</button>
+                            </div>
+                        }
+                    }) }
+                    <button type="button" class={classes!("formula-y-add", &theme.button)} #contract data-formulay-role="add" #add_disabled onclick={ctx.link().callback(|_| #component_msg_ident::AddItem(#field_enum_ident::#field_variant))}>{"Add"}</button>
+                </fieldset>
+                #field_errors
+                </div>
+            }
+        } else if field_is_nested(field) {
+            // The nested struct's own form is rendered inside the fieldset, passing every change
+            // back up as a whole new value
+            let field_type = &field.ty;
+            let msg_variant_ident = get_update_field_msg_variant_ident(field, input_struct_ident);
+            let field_variant = get_field_variant_ident(field);
+            let fieldset_class = format!("{}-fieldset formula-y-fieldset", field_id_suffix);
+            quote! {
+                <div #contract data-formulay-role="field" class={classes!("formula-y-form-item", theme.item_classes())}>
+                <fieldset class={#fieldset_class} #contract data-formulay-role="input" disabled={read_only}>
+                    <legend class={label_class} #contract data-formulay-role="label">{#label}</legend>
+                    #indicator
+                    { <#field_type as YFormNested>::nested_view(self.inner().#field_ident.clone(), field_id, NestedLink {
+                        onchange: ctx.link().callback(#component_msg_ident::#msg_variant_ident),
+                        onvalidity: ctx.link().callback(|valid| #component_msg_ident::NestedValidity(#field_enum_ident::#field_variant, 0, valid)),
+                        display_required_warnings: self.display_required_warnings,
+                        enforce_required_fields: ctx.props().enforce_required_fields.unwrap_or_default().blocks_submit(),
+                    }) }
+                </fieldset>
+                #field_errors
+                </div>
+            }
+        } else {
+            quote! {
+                <p>{"type not supported"}</p>
+            }
+        };
+
+        let field_variant = get_field_variant_ident(field);
+        // Computed fields are shown, but only ever changed by recalculating them, and readonly
+        // fields aren't changed at all
+        let computed = attrs.computed.is_some() || attrs.readonly;
+        let label_placeholder_setup = if can_show_label_placeholder {
+            let responsive = struct_attrs.label_placeholders_below.is_some();
+            quote! {
+                let label_placeholder = #responsive || ctx.props().label_placeholders.unwrap_or(#search);
+                let label_class = if label_placeholder {
+                    format!("{} formula-y-placeholder-label", self.#method_name_label_ident())
+                } else {
+                    self.#method_name_label_ident()
+                };
+            }
+        } else {
+            quote! { let label_class = self.#method_name_label_ident(); }
+        };
+        // The theme's classes are added as the form renders, so that a new theme restyles it
+        let theme_input = if field_is_bool(field) || field_is_option_bool(field) {
+            quote! { checkbox }
+        } else {
+            quote! { input }
+        };
+        let label_placeholder_setup = quote! {
+            #label_placeholder_setup
+            let label_class = classes!(label_class, &theme.label).to_string();
+            let input_class = classes!(self.#method_name_input_ident(), &theme.#theme_input).to_string();
+        };
+        let escape_revert_listeners = get_escape_revert_listeners(field, &attrs, &field_enum_ident, &component_msg_ident);
+        // A field waiting on its init value is shown disabled, marked busy for screen readers
+        let loading_placeholder = quote! {
+            let item = html! { #inner };
+            if loading {
+                html! { <div class="formula-y-loading" aria-busy="true">{item}</div> }
+            } else {
+                item
+            }
+        };
+        let form_field = if field_has_single_input(field, &attrs) {
+            // Until its edit button is clicked, an inline field shows its value as text
+            let inline_edit_listeners = get_inline_edit_listeners(field, &attrs, &field_enum_ident, &component_msg_ident);
+            let format_listeners = get_format_listeners(field, &attrs, &field_enum_ident, &component_msg_ident);
+            let value = review::display_value(field, quote! { self.inner().#field_ident });
+            let edit_label = quote! { format!("Edit {}", field_label) };
+            // A masked field shows a value the user hasn't changed as bullets and its last few
+            // characters, until its Change button clears it for new input
+            let masked_branch = if attrs.masked_display {
+                let (has_value, text) = if field_is_option_string(field) {
+                    (
+                        quote! { self.inner().#field_ident.as_ref().is_some_and(|value| !value.is_empty()) },
+                        quote! { self.inner().#field_ident.as_deref().unwrap_or_default() },
+                    )
+                } else {
+                    (
+                        quote! { !self.inner().#field_ident.is_empty() },
+                        quote! { &self.inner().#field_ident },
+                    )
+                };
+                let change_label = quote! { format!("Change {}", field_label) };
+                quote! {
+                    else if #has_value && !self.edited.contains(&#field_enum_ident::#field_variant) {
+                        html! {
+                            <div #contract data-formulay-role="field" class={classes!("formula-y-form-item", "formula-y-masked", theme.item_classes())}>
+                                <span class={classes!(self.#method_name_label_ident(), &theme.label)} #contract data-formulay-role="label">{#label}</span>
+                                <span id={field_id} class="formula-y-masked-value" #contract data-formulay-role="value">{mask_value(#text)}</span>
+                                if !read_only {
+                                    <button type="button" class={classes!("formula-y-masked-change", &theme.button)} #contract data-formulay-role="unmask" aria-label={#change_label} onclick={ctx.link().callback(|_| #component_msg_ident::UnmaskField(#field_enum_ident::#field_variant))}>{"Change"}</button>
+                                }
+                            </div>
+                        }
+                    }
+                }
+            } else {
+                quote! {}
+            };
+            quote! {
+                {{
+                    let field_id = self.field_id(#field_id_suffix);
+                    let error_id = format!("{}-error", field_id);
+                    let access = self.field_access(ctx, #field_enum_ident::#field_variant);
+                    let loading = self.loading.contains(&#field_enum_ident::#field_variant);
+                    let read_only = #computed || ctx.props().disabled.unwrap_or_default() || access == FieldAccess::ReadOnly || loading || self.locked || self.expired;
+                    let field_label = self.label_for(ctx, #field_enum_ident::#field_variant, #label_text);
+                    let inline = ctx.props().inline_edit.unwrap_or(#save_individually);
+                    #label_placeholder_setup
+                    #escape_revert_listeners
+                    #inline_edit_listeners
+                    #format_listeners
+                    if access == FieldAccess::Hidden {
+                        html! {}
+                    } #masked_branch else if inline && self.inline_editing != Some(#field_enum_ident::#field_variant) {
+                        html! {
+                            <div #contract data-formulay-role="field" class={classes!("formula-y-form-item", "formula-y-inline", theme.item_classes())}>
+                                <span class={classes!(self.#method_name_label_ident(), &theme.label)} #contract data-formulay-role="label">{#label}</span>
+                                <span id={field_id} class="formula-y-inline-value" #contract data-formulay-role="value">{#value}</span>
+                                if !read_only {
+                                    <button type="button" class={classes!("formula-y-inline-edit", &theme.button)} #contract data-formulay-role="edit" aria-label={#edit_label} onclick={ctx.link().callback(|_| #component_msg_ident::EditInline(#field_enum_ident::#field_variant))}>{"✎"}</button>
+                                }
+                            </div>
+                        }
+                    } else {
+                        #loading_placeholder
+                    }
+                }}
+            }
+        } else {
+            quote! {
+                {{
+                    let field_id = self.field_id(#field_id_suffix);
+                    let error_id = format!("{}-error", field_id);
+                    let access = self.field_access(ctx, #field_enum_ident::#field_variant);
+                    let loading = self.loading.contains(&#field_enum_ident::#field_variant);
+                    let read_only = #computed || ctx.props().disabled.unwrap_or_default() || access == FieldAccess::ReadOnly || loading || self.locked || self.expired;
+                    let field_label = self.label_for(ctx, #field_enum_ident::#field_variant, #label_text);
+                    #label_placeholder_setup
+                    if access == FieldAccess::Hidden {
+                        html! {}
+                    } else {
+                        #loading_placeholder
+                    }
+                }}
+            }
+        };
+        // The fields of a multi-step form stay in the page while other steps are showing, only hidden
+        let form_field = if steps.is_empty() {
+            form_field
+        } else {
+            let step = get_step_index(field, &steps);
+            quote! {
+                <div class="formula-y-step" hidden={self.current_step != #step}>
+                    #form_field
+                </div>
+            }
+        };
+        (attrs, form_field)
+    }).collect::<Vec<_>>();
+
+    // Fields sharing a section are gathered into one collapsible block, placed where the first of
+    // them appears in the struct
+    let mut sections: Vec<(&String, bool, Vec<&proc_macro2::TokenStream>)> = Vec::new();
+    for (attrs, form_field) in form_fields.iter() {
+        if let Some(title) = &attrs.section {
+            match sections.iter_mut().find(|(t, _, _)| *t == title) {
+                Some((_, collapsed, section_fields)) => {
+                    *collapsed |= attrs.collapsed;
+                    section_fields.push(form_field);
+                }
+                None => sections.push((title, attrs.collapsed, vec![form_field])),
+            }
+        }
+    }
+    let mut rendered_sections = Vec::new();
+    let form_items = form_fields
+        .iter()
+        .filter_map(|(attrs, form_field)| {
+            let title = match &attrs.section {
+                Some(title) => title,
+                None => return Some(form_field.clone()),
+            };
+            if rendered_sections.contains(&title) {
+                return None;
+            }
+            rendered_sections.push(title);
+
+            let (_, collapsed, section_fields) =
+                sections.iter().find(|(t, _, _)| *t == title).unwrap();
+            let section_class = format!("{}-section formula-y-section", title.to_case(Case::Kebab));
+            let open = if *collapsed {
+                quote! {}
+            } else {
+                quote! { open=true }
+            };
+            // In a multi-step form the section is hidden along with the last of its fields
+            let hidden = if steps.is_empty() {
+                quote! {}
+            } else {
+                let section_steps = fields
+                    .iter()
+                    .filter(|field| FieldAttrs::from_field(field).section.as_ref() == Some(title))
+                    .map(|field| get_step_index(field, &steps));
+                quote! { hidden={![#(#section_steps),*].contains(&self.current_step)} }
+            };
+            Some(quote! {
+                <details class={#section_class} #open #hidden>
+                    <summary class="formula-y-section-title">{#title}</summary>
+                    #(#section_fields)*
+                </details>
+            })
+        })
+        .collect::<Vec<_>>();
+
+    // With the test-utils feature enabled we also generate a tester which drives the component
+    // through its msgs the same way the inputs do, so form logic can be unit tested without a browser.
+    let tester = if cfg!(feature = "test-utils") {
+        testing::tester(
+            input_struct_ident,
+            &component_ident,
+            &component_msg_ident,
+            &fields,
+            &struct_attrs,
+        )
+    } else {
+        quote! {}
+    };
+
+    // Browser level tests are opt in per struct since they need wasm-bindgen-test
+    let wasm_tests = if struct_attrs.wasm_tests {
+        testing::wasm_tests(input_struct_ident, &component_ident, &fields, &struct_attrs)
+    } else {
+        quote! {}
+    };
+
+    // With the debug-panel feature enabled, a collapsible panel under the form shows the live
+    // state of the component. The value itself is only printed for structs marked
+    // #[yform(serialize)], so enabling the feature doesn't require every form to be Serialize.
+    let debug_value = if struct_attrs.serialize {
+        quote! {
+            <pre>{serde_json::to_string_pretty(&*self.inner()).unwrap_or_else(|e| e.to_string())}</pre>
+        }
+    } else {
+        quote! {}
+    };
+    let debug_panel = if cfg!(feature = "debug-panel") {
+        quote! {
+            <details class="formula-y-debug-panel">
+                <summary>{"Debug"}</summary>
+                #debug_value
+                <ul>
+                    <li>{format!("required_components_provided: {}", self.required_components_provided())}</li>
+                    <li>{format!("display_required_warnings: {}", self.display_required_warnings)}</li>
+                    <li>{format!("submitted: {}", self.submitted)}</li>
+                    <li>{format!("dirty: {}", self.dirty)}</li>
+                </ul>
+            </details>
+        }
+    } else {
+        quote! {}
+    };
+
+    // With the clipboard feature enabled, the copy_button prop of a struct marked
+    // #[yform(serialize)] renders a button which copies the current value to the clipboard as
+    // JSON.
+    let copy_values = cfg!(feature = "clipboard") && struct_attrs.serialize;
+    let (copy_msg_variant, copy_apply_arm, copy_button) = if copy_values {
+        (
+            quote! { CopyValues, },
+            quote! {
+                #component_msg_ident::CopyValues => {
+                    let json = serde_json::to_string_pretty(&*self.inner()).unwrap_or_else(|e| e.to_string());
+                    if let Some(window) = web_sys::window() {
+                        // Nothing in the form depends on the copy finishing, so the promise is dropped
+                        let _ = window.navigator().clipboard().write_text(&json);
+                    }
+                    false
+                }
+            },
+            quote! {
+                if ctx.props().copy_button.unwrap_or_default() {
+                    <button type="button" class="formula-y-copy" onclick={link.callback(|_| #component_msg_ident::CopyValues)}>{"Copy values"}</button>
+                }
+            },
+        )
+    } else {
+        (quote! {}, quote! {}, quote! {})
+    };
+
+    // With #[yform(review)], a submit which passes the required field checks swaps the form for a
+    // read only summary of its values, and onsubmit is only called once that is confirmed.
+    // A multi-step form shows one step of fields at a time, with Back and Next buttons in place of
+    // submitting until the last step. The current step's validity is passed to onstepvalidity
+    // whenever it changes, or the step does.
+    let (
+        step_field,
+        step_init,
+        step_msg_variant,
+        step_apply_arm,
+        step_indicator,
+        review_step_jump,
+        submit_step_body,
+        step_methods,
+        step_rendered,
+    ) = if steps.is_empty() {
+        (
+            quote! {},
+            quote! {},
+            quote! {},
+            quote! {},
+            quote! {},
+            quote! {},
+            quote! { false },
+            quote! {},
+            quote! {},
+        )
+    } else {
+        let step_fields = (0..steps.len()).map(|step| {
+            let variants = fields
+                .iter()
+                .filter(|field| get_step_index(field, &steps) == step)
+                .map(get_field_variant_ident);
+            quote! { #step => &[#(#field_enum_ident::#variants),*] }
+        });
+        let step_count = steps.len();
+        let review_step_jump = if struct_attrs.review {
+            let step_arms = fields.iter().map(|field| {
+                let field_ident = field.ident.clone().unwrap();
+                let field_id_suffix = field_name(&field_ident).to_case(Case::Kebab);
+                let step = get_step_index(field, &steps);
+                quote! { #field_id_suffix => #step }
+            });
+            quote! {
+                self.current_step = match field {
+                    #(#step_arms,)*
+                    _ => self.current_step,
+                };
+            }
+        } else {
+            quote! {}
+        };
+        (
+            quote! {
+                // The step of a multi-step form being shown, counting from 0
+                current_step: usize,
+                // The last step and validity passed to onstepvalidity
+                step_validity: Option<(usize, bool)>,
+            },
+            quote! { current_step: 0, step_validity: None, },
+            quote! { PreviousStep, },
+            quote! {
+                #component_msg_ident::PreviousStep => {
+                    self.current_step = self.current_step.saturating_sub(1);
+                    self.display_required_warnings = false;
+                    true
+                }
+            },
+            quote! {
+                <p class="formula-y-step-indicator">{format!("Step {} of {}", self.current_step + 1, #step_count)}</p>
+            },
+            review_step_jump,
+            quote! {
+                if self.current_step == #last_step {
+                    return false;
+                }
+                if self.step_is_valid(self.current_step, enforce_required) {
+                    self.current_step += 1;
+                    self.display_required_warnings = false;
+                    self.submit_failed = false;
+                } else {
+                    self.display_required_warnings = true;
+                    self.submit_failed = true;
+                }
+                true
+            },
+            quote! {
+                // Whether every field of a step could be submitted, which Next checks before moving on
+                fn step_is_valid(&self, step: usize, enforce_required: bool) -> bool {
+                    let step_fields: &[#field_enum_ident] = match step {
+                        #(#step_fields,)*
+                        _ => &[],
+                    };
+                    step_fields.iter().all(|field| {
+                        self.field_is_valid(*field) && !(enforce_required && self.field_is_missing(*field))
+                    })
+                }
+
+                // Pass the current step and whether it's valid to onstepvalidity, when either has
+                // changed since it was last passed
+                fn emit_step_validity(&mut self, ctx: &Context<Self>) {
+                    let enforce_required = ctx.props().enforce_required_fields.unwrap_or_default().blocks_submit();
+                    let validity = (self.current_step, self.step_is_valid(self.current_step, enforce_required));
+                    if self.step_validity != Some(validity) {
+                        self.step_validity = Some(validity);
+                        if let Some(onstepvalidity) = &ctx.props().onstepvalidity {
+                            onstepvalidity.emit(validity);
+                        }
+                    }
+                }
+            },
+            quote! { self.emit_step_validity(ctx); },
+        )
+    };
+    let enforce_required_param = if steps.is_empty() {
+        quote! { _enforce_required }
+    } else {
+        quote! { enforce_required }
+    };
+
+    let (review_fields, review_inits, hold_for_review, review_msg_variant, review_apply_arm) =
+        if struct_attrs.review {
+            (
+                quote! {
+                    // Whether the summary is showing in place of the form
+                    reviewing: bool,
+                },
+                quote! {
+                    reviewing: false,
+                },
+                quote! {
+                    if self.reviewing {
+                        self.reviewing = false;
+                        false
+                    } else {
+                        self.reviewing = true;
+                        true
+                    }
+                },
+                quote! { EditField(&'static str), },
+                quote! {
+                    #component_msg_ident::EditField(field) => {
+                        self.reviewing = false;
+                        #review_step_jump
+                        self.focus_on_render = Some(field);
+                        true
+                    }
+                },
+            )
+        } else {
+            (quote! {}, quote! {}, quote! { false }, quote! {}, quote! {})
+        };
+
+    // Fields with #[yform(throttle_ms = N)] are updated at most once every N ms while they keep
+    // changing, i.e. while a slider is dragged. The first update goes through and starts the
+    // interval, during which only the latest update is kept, to go through once it ends.
+    let throttled_fields = fields
+        .iter()
+        .filter_map(|field| Some((field, FieldAttrs::from_field(field).throttle_ms?)))
+        .collect::<Vec<_>>();
+    let (
+        throttle_fields,
+        throttle_inits,
+        throttle_msg_variant,
+        throttle_apply_arm,
+        throttle_arms,
+        throttle_method,
+    ) = if throttled_fields.is_empty() {
+        (
+            quote! {},
+            quote! {},
+            quote! {},
+            quote! {},
+            quote! {},
+            quote! {},
+        )
+    } else {
+        let throttle_arms = throttled_fields.iter().map(|(field, throttle_ms)| {
+            let field_variant = get_field_variant_ident(field);
+            let msg_variant_ident = get_update_field_msg_variant_ident(field, input_struct_ident);
+            quote! {
+                msg @ #component_msg_ident::#msg_variant_ident(_)
+                    if self.throttle(ctx, #field_enum_ident::#field_variant, #throttle_ms) =>
+                {
+                    self.throttled.insert(#field_enum_ident::#field_variant, msg);
+                    false
+                }
+            }
+        });
+        (
+            quote! {
+                // The intervals of throttled fields still running, which are cancelled by dropping them
+                throttle_timers: std::collections::HashMap<#field_enum_ident, gloo_timers::callback::Timeout>,
+                // The latest update held back from each throttled field
+                throttled: std::collections::HashMap<#field_enum_ident, #component_msg_ident>,
+            },
+            quote! {
+                throttle_timers: std::collections::HashMap::new(),
+                throttled: std::collections::HashMap::new(),
+            },
+            quote! { ThrottleElapsed(#field_enum_ident), },
+            quote! { #component_msg_ident::ThrottleElapsed(_) | },
+            quote! {
+                #(#throttle_arms)*
+                #component_msg_ident::ThrottleElapsed(field) => {
+                    self.throttle_timers.remove(&field);
+                    match self.throttled.remove(&field) {
+                        Some(msg) => self.update(ctx, msg),
+                        None => false,
+                    }
+                }
+            },
+            quote! {
+                // Whether an update to a throttled field is held back, starting its interval
+                // when none is running
+                fn throttle(&mut self, ctx: &Context<Self>, field: #field_enum_ident, throttle_ms: u32) -> bool {
+                    if self.throttle_timers.contains_key(&field) {
+                        return true;
+                    }
+                    let link = ctx.link().clone();
+                    self.throttle_timers.insert(field, gloo_timers::callback::Timeout::new(throttle_ms, move || {
+                        link.send_message(#component_msg_ident::ThrottleElapsed(field));
+                    }));
+                    false
+                }
+            },
+        )
+    };
+
+    // Floats with #[yform(unit_options(...), unit_factors(...))] are entered in whichever of the
+    // units is picked beside their input, and stored in the first. The picked units are kept by the
+    // form, each field starting out in the first.
+    let (unit_fields, unit_inits, unit_msg_variants, unit_apply_arms, unit_methods) = if fields
+        .iter()
+        .all(|field| FieldAttrs::from_field(field).unit_options.is_empty())
+    {
+        (quote! {}, quote! {}, quote! {}, quote! {}, quote! {})
+    } else {
+        let factor_arms = fields.iter().map(|field| {
+            let field_variant = get_field_variant_ident(field);
+            let factors = FieldAttrs::from_field(field).unit_factors;
+            if factors.is_empty() {
+                quote! { #field_enum_ident::#field_variant => 1.0 }
+            } else {
+                quote! { #field_enum_ident::#field_variant => [#(#factors),*][self.unit(field)] }
+            }
+        });
+        (
+            quote! {
+                // The index of the unit picked for each field with unit_options, apart from the first
+                units: std::collections::HashMap<#field_enum_ident, usize>,
+            },
+            quote! { units: std::collections::HashMap::new(), },
+            quote! {
+                ChooseUnit(#field_enum_ident, usize),
+                EnterInUnit(#field_enum_ident, String, fn(String) -> #component_msg_ident),
+            },
+            quote! {
+                #component_msg_ident::ChooseUnit(field, unit) => {
+                    self.units.insert(field, unit);
+                    true
+                }
+
+                // Text which doesn't parse is left as typed, for the field to flag
+                #component_msg_ident::EnterInUnit(field, text, msg_for) => {
+                    let factor = self.unit_factor(field);
+                    let text = match text.trim().parse::<f64>() {
+                        Ok(value) => (value * factor).to_string(),
+                        Err(_) => text,
+                    };
+                    self.apply_msg(msg_for(text))
+                }
+            },
+            quote! {
+                // The index of the unit picked for a field, which starts out as the first
+                fn unit(&self, field: #field_enum_ident) -> usize {
+                    self.units.get(&field).copied().unwrap_or_default()
+                }
+
+                // How many of the first unit the unit picked for a field is
+                fn unit_factor(&self, field: #field_enum_ident) -> f64 {
+                    match field {
+                        #(#factor_arms,)*
+                    }
+                }
+
+                // A value stored in the first unit, in the unit picked for its field. It's rounded
+                // so that converting it back and forth doesn't show float noise.
+                fn in_unit(&self, field: #field_enum_ident, value: f64) -> f64 {
+                    ((value / self.unit_factor(field)) * 1e9).round() / 1e9
+                }
+            },
+        )
+    };
+
+    // What is searched for above each chrono_tz::Tz field's select is kept by the form, narrowing
+    // down the zones it offers
+    let (time_zone_fields, time_zone_inits, time_zone_msg_variant, time_zone_apply_arm) =
+        if fields.iter().any(field_is_time_zone) {
+            (
+                quote! {
+                    time_zone_searches: std::collections::HashMap<#field_enum_ident, String>,
+                },
+                quote! { time_zone_searches: std::collections::HashMap::new(), },
+                quote! { SearchTimeZones(#field_enum_ident, String), },
+                quote! {
+                    #component_msg_ident::SearchTimeZones(field, search) => {
+                        self.time_zone_searches.insert(field, search);
+                        true
+                    }
+                },
+            )
+        } else {
+            (quote! {}, quote! {}, quote! {}, quote! {})
+        };
+
+    // Any form apart from a shared_state one can be nested in another with #[yform(nested)]. A
+    // nested form renders its fields without the surrounding form or buttons, and passes each
+    // change up along with whether its values could be submitted, which blocks the containing
+    // form's submit while they couldn't.
+    let (nested_notify, nested_sync, nested_first_render, nested_impl) = if struct_attrs
+        .shared_state
+    {
+        (quote! {}, quote! {}, quote! {}, quote! {})
+    } else {
+        let summary_items = fields.iter().map(|field| {
+            let field_ident = field.ident.clone().unwrap();
+            let label = get_label(field);
+            let value = review::display_value(field, quote! { self.#field_ident });
+            quote! {
+                <dt class="formula-y-summary-label">{#label}</dt>
+                <dd class="formula-y-summary-value">{#value}</dd>
+            }
+        });
+        let summary_class = format!(
+            "{}-summary formula-y-summary",
+            format!("{}", input_struct_ident).to_case(Case::Kebab)
+        );
+        let sample = testing::sample_value(input_struct_ident, &fields, &unsupported_inits);
+        (
+            quote! {
+                if let Some(nested) = &ctx.props().nested {
+                    if changed {
+                        nested.onchange.emit(self.current_value());
+                    }
+                    nested.onvalidity.emit(self.can_submit(nested.enforce_required_fields));
+                }
+            },
+            // The containing form's required field warnings are shown in the nested one too, and
+            // its copy of the value is always up to date, so the nested form follows it
+            quote! {
+                if let Some(nested) = &ctx.props().nested {
+                    self.display_required_warnings = nested.display_required_warnings;
+                    if let Some(init) = &ctx.props().init {
+                        self.set_value(init.clone());
+                    }
+                }
+            },
+            quote! {
+                if first_render {
+                    if let Some(nested) = &ctx.props().nested {
+                        nested.onvalidity.emit(self.can_submit(nested.enforce_required_fields));
+                    }
+                }
+            },
+            quote! {
+                impl YFormNested for #input_struct_ident {
+                    fn nested_view(init: Self, id_prefix: String, link: NestedLink<Self>) -> Html {
+                        html! { <#component_ident {init} {id_prefix} nested={link} /> }
+                    }
+
+                    fn summary(&self) -> Html {
+                        html! {
+                            <dl class={#summary_class}>
+                                #(#summary_items)*
+                            </dl>
+                        }
+                    }
+
+                    fn sample() -> Self {
+                        #sample
+                    }
+
+                    fn field_values(&self) -> Vec<(&'static str, FieldValue)> {
+                        let mut values = Vec::new();
+                        self.visit_fields(&mut |_: #field_enum_ident, meta: &FieldMeta, value: FieldValue| {
+                            values.push((meta.name, value));
+                        });
+                        values
+                    }
+                }
+            },
+        )
+    };
+    // Only nested forms have anything to do on the first render in particular
+    let first_render_param = if struct_attrs.shared_state {
+        quote! { _first_render }
+    } else {
+        quote! { first_render }
+    };
+
+    let preview = if struct_attrs.preview {
+        preview::preview(
+            input_struct_ident,
+            &component_ident,
+            &fields,
+            &unsupported_inits,
+            &struct_attrs,
+        )
+    } else {
+        quote! {}
+    };
+    let hook = if struct_attrs.hook {
+        hook::hook(input_struct_ident, &component_ident, &struct_attrs)
+    } else {
+        quote! {}
+    };
+
+    // By default the component owns its own copy of the struct behind an Rc, cloning it out for
+    // onsubmit, or handing out the Rc itself to onsubmit_rc. In shared_state mode it is kept behind
+    // an Rc<RefCell<..>> instead, which is handed to onsubmit as is, so structs which can't be
+    // cloned can still derive a form.
+    let state_type = get_state_type(input_struct_ident, &struct_attrs);
+    // The struct's own validate fn runs once everything else allows a submit. The errors it gives
+    // are kept, and rerun on every change until they are fixed.
+    let (
+        validate_struct_method,
+        validate_struct_check,
+        revalidate_struct,
+        validation_error_field,
+        validation_error_init,
+    ) = match &struct_attrs.validate {
+        Some(validate) => (
+            quote! {
+                // Run the struct's validate fn, keeping the errors it gives
+                fn run_validate(&mut self) {
+                    let errors = #validate(&self.inner()).err().unwrap_or_default();
+                    self.validation_errors = errors;
+                }
+            },
+            quote! { && { self.run_validate(); self.validation_errors.is_empty() } },
+            quote! {
+                if !self.validation_errors.is_empty() {
+                    self.run_validate();
+                }
+            },
+            quote! {
+                // The errors the struct's validate fn gave on the last submit attempt, by field
+                validation_errors: Vec<(#field_enum_ident, String)>,
+            },
+            quote! { validation_errors: Vec::new(), },
+        ),
+        None => Default::default(),
+    };
+    let emit_onfieldsave = if save_individually {
+        quote! {
+            if let Some(onfieldsave) = &ctx.props().onfieldsave {
+                onfieldsave.emit((field, self.current_value()));
+            }
+        }
+    } else {
+        quote! {}
+    };
+    let (storage_type, inner_storage, current_value) = if struct_attrs.shared_state {
+        (
+            quote! { #state_type },
+            quote! { inner },
+            quote! { self.inner.clone() },
+        )
+    } else {
+        (
+            quote! { std::rc::Rc<#input_struct_ident> },
+            quote! { std::rc::Rc::new(inner) },
+            quote! { (*self.inner).clone() },
+        )
+    };
+
+    // A reset goes back to the value the form was created with. The shared value of a shared_state
+    // form is edited in place, so there it is cleared back to new() instead.
+    let (initial_field, initial_init, reset_state) = if struct_attrs.shared_state {
+        (
+            quote! {},
+            quote! {},
+            quote! {
+                {
+                    *self.inner.borrow_mut() = #input_struct_ident::new();
+                    self.inner.clone()
+                }
+            },
+        )
+    } else {
+        (
+            quote! {
+                // The value the form was created with, which a reset goes back to
+                initial: std::rc::Rc<#input_struct_ident>,
+            },
+            quote! { initial: std::rc::Rc::new(inner.clone()), },
+            quote! { (*self.initial).clone() },
+        )
+    };
+
+    // Fields with #[yform(submit_with = "...")] are converted on the way out, so the value
+    // submitted is a converted copy rather than the one the inputs show. Drafts are left as
+    // entered so they can be passed back in through init. Fields with
+    // #[yform(encrypt_with = "...")] are encrypted last, after any submit_with. Floats with a
+    // precision are rounded first, catching values from init which changes never rounded, except
+    // in shared_state mode which submits the struct itself.
+    let submit_withs = fields
+        .iter()
+        .filter_map(|field| {
+            let attrs = FieldAttrs::from_field(field);
+            let field_ident = field.ident.clone().unwrap();
+            let round = (attrs.precision.is_some() && !struct_attrs.shared_state).then(|| {
+                let rounded = get_rounded(field, quote! { value.#field_ident });
+                quote! { value.#field_ident = #rounded; }
+            });
+            let submit_with = attrs.submit_with.map(|submit_with| {
+                quote! { value.#field_ident = #submit_with(value.#field_ident); }
+            });
+            let encrypt_with = attrs.encrypt_with.map(|encrypt_with| {
+                if field_is_option_string(field) {
+                    quote! { value.#field_ident = value.#field_ident.as_deref().map(#encrypt_with); }
+                } else {
+                    quote! { value.#field_ident = #encrypt_with(&value.#field_ident); }
+                }
+            });
+            (round.is_some() || submit_with.is_some() || encrypt_with.is_some())
+                .then(|| quote! { #round #submit_with #encrypt_with })
+        })
+        .collect::<Vec<_>>();
+    let submit_value = if submit_withs.is_empty() {
+        current_value.clone()
+    } else {
+        quote! {
+            let mut value = #current_value;
+            #(#submit_withs)*
+            value
+        }
+    };
+    let emit_onsubmit_rc = if struct_attrs.shared_state {
+        quote! {}
+    } else if submit_withs.is_empty() {
+        quote! {
+            if let Some(onsubmit_rc) = &ctx.props().onsubmit_rc {
+                onsubmit_rc.emit(self.inner.clone());
+            }
+        }
+    } else {
+        quote! {
+            if let Some(onsubmit_rc) = &ctx.props().onsubmit_rc {
+                onsubmit_rc.emit(std::rc::Rc::new(self.submit_value()));
+            }
+        }
+    };
+
+    // Forms with #[yform(actions(...))] get one button per action in place of the submit button,
+    // each of which goes through the same checks as submitting before calling onaction with the
+    // chosen action.
+    let action_ident = append_to_ident(&component_ident, "Action"); // Data -> DataFormAction
+    let (action_enum, action_msg_variant, action_update_arm, action_apply_arm, submit_buttons) =
+        if struct_attrs.actions.is_empty() {
+            (
+                quote! {},
+                quote! {},
+                quote! {},
+                quote! {},
+                quote! {
+                    <button class={classes!("formula-y-submit", &theme.submit, ctx.props().submit_class.clone())} data-formulay-role="submit" disabled={submit_disabled}>
+                        { ctx.props().submit_label.clone().unwrap_or_else(|| "Submit".to_string()) }
+                    </button>
+                },
+            )
+        } else {
+            let variants = struct_attrs.actions.iter().map(|(variant, _)| variant);
+            let buttons = struct_attrs.actions.iter().map(|(variant, label)| {
+                let class = format!("{}-action", label.to_case(Case::Kebab));
+                quote! {
+                    <button type="button" class={classes!(#class, "formula-y-action", &theme.submit)} data-formulay-role="action" disabled={submit_disabled} onclick={link.callback(|_| #component_msg_ident::OnAction(#action_ident::#variant))}>{#label}</button>
+                }
+            });
+            (
+                quote! {
+                    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+                    pub enum #action_ident {
+                        #(#variants,)*
+                    }
+                },
+                quote! { OnAction(#action_ident), },
+                quote! {
+                    #component_msg_ident::OnAction(action) => {
+                        if self.cooldown_remaining_ms > 0 {
+                            return false;
+                        }
+                        let required_policy = ctx.props().enforce_required_fields.unwrap_or_default();
+
+                        if self.try_submit(required_policy) {
+                            if let Some(onaction) = &ctx.props().onaction {
+                                onaction.emit((action, self.submit_value()));
+                            }
+                            self.announce_submitted(ctx);
+                        } else {
+                            ctx.link().send_message(#component_msg_ident::DisplayRequiredWarnings);
+                        }
+                        true
+                    },
+                },
+                quote! { #component_msg_ident::OnAction(_) => false, },
+                quote! { #(#buttons)* },
+            )
+        };
+
+    let new_state = wrap_state(quote! { #input_struct_ident::new() }, &struct_attrs);
+    let inner_ref_type = get_inner_ref_type(input_struct_ident, &struct_attrs);
+    let inner_accessors = if struct_attrs.shared_state {
+        quote! {
+            fn inner(&self) -> #inner_ref_type {
+                self.inner.borrow()
+            }
+
+            fn inner_mut(&mut self) -> std::cell::RefMut<'_, #input_struct_ident> {
+                self.inner.borrow_mut()
+            }
+        }
+    } else {
+        quote! {
+            fn inner(&self) -> #inner_ref_type {
+                &self.inner
+            }
+
+            fn inner_mut(&mut self) -> &mut #input_struct_ident {
+                std::rc::Rc::make_mut(&mut self.inner)
+            }
+        }
+    };
+
+    // Every prop apart from init and value is cheap to compare, so the props only need the input
+    // struct to be PartialEq because of them. With no_partial_eq the comparison is written out by
+    // hand instead, ignoring init (which is only read when the form is created, or as fields finish
+    // loading, which changes the loading props anyway) and counting a value as always changed, or
+    // comparing both by pointer in shared_state mode.
+    let mut compared_props = vec![
+        (
+            quote! { onsubmit },
+            quote! { Option<Callback<#state_type>> },
+        ),
+        (
+            quote! { onsavedraft },
+            quote! { Option<Callback<#state_type>> },
+        ),
+        (
+            quote! { enforce_required_fields },
+            quote! { Option<RequiredPolicy> },
+        ),
+        (quote! { id_prefix }, quote! { Option<String> }),
+        (quote! { escape_reverts }, quote! { Option<bool> }),
+        (quote! { disabled }, quote! { Option<bool> }),
+        (quote! { onerror }, quote! { Option<Callback<String>> }),
+        (quote! { announce_submit }, quote! { Option<bool> }),
+        (quote! { state_indicators }, quote! { Option<bool> }),
+        (quote! { aria_label }, quote! { Option<String> }),
+        (quote! { failed_submits }, quote! { Option<usize> }),
+        (quote! { submit_cooldown_ms }, quote! { Option<u32> }),
+        (quote! { submit_cooldown_max_ms }, quote! { Option<u32> }),
+        (quote! { oncooldown }, quote! { Option<Callback<u32>> }),
+        (quote! { deadline }, quote! { Option<formula_y::Deadline> }),
+        (
+            quote! { onexpire },
+            quote! { Option<Callback<#state_type>> },
+        ),
+        (quote! { described_by }, quote! { Option<String> }),
+        (quote! { description }, quote! { Option<String> }),
+        (quote! { submitted_announcement }, quote! { Option<String> }),
+        (
+            quote! { errors_announcement },
+            quote! { Option<ErrorsAnnouncement> },
+        ),
+        (quote! { loading }, quote! { Option<bool> }),
+        (quote! { init_merge }, quote! { Option<InitMerge> }),
+        (
+            quote! { loading_fields },
+            quote! { Option<Vec<#field_enum_ident>> },
+        ),
+        (quote! { reset_button }, quote! { Option<bool> }),
+        (quote! { submit_label }, quote! { Option<String> }),
+        (quote! { submit_class }, quote! { Option<String> }),
+        (quote! { submit_disabled }, quote! { Option<bool> }),
+        (quote! { clear_on_submit }, quote! { Option<bool> }),
+        (quote! { reset_count }, quote! { Option<usize> }),
+        (quote! { submitted_reset_count }, quote! { Option<usize> }),
+        (quote! { keyboard_navigation }, quote! { Option<bool> }),
+        (quote! { auto_submit_after_ms }, quote! { Option<u32> }),
+        (
+            quote! { oncompositionstart },
+            quote! { Option<Callback<web_sys::CompositionEvent>> },
+        ),
+        (
+            quote! { oncompositionend },
+            quote! { Option<Callback<web_sys::CompositionEvent>> },
+        ),
+        (
+            quote! { onautofill },
+            quote! { Option<Callback<#field_enum_ident>> },
+        ),
+        (
+            quote! { ontelemetry },
+            quote! { Option<Callback<TelemetryEvent<#field_enum_ident>>> },
+        ),
+        (quote! { lock_on_submit }, quote! { Option<bool> }),
+        (quote! { theme }, quote! { Option<formula_y::Theme> }),
+        (quote! { density }, quote! { Option<formula_y::Density> }),
+        (
+            quote! { labels },
+            quote! { Option<std::collections::HashMap<#field_enum_ident, String>> },
+        ),
+        (quote! { onunlock }, quote! { Option<Callback<()>> }),
+        (
+            quote! { onsnapshot },
+            quote! { Option<Callback<#snapshot_ident>> },
+        ),
+        (
+            quote! { onabandon },
+            quote! { Option<Callback<Vec<#field_enum_ident>>> },
+        ),
+        (quote! { label_placeholders }, quote! { Option<bool> }),
+        (
+            quote! { onchange },
+            quote! { Option<Callback<#state_type>> },
+        ),
+        (quote! { inline_edit }, quote! { Option<bool> }),
+        (quote! { audit_trail }, quote! { Option<bool> }),
+        (
+            quote! { onedit },
+            quote! { Option<Callback<FieldEdit<#field_enum_ident>>> },
+        ),
+        (
+            quote! { onfieldchange },
+            quote! { Option<Callback<(#field_enum_ident, #state_type)>> },
+        ),
+    ];
+    for field in fields.iter() {
+        let attrs = FieldAttrs::from_field(field);
+        if attrs.options_prop {
+            let options_ident = get_options_prop_ident(field);
+            let options_type = if attrs.grouped {
+                quote! { Option<Vec<(String, String, String)>> }
+            } else {
+                quote! { Option<Vec<(String, String)>> }
+            };
+            compared_props.push((quote! { #options_ident }, options_type));
+        }
+    }
+    compared_props.push((
+        quote! { field_policy },
+        quote! { Option<FieldPolicy<#field_enum_ident>> },
+    ));
+    if copy_values {
+        compared_props.push((quote! { copy_button }, quote! { Option<bool> }));
+    }
+    if search {
+        compared_props.push((quote! { search_icon }, quote! { Option<Html> }));
+    }
+    if !steps.is_empty() {
+        compared_props.push((
+            quote! { onstepvalidity },
+            quote! { Option<Callback<(usize, bool)>> },
+        ));
+    }
+    if struct_attrs.save_individually {
+        compared_props.push((
+            quote! { onfieldsave },
+            quote! { Option<Callback<(#field_enum_ident, #state_type)>> },
+        ));
+    }
+    if !struct_attrs.actions.is_empty() {
+        compared_props.push((
+            quote! { onaction },
+            quote! { Option<Callback<(#action_ident, #state_type)>> },
+        ));
+    }
+    if !struct_attrs.shared_state {
+        compared_props.push((
+            quote! { onsubmit_rc },
+            quote! { Option<Callback<std::rc::Rc<#input_struct_ident>>> },
+        ));
+        compared_props.push((
+            quote! { nested },
+            quote! { Option<NestedLink<#input_struct_ident>> },
+        ));
+    }
+    let prop_fields = compared_props
+        .iter()
+        .map(|(prop_ident, prop_type)| quote! { pub #prop_ident: #prop_type });
+    let props = if struct_attrs.no_partial_eq {
+        let prop_comparisons = compared_props
+            .iter()
+            .map(|(prop_ident, _)| quote! { self.#prop_ident == other.#prop_ident });
+        let init_comparison = if struct_attrs.shared_state {
+            quote! {
+                match (&self.init, &other.init) {
+                    (Some(init), Some(other_init)) => std::rc::Rc::ptr_eq(init, other_init),
+                    (None, None) => true,
+                    _ => false
+                } && match (&self.value, &other.value) {
+                    (Some(value), Some(other_value)) => std::rc::Rc::ptr_eq(value, other_value),
+                    (None, None) => true,
+                    _ => false
+                }
+            }
+        } else {
+            quote! { self.value.is_none() && other.value.is_none() }
+        };
+
+        quote! {
+            #[derive(Properties)]
+            pub struct #component_prop_ident {
+                #(#prop_fields,)*
+                pub init: Option<#state_type>,
+                pub snapshot: Option<#snapshot_ident>,
+                pub value: Option<#state_type>
+            }
+
+            impl PartialEq for #component_prop_ident {
+                fn eq(&self, other: &Self) -> bool {
+                    #(#prop_comparisons &&)* #init_comparison
+                }
+            }
+        }
+    } else {
+        quote! {
+            #[derive(PartialEq, Properties)]
+            pub struct #component_prop_ident {
+                #(#prop_fields,)*
+                pub init: Option<#state_type>,
+                pub snapshot: Option<#snapshot_ident>,
+                pub value: Option<#state_type>
+            }
+        }
+    };
+
+    // A snapshot of what the form holds beyond its props, which a parent can keep while the form
+    // isn't mounted (i.e. behind another tab) and hand back to pick up where the user left off
+    let snapshot_derives = if struct_attrs.no_partial_eq {
+        quote! { #[derive(Clone)] }
+    } else {
+        quote! { #[derive(Clone, PartialEq)] }
+    };
+    let snapshot_doc = format!(
+        "What a `{}` holds beyond its props, from its `snapshot()` method or `onsnapshot` callback, and restored with `restore()` or the `snapshot` prop",
+        component_ident
+    );
+    let (snapshot_step, restore_step) = if steps.is_empty() {
+        (quote! { 0 }, quote! {})
+    } else {
+        (
+            quote! { self.current_step },
+            quote! { self.current_step = state.step.min(#last_step); },
+        )
+    };
+    let (snapshot_validation_errors, restore_validation_errors) = if struct_attrs.validate.is_some()
+    {
+        (
+            quote! { self.validation_errors.clone() },
+            quote! { self.validation_errors = state.validation_errors; },
+        )
+    } else {
+        (quote! { Vec::new() }, quote! {})
+    };
+    let snapshot_struct = quote! {
+        #[doc = #snapshot_doc]
+        #snapshot_derives
+        pub struct #snapshot_ident {
+            /// The values of the fields
+            pub value: #state_type,
+            /// Text typed into number and date inputs which didn't parse, by field
+            pub unparsed: std::collections::HashMap<#field_enum_ident, String>,
+            /// The errors the struct's validate fn gave on the last submit attempt, by field
+            pub validation_errors: Vec<(#field_enum_ident, String)>,
+            /// Whether required fields left empty are showing their errors
+            pub required_warnings: bool,
+            /// Whether the form has been changed since it was created or reset
+            pub dirty: bool,
+            /// The fields the user has changed
+            pub edited: std::collections::HashSet<#field_enum_ident>,
+            /// The step of a multi-step form being shown, counting from 0
+            pub step: usize,
+        }
+    };
+
+    let own_form_class = format!("{}-form", input_struct_ident).to_case(Case::Kebab);
+    let mut form_class = format!("{} formula-y-form", own_form_class);
+    if search {
+        form_class.push_str(" formula-y-search");
+    }
+    let id_base = format!("{}", component_ident).to_case(Case::Kebab);
+
+    // Labels shown as placeholders stay in the page for screen readers, only hidden from view.
+    // Below the label_placeholders_below width they are hidden by a media query, and above it
+    // the placeholders are hidden instead, so the label only ever shows up once.
+    let form_selector = format!(
+        ".{}-form",
+        format!("{}", input_struct_ident).to_case(Case::Kebab)
+    );
+    let hide_labels = format!(
+        "{} .formula-y-placeholder-label {{ position: absolute; width: 1px; height: 1px; padding: 0; margin: -1px; overflow: hidden; clip: rect(0, 0, 0, 0); white-space: nowrap; border: 0; }}",
+        form_selector
+    );
+    let label_placeholder_style = match &struct_attrs.label_placeholders_below {
+        Some(width) => {
+            let responsive = format!(
+                "@media (max-width: {width}) {{ {hide_labels} }} @media not all and (max-width: {width}) {{ {form} .formula-y-placeholder-label + input::placeholder, {form} .formula-y-placeholder-label + textarea::placeholder {{ color: transparent; }} }}",
+                width = width,
+                hide_labels = hide_labels,
+                form = form_selector
+            );
+            quote! {
+                if ctx.props().label_placeholders.unwrap_or(#search) {
+                    <style>{#hide_labels}</style>
+                } else {
+                    <style>{#responsive}</style>
+                }
+            }
+        }
+        None => quote! {
+            if ctx.props().label_placeholders.unwrap_or(#search) {
+                <style>{#hide_labels}</style>
+            }
+        },
+    };
+
+    // Browsers don't send an event when they autofill an input, but Chrome and Safari start the
+    // :-webkit-autofill styles on it, so a no-op animation there is listened for instead
+    let autofill_style = format!(
+        "@keyframes formula-y-autofill {{ from {{}} to {{}} }} {form} input:-webkit-autofill, {form} select:-webkit-autofill, {form} textarea:-webkit-autofill {{ animation-name: formula-y-autofill; }}",
+        form = form_selector
+    );
+
+    // Search forms lay their fields and button out in a single row
+    let search_style = if search {
+        let style = format!(
+            "{form}.formula-y-search {{ display: flex; align-items: center; gap: 0.5em; }} {form}.formula-y-search > .formula-y-form-item {{ flex: 1 1 auto; }}",
+            form = form_selector
+        );
+        quote! { <style>{#style}</style> }
+    } else {
+        quote! {}
+    };
+
+    let field_variants = fields.iter().map(get_field_variant_ident);
+    let all_field_variants = fields
+        .iter()
+        .map(get_field_variant_ident)
+        .collect::<Vec<_>>();
+
+    // Fields which finish loading take their value from init, unless the user has changed them in
+    // the meantime, and so do fields the user hasn't changed when init itself changes, as decided
+    // by the init_merge prop. A controlled form shows its value prop instead, and in shared_state
+    // mode the parent changes the struct it shares directly. Spotting a new init means comparing
+    // it with the last one, which no_partial_eq forms can't.
+    let merges_init = !struct_attrs.shared_state && !struct_attrs.no_partial_eq;
+    let (take_init_method, load_fields) = if struct_attrs.shared_state {
+        (quote! {}, quote! {})
+    } else {
+        let field_idents = fields
+            .iter()
+            .map(|field| field.ident.clone().unwrap())
+            .collect::<Vec<_>>();
+        // Fields left out by skip_unsupported are never taken from init
+        let rest = (!unsupported.is_empty()).then(|| quote! { .. });
+        let takes = fields.iter().map(|field| {
+            let field_ident = field.ident.clone().unwrap();
+            let field_variant = get_field_variant_ident(field);
+            let field_key = get_field_key(field, &field_enum_ident);
+            quote! {
+                if fields.contains(&#field_enum_ident::#field_variant) {
+                    self.inner_mut().#field_ident = #field_ident;
+                    self.edited.remove(&#field_enum_ident::#field_variant);
+                    self.invalid_numbers.remove(&#field_key);
+                }
+            }
+        });
+        let invalid_arms = fields.iter().map(|field| {
+            let field_variant = get_field_variant_ident(field);
+            quote! { #field_enum_ident::#field_variant => self.invalid_numbers.contains_key(&#field_enum_ident::#field_variant) }
+        });
+        let compute_all = computed_fields.iter().map(|(field_ident, _)| {
+            let compute_ident = get_compute_method_ident(field_ident);
+            quote! { self.#compute_ident(); }
+        });
+        let mark_init_seen = if merges_init {
+            quote! { self.init_seen = ctx.props().init.clone(); }
+        } else {
+            quote! {}
+        };
+        (
+            quote! {
+                // Take the given fields' values from init, in place of whatever the form holds
+                fn take_init(&mut self, init: &#input_struct_ident, fields: &std::collections::HashSet<#field_enum_ident>) {
+                    let #input_struct_ident { #(#field_idents,)* #rest } = init.clone();
+                    #(#takes)*
+                    #(#sync_item_keys)*
+                    #(#compute_all)*
+                }
+
+                // Whether the user has changed a field, including typing a number which doesn't parse
+                fn is_edited(&self, field: #field_enum_ident) -> bool {
+                    self.edited.contains(&field)
+                        || match field {
+                            #(#invalid_arms,)*
+                        }
+                }
+            },
+            // The init which arrives as fields finish loading is expected rather than news
+            quote! {
+                if let (Some(init), None) = (&ctx.props().init, &ctx.props().value) {
+                    let unedited = loaded.into_iter().filter(|field| !self.is_edited(*field)).collect();
+                    self.take_init(init, &unedited);
+                    #mark_init_seen
+                }
+            },
+        )
+    };
+    let (
+        init_merge_field,
+        init_merge_init,
+        merge_init,
+        init_merge_msg_variant,
+        init_merge_apply_arm,
+        init_merge_notice,
+    ) = if merges_init {
+        (
+            quote! {
+                // The init prop as of the last props, to spot a new one arriving
+                init_seen: Option<#input_struct_ident>,
+                // A new init waiting on the user to choose between it and their own edits
+                pending_init: Option<#input_struct_ident>,
+            },
+            quote! {
+                init_seen: None,
+                pending_init: None,
+            },
+            quote! {
+                if ctx.props().init != self.init_seen {
+                    self.init_seen = ctx.props().init.clone();
+                    if let (Some(init), None) = (&ctx.props().init, &ctx.props().value) {
+                        let fields = [#(#field_enum_ident::#all_field_variants),*]
+                            .into_iter()
+                            .collect::<std::collections::HashSet<_>>();
+                        let unedited = fields
+                            .iter()
+                            .copied()
+                            .filter(|field| !self.is_edited(*field))
+                            .collect::<std::collections::HashSet<_>>();
+                        match ctx.props().init_merge.unwrap_or_default() {
+                            InitMerge::Ignore => {}
+                            InitMerge::Overwrite => self.take_init(init, &fields),
+                            InitMerge::KeepEdits => self.take_init(init, &unedited),
+                            InitMerge::Prompt => {
+                                self.take_init(init, &unedited);
+                                if unedited.len() < fields.len() {
+                                    self.pending_init = Some(init.clone());
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            quote! {
+                AcceptInit,
+                DismissInit,
+            },
+            quote! {
+                #component_msg_ident::AcceptInit => {
+                    if let Some(init) = self.pending_init.take() {
+                        let fields = [#(#field_enum_ident::#all_field_variants),*].into_iter().collect();
+                        self.take_init(&init, &fields);
+                        self.value_changed = true;
+                    }
+                    true
+                }
+                #component_msg_ident::DismissInit => {
+                    self.pending_init = None;
+                    true
+                }
+            },
+            quote! {
+                if self.pending_init.is_some() {
+                    <div class="formula-y-init-changed" role="alert">
+                        <span>{"This has been changed elsewhere since you started editing."}</span>
+                        <button type="button" class="formula-y-init-accept" onclick={link.callback(|_| #component_msg_ident::AcceptInit)}>{"Load the changes"}</button>
+                        <button type="button" class="formula-y-init-dismiss" onclick={link.callback(|_| #component_msg_ident::DismissInit)}>{"Keep my edits"}</button>
+                    </div>
+                }
+            },
+        )
+    } else {
+        Default::default()
+    };
+    // The init prop seen last is about the props rather than the form's values, so it survives a
+    // reset, as the loading fields do
+    let (keep_init_seen, restore_init_seen, init_seen_on_create) = if merges_init {
+        (
+            quote! { let init_seen = self.init_seen.take(); },
+            quote! { self.init_seen = init_seen; },
+            quote! { form.init_seen = ctx.props().init.clone(); },
+        )
+    } else {
+        Default::default()
+    };
+
+    // Fields waiting on their value have their text hidden, with a shimmer in its place
+    let loading_style = format!(
+        "{form} .formula-y-loading input, {form} .formula-y-loading select, {form} .formula-y-loading textarea {{ color: transparent; background: linear-gradient(90deg, #eee 25%, #f6f6f6 50%, #eee 75%) 0 0 / 200% 100%; animation: formula-y-loading 1.5s linear infinite; }} {form} .formula-y-loading ::placeholder {{ color: transparent; }} @keyframes formula-y-loading {{ to {{ background-position: -200% 0; }} }}",
+        form = form_selector
+    );
+
+    // Login forms track whether their password is revealed as text
+    let (login_field, login_init, login_msg_variant, login_apply_arm) = if struct_attrs.is_login() {
+        (
+            quote! {
+                // Whether the password input shows its value as text
+                password_revealed: bool,
+            },
+            quote! { password_revealed: false, },
+            quote! { TogglePasswordReveal, },
+            quote! {
+                #component_msg_ident::TogglePasswordReveal => {
+                    self.password_revealed = !self.password_revealed;
+                    true
+                }
+            },
+        )
+    } else {
+        Default::default()
+    };
+
+    // Formatted numbers track which of them is focused, since that one shows its plain number
+    let (format_field, format_init, format_msg_variant, format_apply_arm, format_method) = if fields
+        .iter()
+        .any(|field| FieldAttrs::from_field(field).format.is_some())
+    {
+        (
+            quote! {
+                // The formatted number field being edited, which shows its plain number
+                focused_formatted: Option<#field_enum_ident>,
+            },
+            quote! { focused_formatted: None, },
+            quote! { FocusFormatted(#field_enum_ident, bool), },
+            quote! {
+                #component_msg_ident::FocusFormatted(field, focused) => {
+                    if focused {
+                        self.focused_formatted = Some(field);
+                    } else if self.focused_formatted == Some(field) {
+                        self.focused_formatted = None;
+                    }
+                    true
+                }
+            },
+            quote! {
+                // Group the digits of a number's whole part in threes, i.e. 1234567.5 -> 1,234,567.5
+                fn format_thousands(number: String) -> String {
+                    let (sign, unsigned) = match number.strip_prefix('-') {
+                        Some(unsigned) => ("-", unsigned),
+                        None => ("", number.as_str()),
+                    };
+                    let (whole, fraction) = unsigned.split_at(unsigned.find('.').unwrap_or(unsigned.len()));
+                    let mut grouped = String::new();
+                    for (index, digit) in whole.chars().enumerate() {
+                        if index > 0 && (whole.len() - index) % 3 == 0 {
+                            grouped.push(',');
+                        }
+                        grouped.push(digit);
+                    }
+                    format!("{}{}{}", sign, grouped, fraction)
+                }
+            },
+        )
+    } else {
+        Default::default()
+    };
+
+    // Vec fields have items added and removed through msgs naming the field
+    let (item_key_fields, item_key_inits, item_msg_variants, item_apply_arms, item_methods) =
+        if vec_fields.is_empty() {
+            (quote! {}, quote! {}, quote! {}, quote! {}, quote! {})
+        } else {
+            let field_computes = |field: &syn::Field| {
+                let field_ident = field.ident.clone().unwrap();
+                computed_fields
+                    .iter()
+                    .filter(|(_, dependencies)| dependencies.contains(&field_ident))
+                    .map(|(computed_ident, _)| get_compute_method_ident(computed_ident))
+                    .collect::<Vec<_>>()
+            };
+            let len_arms = fields.iter().map(|field| {
+                let field_ident = field.ident.clone().unwrap();
+                let field_variant = get_field_variant_ident(field);
+                if field_is_vec(field) {
+                    quote! { #field_enum_ident::#field_variant => self.inner().#field_ident.len() }
+                } else {
+                    quote! { #field_enum_ident::#field_variant => 0 }
+                }
+            });
+            // Vec fields with max_items take no more items once they hold that many, and with
+            // min_items give up no more once they hold that few
+            let add_arms = fields.iter().map(|field| {
+                let field_ident = field.ident.clone().unwrap();
+                let field_variant = get_field_variant_ident(field);
+                let computes = field_computes(field);
+                let at_max = FieldAttrs::from_field(field).max_items.map(|max_items| {
+                    quote! {
+                        if self.inner().#field_ident.len() >= #max_items {
+                            return false;
+                        }
+                    }
+                });
+                if field_is_string_vec(field) {
+                    quote! { #field_enum_ident::#field_variant => {
+                        #at_max
+                        self.inner_mut().#field_ident.push(String::new());
+                        #(self.#computes();)*
+                    } }
+                } else if field_is_nested_vec(field) {
+                    let item_type = item_type(field);
+                    quote! { #field_enum_ident::#field_variant => {
+                        #at_max
+                        self.inner_mut().#field_ident.push(<#item_type>::new());
+                        #(self.#computes();)*
+                    } }
+                } else {
+                    quote! { #field_enum_ident::#field_variant => return false }
+                }
+            });
+            let remove_arms = fields.iter().map(|field| {
+                let field_ident = field.ident.clone().unwrap();
+                let field_variant = get_field_variant_ident(field);
+                let computes = field_computes(field);
+                let at_min = FieldAttrs::from_field(field).min_items.map(|min_items| {
+                    quote! { || self.inner().#field_ident.len() <= #min_items }
+                });
+                if field_is_vec(field) {
+                    quote! { #field_enum_ident::#field_variant => {
+                        if index >= self.inner().#field_ident.len() #at_min {
+                            return false;
+                        }
+                        self.inner_mut().#field_ident.remove(index);
+                        #(self.#computes();)*
+                    } }
+                } else {
+                    quote! { #field_enum_ident::#field_variant => return false }
+                }
+            });
+            (
+                quote! {
+                    // The keys of the items of each Vec field, in order
+                    item_keys: std::collections::HashMap<#field_enum_ident, Vec<usize>>,
+                    next_item_key: usize,
+                },
+                quote! {
+                    item_keys: std::collections::HashMap::new(),
+                    next_item_key: 0,
+                },
+                quote! {
+                    AddItem(#field_enum_ident),
+                    RemoveItem(#field_enum_ident, usize),
+                },
+                quote! {
+                    #component_msg_ident::AddItem(field) => self.add_item(field),
+                    #component_msg_ident::RemoveItem(field, index) => self.remove_item(field, index),
+                },
+                quote! {
+                    // Give every item of a Vec field a new key, i.e. once the whole list is replaced
+                    fn reset_item_keys(&mut self, field: #field_enum_ident) {
+                        let len = match field {
+                            #(#len_arms,)*
+                        };
+                        let keys = (self.next_item_key..self.next_item_key + len).collect();
+                        self.next_item_key += len;
+                        self.invalid_nested.retain(|(invalid, _)| *invalid != field);
+                        self.item_keys.insert(field, keys);
+                    }
+
+                    // The key of the item at an index of a Vec field
+                    fn item_key(&self, field: #field_enum_ident, index: usize) -> usize {
+                        self.item_keys
+                            .get(&field)
+                            .and_then(|keys| keys.get(index))
+                            .copied()
+                            .unwrap_or(index)
+                    }
+
+                    // Append an empty item to a Vec field, returning whether to rerender
+                    fn add_item(&mut self, field: #field_enum_ident) -> bool {
+                        match field {
+                            #(#add_arms,)*
+                        }
+                        let key = self.next_item_key;
+                        self.next_item_key += 1;
+                        self.item_keys.entry(field).or_default().push(key);
+                        self.after_field_update();
+                        true
+                    }
+
+                    // Remove the item at an index of a Vec field, returning whether to rerender
+                    fn remove_item(&mut self, field: #field_enum_ident, index: usize) -> bool {
+                        match field {
+                            #(#remove_arms,)*
+                        }
+                        if let Some(keys) = self.item_keys.get_mut(&field) {
+                            if index < keys.len() {
+                                let key = keys.remove(index);
+                                self.invalid_nested.remove(&(field, key));
+                            }
+                        }
+                        self.after_field_update();
+                        true
+                    }
+                },
+            )
+        };
+
+    let id_suffix_arms = fields.iter().map(|field| {
+        let field_variant = get_field_variant_ident(field);
+        let field_id_suffix = field_name(field.ident.as_ref().unwrap()).to_case(Case::Kebab);
+        quote! { #field_enum_ident::#field_variant => #field_id_suffix }
+    });
+    let field_missing_arms = fields.iter().map(|field| {
+        let field_variant = get_field_variant_ident(field);
+        let missing =
+            get_required_missing(field, &fields, &struct_attrs).unwrap_or_else(|| quote! { false });
+        quote! { #field_enum_ident::#field_variant => #missing }
+    });
+    let field_valid_arms = fields.iter().map(|field| {
+        let field_variant = get_field_variant_ident(field);
+        let attrs = FieldAttrs::from_field(field);
+        let mut valid_checks = Vec::new();
+        if field_is_parsed(field) {
+            let field_key = get_field_key(field, &field_enum_ident);
+            valid_checks.push(quote! { !self.invalid_numbers.contains_key(&#field_key) });
+        } else if attrs.regex.is_some() {
+            let regex_error_method_ident = get_regex_error_method_ident(field, input_struct_ident);
+            valid_checks.push(quote! { self.#regex_error_method_ident().is_none() });
+        } else if field_is_nested(field) || field_is_nested_vec(field) {
+            valid_checks.push(quote! { !self.invalid_nested.iter().any(|(invalid, _)| *invalid == #field_enum_ident::#field_variant) });
+        }
+        if attrs.validate.is_some() {
+            let validate_error_method_ident = get_validate_error_method_ident(field, input_struct_ident);
+            valid_checks.push(quote! { self.#validate_error_method_ident().is_none() });
+        }
+        if let Some(unchosen) = get_unchosen(field, &attrs) {
+            valid_checks.push(quote! { !#unchosen });
+        }
+        if let Some(error) = get_item_count_error(field, &attrs) {
+            valid_checks.push(quote! { #error.is_none() });
+        }
+        if valid_checks.is_empty() {
+            quote! { #field_enum_ident::#field_variant => true }
+        } else {
+            quote! { #field_enum_ident::#field_variant => #(#valid_checks)&&* }
+        }
+    });
+
+    // Number and date fields holding text which didn't parse are reverted to that text
+    let snapshot_arms = fields.iter().map(|field| {
+        let field_ident = field.ident.clone().unwrap();
+        let field_variant = get_field_variant_ident(field);
+        let msg_variant_ident = get_update_field_msg_variant_ident(field, input_struct_ident);
+        if field_is_parsed(field) {
+            let field_key = get_field_key(field, &field_enum_ident);
+            quote! {
+                #field_enum_ident::#field_variant => Some(match self.invalid_numbers.get(&#field_key) {
+                    Some(text) => #component_msg_ident::InvalidNumber(#field_key, text.clone()),
+                    None => #component_msg_ident::#msg_variant_ident(self.inner().#field_ident),
+                })
+            }
+        } else if field_is_string(field)
+            || field_is_option_string(field)
+            || field_is_bool(field)
+            || field_is_option_bool(field)
+            || field_is_enum(field)
+            || field_is_option_enum(field)
+        {
+            quote! {
+                #field_enum_ident::#field_variant => Some(#component_msg_ident::#msg_variant_ident(self.inner().#field_ident.clone()))
+            }
+        } else {
+            quote! { #field_enum_ident::#field_variant => None }
+        }
+    });
+
+    // The buttons are wrapped in a row placed above the fields, below them, or both, with a class
+    // for each end and for the alignment. As an action bar, the row's buttons sit in an inner
+    // element, so the row can stick to the edge of the screen spanning the form while the buttons
+    // keep their alignment.
+    // Until the last step of a multi-step form, Next takes the place of the submit buttons. It
+    // still submits the form, which moves on a step, so Enter does the same.
+    let submit_buttons = if steps.is_empty() {
+        submit_buttons
+    } else {
+        quote! {
+            if self.current_step > 0 {
+                <button type="button" class={classes!("formula-y-back", &theme.button)} data-formulay-role="back" onclick={link.callback(|_| #component_msg_ident::PreviousStep)}>{"Back"}</button>
+            }
+            if self.current_step < #last_step {
+                <button class={classes!("formula-y-next", &theme.submit)} data-formulay-role="next" disabled={submit_disabled}>{"Next"}</button>
+            } else {
+                #submit_buttons
+            }
+        }
+    };
+    let button_row = |end: &str| {
+        let mut class = format!("formula-y-buttons formula-y-buttons-{}", end);
+        if let Some(align) = &struct_attrs.align {
+            class.push_str(&format!(" formula-y-align-{}", align));
+        }
+        // A form locked by its submit only offers to unlock it again
+        let buttons = quote! {
+            if self.locked {
+                <button type="button" class={classes!("formula-y-unlock", &theme.button)} data-formulay-role="unlock" onclick={link.callback(|_| #component_msg_ident::Unlock)}>{"Edit"}</button>
+            } else {
+                #submit_buttons
+                if ctx.props().onsavedraft.is_some() {
+                    <button type="button" class={classes!("formula-y-save-draft", &theme.button)} data-formulay-role="save-draft" onclick={link.callback(|_| #component_msg_ident::OnSaveDraft)}>{"Save draft"}</button>
+                }
+                if ctx.props().reset_button.unwrap_or_default() {
+                    <button type="button" class={classes!("formula-y-reset", &theme.button)} data-formulay-role="reset" onclick={link.callback(|_| #component_msg_ident::Reset)}>{"Reset"}</button>
+                }
+            }
+            #copy_button
+        };
+        if struct_attrs.action_bar {
+            let bar_class = format!("formula-y-action-bar formula-y-action-bar-{}", end);
+            quote! {
+                <div class={#bar_class}>
+                    <div class={#class}>
+                        #buttons
+                    </div>
+                </div>
+            }
+        } else {
+            quote! {
+                <div class={#class}>
+                    #buttons
+                </div>
+            }
+        }
+    };
+    // Search forms end their row with a single search button, showing the search_icon prop if one
+    // is passed
+    let search_button = quote! {
+        <button type="submit" class={classes!("formula-y-search-button", &theme.submit)} data-formulay-role="submit" aria-label="Search" disabled={submit_disabled}>
+            { ctx.props().search_icon.clone().unwrap_or_else(|| html! { "🔍" }) }
+        </button>
+    };
+    // Forms whose fields are saved individually have nothing to submit, so they have no buttons
+    let top_buttons = match struct_attrs.button_position {
+        _ if struct_attrs.save_individually || search => quote! {},
+        ButtonPosition::Top | ButtonPosition::Both => button_row("top"),
+        ButtonPosition::Bottom => quote! {},
+    };
+    let bottom_buttons = match struct_attrs.button_position {
+        _ if struct_attrs.save_individually => quote! {},
+        _ if search => search_button,
+        ButtonPosition::Bottom | ButtonPosition::Both => button_row("bottom"),
+        ButtonPosition::Top => quote! {},
+    };
+
+    let keyboard_navigation_callback = get_keyboard_navigation_callback();
+    let strict_numeric_listener = get_strict_numeric_listener(&fields);
+    // Text inputs store their values on change, so search forms pass each input on to its input as
+    // a change event. Typing then updates the form and emits onchange as it goes, apart from text
+    // still being composed through an IME, which is passed on once the composition ends.
+    let oninput = if search {
+        quote! {
+            let auto_submit = ctx.props().auto_submit_after_ms.is_some();
+            let oninput = Some(link.batch_callback(move |event: InputEvent| {
+                let target = event.target().unwrap().unchecked_into::<web_sys::Element>();
+                let input_type = target.get_attribute("type").unwrap_or_default();
+                let typed = match target.tag_name().as_str() {
+                    "INPUT" => input_type != "checkbox" && input_type != "radio",
+                    "TEXTAREA" => true,
+                    _ => false,
+                };
+                if typed && !event.is_composing() {
+                    if let Err(error) = #component_ident::dispatch_change(&target) {
+                        return Some(#component_msg_ident::InternalError(error));
+                    }
+                }
+                auto_submit.then(|| #component_msg_ident::ScheduleAutoSubmit)
+            }));
+        }
+    } else {
+        quote! {
+            let oninput = ctx
+                .props()
+                .auto_submit_after_ms
+                .map(|_| link.callback(|_: InputEvent| #component_msg_ident::ScheduleAutoSubmit));
+        }
+    };
+
+    // The live region is kept out of sight, but not hidden from screen readers the way
+    // display: none would
+    let announcement_style = "position: absolute; width: 1px; height: 1px; overflow: hidden; clip: rect(0 0 0 0); white-space: nowrap;";
+    let visit_fields_method = visit::visit_fields_method(&field_enum_ident, &fields);
+    let csv_methods = visit::csv_methods(&fields);
+    // #[yform(derive_default)] gives the struct the same values as new() through Default, for
+    // code which builds the struct without going through the form
+    let default_impl = if struct_attrs.derive_default {
+        quote! {
+            impl Default for #input_struct_ident {
+                fn default() -> Self {
+                    Self::new()
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+    let unmask_arms = fields
+        .iter()
+        .filter(|field| FieldAttrs::from_field(field).masked_display)
+        .map(|field| {
+            let field_variant = get_field_variant_ident(field);
+            let msg_variant_ident = get_update_field_msg_variant_ident(field, input_struct_ident);
+            quote! {
+                #field_enum_ident::#field_variant => {
+                    self.apply_msg(#component_msg_ident::#msg_variant_ident(Default::default()))
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+    let unmask_fallback = if unmask_arms.len() < fields.len() {
+        quote! { _ => true, }
+    } else {
+        quote! {}
+    };
+    let import_methods = visit::import_methods(&fields, &unsupported_inits);
+    let field_variant_idents = fields
+        .iter()
+        .map(get_field_variant_ident)
+        .collect::<Vec<_>>();
+    let field_names = fields
+        .iter()
+        .map(|field| field_name(field.ident.as_ref().unwrap()))
+        .collect::<Vec<_>>();
+    let flagged_method_idents = fields
+        .iter()
+        .map(|field| get_flagged_method_ident(field, input_struct_ident))
+        .collect::<Vec<_>>();
+
+    // The last input event of a composition comes before compositionend in Chrome, while it is
+    // still composing, so search forms pass the picked text on from here instead
+    let composition_commit = if search {
+        quote! {
+            if let Some(target) = event.target() {
+                if let Err(error) = Self::dispatch_change(&target) {
+                    ctx.link().send_message(#component_msg_ident::InternalError(error));
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // The view swaps the form for the review step while it is showing
+    let form = quote! {
+        <form id={self.id_prefix.clone()} class={classes!(self.form_class(), theme.form_classes())} data-formulay-role="form" aria-label={ctx.props().aria_label.clone()} aria-describedby={self.form_described_by(ctx)} onkeydown={keyboard_navigation} oninput={oninput} onsubmit={link.callback(|e: FocusEvent| {
+            e.prevent_default();
+
+            #component_msg_ident::OnSubmit
+        })}>
+
+            #label_placeholder_style
+            #search_style
+            <style>{#autofill_style}</style>
+            if !self.loading.is_empty() {
+                <style>{#loading_style}</style>
+            }
+            if let Some(description) = &ctx.props().description {
+                <p id={format!("{}-description", self.id_prefix)} class="formula-y-description">{description}</p>
+            }
+            if let Some(error) = &self.internal_error {
+                <div class="formula-y-internal-error" role="alert">{error}</div>
+            }
+            if self.cooldown_remaining_ms > 0 {
+                <p class="formula-y-cooldown">{format!("Try again in {}s", (self.cooldown_remaining_ms + 999) / 1000)}</p>
+            }
+            if self.expired {
+                <p class="formula-y-expired-notice" data-formulay-role="countdown" role="alert">{"Time is up"}</p>
+            } else if let Some(deadline) = self.deadline {
+                <p class="formula-y-countdown" data-formulay-role="countdown" role="timer">{format!("Time remaining {}", deadline.countdown())}</p>
+            }
+            if ctx.props().announce_submit.unwrap_or(true) {
+                <div class="formula-y-announcement" role="status" aria-live="polite" aria-atomic="true" style={#announcement_style}>{&self.announcement}</div>
+            }
+            #init_merge_notice
+            #step_indicator
+            #top_buttons
+            #(#form_items)*
+
+            #bottom_buttons
+        </form>
+    };
+    let form = if struct_attrs.shared_state {
+        form
+    } else {
+        quote! {
+            if ctx.props().nested.is_some() {
+                <fieldset id={self.id_prefix.clone()} class={classes!(self.form_class(), "formula-y-nested", theme.form_classes())} data-formulay-role="form">
+                    #label_placeholder_style
+                    #(#form_items)*
+                </fieldset>
+            } else {
+                #form
+            }
+        }
+    };
+    let form_view = if struct_attrs.review {
+        let review_view = review::review_view(
+            input_struct_ident,
+            &field_enum_ident,
+            &component_msg_ident,
+            &fields,
+        );
+        quote! {
+            if self.reviewing {
+                #review_view
+            } else {
+                #form
+            }
+        }
+    } else {
+        form
+    };
+
+    quote! {
+
+        #(#unsupported_warnings)*
+        #(#option_warnings)*
+
+        impl #input_struct_ident {
+            pub fn new() -> Self {
+                Self {
+                    #(#component_field_inits,)*
+                    #(#unsupported_inits,)*
+                }
+            }
+
+            #visit_fields_method
+
+            #csv_methods
+
+            #import_methods
+        }
+
+        #default_impl
+
+        pub struct #component_ident {
+            inner: #storage_type,
+            display_required_warnings: bool,
+            submitted: bool,
+            // Whether any field has been changed since the form was created
+            dirty: bool,
+            // Whether onabandon has been called since the last change
+            abandoned: bool,
+            // Whether a submit with lock_on_submit made every field read only, until unlocked
+            locked: bool,
+            // The theme from a ContextProvider<Theme> above the form, kept up to date by the handle
+            context_theme: Option<formula_y::Theme>,
+            theme_handle: Option<yew::context::ContextHandle<formula_y::Theme>>,
+            // Prepended to the ids of every generated element
+            id_prefix: String,
+            // Whether the last submit attempt was blocked, until the next change to a field
+            submit_failed: bool,
+            // The text of number inputs which doesn't parse, by field name
+            invalid_numbers: std::collections::HashMap<#field_enum_ident, String>,
+            // The focused field, along with the msg which reverts it to its value at focus time
+            focus_snapshot: Option<(#field_enum_ident, #component_msg_ident)>,
+            // The nested fields whose values couldn't be submitted, along with the key of the item
+            // for Vec fields
+            invalid_nested: std::collections::HashSet<(#field_enum_ident, usize)>,
+            #item_key_fields
+            #format_field
+            #login_field
+            #init_merge_field
+            #step_field
+            #validation_error_field
+            // The field being edited in inline mode, and whether it has changed since its edit began
+            inline_editing: Option<#field_enum_ident>,
+            inline_changed: bool,
+            // The id suffix of the field to focus after the next render
+            focus_on_render: Option<&'static str>,
+            // Set when a field changes, until the change is passed to onchange
+            value_changed: bool,
+            // Counts down to an auto submit, which is cancelled by dropping it
+            auto_submit_timer: Option<gloo_timers::callback::Timeout>,
+            // The last failed_submits prop seen, which starts a cooldown whenever it goes up
+            failed_submits: Option<usize>,
+            // How long until the form can be submitted again after a failed submit
+            cooldown_remaining_ms: u32,
+            // Ticks the cooldown down once a second, which is cancelled by dropping it
+            cooldown_timer: Option<gloo_timers::callback::Interval>,
+            // The last deadline prop seen, which the countdown runs to
+            deadline: Option<formula_y::Deadline>,
+            // Ticks the countdown once a second until the deadline passes
+            deadline_timer: Option<gloo_timers::callback::Interval>,
+            // Whether the deadline has passed, leaving the form read only
+            expired: bool,
+            // A failure which would otherwise have panicked, shown in a banner
+            internal_error: Option<String>,
+            // The outcome of the last submit attempt, in the live region screen readers announce
+            announcement: String,
+            // The fields the user has changed, which init values arriving late leave alone
+            edited: std::collections::HashSet<#field_enum_ident>,
+            // Every change made to a field, oldest first, when the audit_trail prop is set
+            edits: Vec<FieldEdit<#field_enum_ident>>,
+            // When each field was first focused, while the ontelemetry prop is set
+            completion_timer: CompletionTimer<#field_enum_ident>,
+            // The fields waiting on their init value as of the last props, shown as placeholders
+            loading: std::collections::HashSet<#field_enum_ident>,
+            // Set while text is being composed through an IME in one of the form's inputs
+            composing: bool,
+            // Listeners for events yew can't listen for, kept for as long as the form is mounted
+            document_listeners: Vec<gloo_events::EventListener>,
+            #initial_field
+            // The last reset_count prop seen, which resets the form whenever it changes
+            reset_count: Option<usize>,
+            // The last submitted_reset_count prop seen, which clears submitted whenever it changes
+            submitted_reset_count: Option<usize>,
+            #review_fields
+            #throttle_fields
+            #unit_fields
+            #time_zone_fields
+        }
+
+        impl #component_ident {
+            /// The class naming this form, alongside `formula-y-form`
+            pub const FORM_CLASS: &'static str = #own_form_class;
+
+            #(#class_consts)*
+
+            /// The field name, label class and input class of each rendered field
+            pub const CLASSES: &'static [(&'static str, &'static str, &'static str)] = &[#(#class_table),*];
+
+            fn from_inner(inner: #state_type) -> Self {
+                // Each instance gets its own id prefix so that mounting the same form more than
+                // once on a page doesn't cross-wire the labels and inputs
+                static INSTANCES: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+                let instance = INSTANCES.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+
+                let form = Self {
+                    id_prefix: format!("{}-{}", #id_base, instance),
+                    #initial_init
+                    inner: #inner_storage,
+                    submitted: false,
+                    display_required_warnings: false,
+                    dirty: false,
+                    abandoned: false,
+                    locked: false,
+                    context_theme: None,
+                    theme_handle: None,
+                    submit_failed: false,
+                    invalid_numbers: std::collections::HashMap::new(),
+                    focus_snapshot: None,
+                    invalid_nested: std::collections::HashSet::new(),
+                    #item_key_inits
+                    #format_init
+                    #login_init
+                    #init_merge_init
+                    #step_init
+                    #validation_error_init
+                    inline_editing: None,
+                    inline_changed: false,
+                    focus_on_render: None,
+                    value_changed: false,
+                    auto_submit_timer: None,
+                    failed_submits: None,
+                    cooldown_remaining_ms: 0,
+                    cooldown_timer: None,
+                    deadline: None,
+                    deadline_timer: None,
+                    expired: false,
+                    internal_error: None,
+                    announcement: String::new(),
+                    edited: std::collections::HashSet::new(),
+                    edits: Vec::new(),
+                    completion_timer: CompletionTimer::default(),
+                    loading: std::collections::HashSet::new(),
+                    composing: false,
+                    document_listeners: Vec::new(),
+                    reset_count: None,
+                    submitted_reset_count: None,
+                    #review_inits
+                    #throttle_inits
+                    #unit_inits
+                    #time_zone_inits
+                };
+                #compute_on_create
+            }
+
+            #inner_accessors
+
+            // The id of a field's input, which its label points to
+            fn field_id(&self, field: &str) -> String {
+                format!("{}-{}", self.id_prefix, field)
+            }
+
+            // The ids of the elements describing the form, from the described_by prop and the
+            // paragraph rendered for the description prop
+            fn form_described_by(&self, ctx: &Context<Self>) -> Option<String> {
+                let description_id = ctx
+                    .props()
+                    .description
+                    .as_ref()
+                    .map(|_| format!("{}-description", self.id_prefix));
+                let ids = ctx
+                    .props()
+                    .described_by
+                    .iter()
+                    .cloned()
+                    .chain(description_id)
+                    .collect::<Vec<_>>();
+                (!ids.is_empty()).then(|| ids.join(" "))
+            }
+
+            // Whether the form has been submitted since it was created, last reset, or last had its
+            // submitted_reset_count prop changed
+            pub fn is_submitted(&self) -> bool {
+                self.submitted
+            }
+
+            // The field's label from the labels prop, or else its own
+            fn label_for(&self, ctx: &Context<Self>, field: #field_enum_ident, label: &str) -> String {
+                ctx.props()
+                    .labels
+                    .as_ref()
+                    .and_then(|labels| labels.get(&field))
+                    .cloned()
+                    .unwrap_or_else(|| label.to_string())
+            }
+
+            // The theme prop, or else the theme provided by a context above the form, with the
+            // density prop in place of the theme's own
+            fn theme(&self, ctx: &Context<Self>) -> formula_y::Theme {
+                let theme = ctx.props()
+                    .theme
+                    .clone()
+                    .or_else(|| self.context_theme.clone())
+                    .unwrap_or_default();
+                match ctx.props().density {
+                    Some(density) => theme.with_density(density),
+                    None => theme,
+                }
+            }
+
+            // Whether a submit with the lock_on_submit prop set has made the fields read only
+            pub fn is_locked(&self) -> bool {
+                self.locked
+            }
+
+            // Hand each field's current value to a visitor, as the struct's visit_fields does
+            pub fn visit_fields(&self, visitor: &mut impl FieldVisitor<#field_enum_ident>) {
+                self.inner().visit_fields(visitor)
+            }
+
+            // Every change made to a field while the audit_trail prop was set, oldest first. Resets
+            // are recorded too, rather than clearing it.
+            pub fn edits(&self) -> &[FieldEdit<#field_enum_ident>] {
+                &self.edits
+            }
+
+            // Whether changes to the fields are recorded, which giving onedit implies
+            fn audits(ctx: &Context<Self>) -> bool {
+                ctx.props().audit_trail.unwrap_or_default() || ctx.props().onedit.is_some()
+            }
+
+            // The value of every field, taken before a msg when changes are recorded
+            fn audit_snapshot(&self, audit: bool) -> Option<Vec<FieldValue>> {
+                audit.then(|| {
+                    let mut values = Vec::new();
+                    self.visit_fields(&mut |_: #field_enum_ident, _: &FieldMeta, value: FieldValue| {
+                        values.push(value);
+                    });
+                    values
+                })
+            }
+
+            // Record the fields whose values differ from a snapshot, returning the new edits
+            fn record_edits(&mut self, before: Option<Vec<FieldValue>>) -> &[FieldEdit<#field_enum_ident>] {
+                let recorded = self.edits.len();
+                if let Some(before) = before {
+                    let mut before = before.into_iter();
+                    let mut edits = Vec::new();
+                    self.visit_fields(&mut |field: #field_enum_ident, meta: &FieldMeta, value: FieldValue| {
+                        if let Some(old) = before.next().filter(|old| *old != value) {
+                            edits.push(FieldEdit::now(field, meta.name, old, value));
+                        }
+                    });
+                    self.edits.extend(edits);
+                }
+                &self.edits[recorded..]
+            }
+
+            // Pass newly recorded edits on to onedit
+            fn emit_edits(&mut self, ctx: &Context<Self>, before: Option<Vec<FieldValue>>) {
+                let edits = self.record_edits(before);
+                if let Some(onedit) = &ctx.props().onedit {
+                    for edit in edits {
+                        onedit.emit(edit.clone());
+                    }
+                }
+            }
+
+            // The field an event happened in, from the id of its input or of the fieldset around its
+            // radios
+            fn event_field(&self, event: &web_sys::Event) -> Option<#field_enum_ident> {
+                let target = event.target()?.dyn_into::<web_sys::Element>().ok()?;
+                let id = target.closest("[id]").ok()??.id();
+                [#(#field_enum_ident::#all_field_variants),*]
+                    .into_iter()
+                    .find(|field| self.field_id(Self::field_id_suffix(*field)) == id)
+            }
+
+            // Pass the fields holding a value on to onabandon when the form is left changed but
+            // unsubmitted, once for each run of changes
+            fn report_abandon(&mut self, ctx: &Context<Self>) {
+                if let Some(onabandon) = &ctx.props().onabandon {
+                    if self.dirty && !self.submitted && !self.abandoned {
+                        self.abandoned = true;
+                        let mut filled = Vec::new();
+                        self.visit_fields(&mut |field: #field_enum_ident, _: &FieldMeta, value: FieldValue| {
+                            if !value.to_text().is_empty() {
+                                filled.push(field);
+                            }
+                        });
+                        onabandon.emit(filled);
+                    }
+                }
+            }
+
+            // Pass each timed field which now holds a valid value on to ontelemetry
+            fn emit_completed_fields(&mut self, ctx: &Context<Self>) {
+                let ontelemetry = match &ctx.props().ontelemetry {
+                    Some(ontelemetry) => ontelemetry,
+                    None => return,
+                };
+                let mut completed = Vec::new();
+                self.visit_fields(&mut |field: #field_enum_ident, meta: &FieldMeta, value: FieldValue| {
+                    if !value.to_text().is_empty() {
+                        completed.push((field, meta.name));
+                    }
+                });
+                for (field, name) in completed {
+                    if self.completion_timer.is_pending(field)
+                        && self.field_is_valid(field)
+                        && !self.field_is_missing(field)
+                    {
+                        if let Some(event) = self.completion_timer.complete(field, name) {
+                            ontelemetry.emit(event);
+                        }
+                    }
+                }
+            }
+
+            pub fn required_components_provided(&self) -> bool {
+                #(#checks)*
+
+                true
+            }
+
+            // Whether every value entered into a field with a regex matches it
+            fn regexes_match(&self) -> bool {
+                #(#regex_checks &&)* true
+            }
+
+            // Whether every field with choices holds one of them
+            fn choices_match(&self) -> bool {
+                #(#choice_checks &&)* true
+            }
+
+            // Whether every Vec field with min_items or max_items holds a number of items between them
+            fn item_counts_fit(&self) -> bool {
+                #(#item_count_checks &&)* true
+            }
+
+            // Whether every field with a validate fn accepts its value
+            fn fields_validate(&self) -> bool {
+                #(#validate_checks &&)* true
+            }
+
+            // Whether the values could be submitted. Numbers which don't parse have no value to
+            // submit, and values not matching their regex, not one of their choices, with too few or
+            // too many items or rejected by their validate fn are never valid, so they block even when
+            // required fields aren't enforced, as do nested forms blocked for any of these reasons.
+            fn can_submit(&self, enforce_required: bool) -> bool {
+                (self.required_components_provided() || !enforce_required)
+                    && self.invalid_numbers.is_empty()
+                    && self.regexes_match()
+                    && self.choices_match()
+                    && self.item_counts_fit()
+                    && self.fields_validate()
+                    && self.invalid_nested.is_empty()
+            }
+
+            #validate_struct_method
+
+            // Attempt a submit, returning whether the required fields allow it, and the struct's
+            // validate fn when it has one. A submit let through with WarnOnly still flags the
+            // required fields left empty.
+            fn try_submit(&mut self, required_policy: RequiredPolicy) -> bool {
+                if self.can_submit(required_policy.blocks_submit()) #validate_struct_check {
+                    self.submitted = true;
+                    self.display_required_warnings = required_policy == RequiredPolicy::WarnOnly
+                        && !self.required_components_provided();
+                    true
+                } else {
+                    false
+                }
+            }
+
+            // Before the last step of a multi-step form, submitting moves on to the next step once
+            // every field of the current one could be submitted, and flags them otherwise. Returns
+            // whether the submit was used up this way.
+            fn submit_step(&mut self, #enforce_required_param: bool) -> bool {
+                #submit_step_body
+            }
+
+            #step_methods
+
+            // Whether a submit which passed the required field checks should stop at the review step
+            // rather than calling onsubmit
+            fn hold_for_review(&mut self) -> bool {
+                #hold_for_review
+            }
+
+            // Send an input a change event, as if the user had left it, so that it stores its value
+            fn dispatch_change(target: &web_sys::EventTarget) -> Result<(), String> {
+                let init = web_sys::EventInit::new();
+                init.set_bubbles(true);
+                let change = web_sys::Event::new_with_event_init_dict("change", &init)
+                    .map_err(|error| format!("Couldn't create a change event: {:?}", error))?;
+                target
+                    .dispatch_event(&change)
+                    .map(|_| ())
+                    .map_err(|error| format!("Couldn't send a change event: {:?}", error))
+            }
+
+            // The fields waiting on their init value, either all of them with the loading prop or the
+            // ones in loading_fields
+            fn loading_fields(ctx: &Context<Self>) -> std::collections::HashSet<#field_enum_ident> {
+                if ctx.props().loading.unwrap_or_default() {
+                    [#(#field_enum_ident::#all_field_variants),*].into_iter().collect()
+                } else {
+                    ctx.props().loading_fields.iter().flatten().copied().collect()
+                }
+            }
+
+            // How the field_policy prop wants a field shown, defaulting to editable
+            fn field_access(&self, ctx: &Context<Self>, field: #field_enum_ident) -> FieldAccess {
+                ctx.props()
+                    .field_policy
+                    .as_ref()
+                    .map_or(FieldAccess::Editable, |policy| policy.access(field))
+            }
+
+            // The suffix of a field's input id
+            fn field_id_suffix(field: #field_enum_ident) -> &'static str {
+                match field {
+                    #(#id_suffix_arms,)*
+                }
+            }
+
+            // Whether a field holds a value which could be submitted, apart from the required
+            // field checks
+            fn field_is_valid(&self, field: #field_enum_ident) -> bool {
+                match field {
+                    #(#field_valid_arms,)*
+                }
+            }
+
+            // Whether a field is missing a value it requires
+            fn field_is_missing(&self, field: #field_enum_ident) -> bool {
+                match field {
+                    #(#field_missing_arms,)*
+                }
+            }
+
+            // Finish editing a field inline, unless it holds a value which isn't valid, or is
+            // missing a required value while those are enforced, which flags it. Gives whether
+            // it changed since its edit began, or None if it is still being edited.
+            fn confirm_inline(&mut self, field: #field_enum_ident, enforce_required: bool) -> Option<bool> {
+                if self.inline_editing != Some(field) || !self.field_is_valid(field) {
+                    return None;
+                }
+                if enforce_required {
+                    self.display_required_warnings = self.field_is_missing(field);
+                    if self.display_required_warnings {
+                        return None;
+                    }
+                }
+                self.inline_editing = None;
+                Some(std::mem::take(&mut self.inline_changed))
+            }
+
+            #item_methods
+
+            #format_method
+
+            // The msg which sets a field back to its current value, for reverting to it later
+            fn field_snapshot(&self, field: #field_enum_ident) -> Option<#component_msg_ident> {
+                match field {
+                    #(#snapshot_arms,)*
+                }
+            }
+
+            // Replace the value the form holds, i.e. with the value prop of a controlled form. Text
+            // which didn't parse is kept, since it never made it into the value.
+            fn set_value(&mut self, inner: #state_type) {
+                self.inner = #inner_storage;
+                #(#sync_item_keys)*
+            }
+
+            // Put the form back the way it was created, keeping its ids
+            fn reset(&mut self) {
+                let inner = #reset_state;
+                let id_prefix = std::mem::take(&mut self.id_prefix);
+                let reset_count = self.reset_count;
+                let submitted_reset_count = self.submitted_reset_count;
+                let failed_submits = self.failed_submits;
+                let cooldown_remaining_ms = self.cooldown_remaining_ms;
+                let cooldown_timer = self.cooldown_timer.take();
+                let deadline = self.deadline;
+                let deadline_timer = self.deadline_timer.take();
+                let expired = self.expired;
+                let composing = self.composing;
+                let document_listeners = std::mem::take(&mut self.document_listeners);
+                let loading = std::mem::take(&mut self.loading);
+                let edits = std::mem::take(&mut self.edits);
+                let context_theme = self.context_theme.take();
+                let theme_handle = self.theme_handle.take();
+                #keep_init_seen
+                *self = Self::from_inner(inner);
+                self.id_prefix = id_prefix;
+                self.reset_count = reset_count;
+                self.submitted_reset_count = submitted_reset_count;
+                // Resetting the form doesn't get around the cooldown
+                self.failed_submits = failed_submits;
+                self.cooldown_remaining_ms = cooldown_remaining_ms;
+                self.cooldown_timer = cooldown_timer;
+                // or the deadline
+                self.deadline = deadline;
+                self.deadline_timer = deadline_timer;
+                self.expired = expired;
+                self.composing = composing;
+                self.document_listeners = document_listeners;
+                self.loading = loading;
+                self.edits = edits;
+                self.context_theme = context_theme;
+                self.theme_handle = theme_handle;
+                #restore_init_seen
+            }
+
+            #take_init_method
+
+            // The value passed to onsubmit
+            fn submit_value(&self) -> #state_type {
+                #submit_value
+            }
+
+            // The value as entered, which is passed to onsavedraft
+            fn current_value(&self) -> #state_type {
+                #current_value
+            }
+
+            // Put a message in the live region for screen readers to announce. A message the same as
+            // the last one gets a trailing non-breaking space, so that it is announced again.
+            fn announce(&mut self, message: String) {
+                self.announcement = if message == self.announcement {
+                    format!("{}\u{a0}", message)
+                } else {
+                    message
+                };
+            }
+
+            fn announce_submitted(&mut self, ctx: &Context<Self>) {
+                let message = ctx
+                    .props()
+                    .submitted_announcement
+                    .clone()
+                    .unwrap_or_else(|| "Form submitted".to_string());
+                self.announce(message);
+            }
+
+            // Announce how many fields are flagged after a submit attempt was blocked. A blocked
+            // submit always counts at least one error, even one only a nested form shows.
+            fn announce_errors(&mut self, ctx: &Context<Self>) {
+                let count = ([#(self.#flagged_method_idents()),*].into_iter().filter(|flagged| *flagged).count()
+                    + self.invalid_nested.len())
+                    .max(1);
+                let message = match &ctx.props().errors_announcement {
+                    Some(errors_announcement) => errors_announcement.message(count),
+                    None if count == 1 => "1 error found".to_string(),
+                    None => format!("{} errors found", count),
+                };
+                self.announce(message);
+            }
+
+            // Stop the form being submitted for a while after the parent reports a failed submit.
+            // The cooldown doubles with each failure in a row, up to submit_cooldown_max_ms.
+            fn start_cooldown(&mut self, ctx: &Context<Self>, failures: usize) {
+                let base = match ctx.props().submit_cooldown_ms {
+                    Some(base) if base > 0 => base,
+                    _ => return,
+                };
+                let max = ctx.props().submit_cooldown_max_ms.unwrap_or(5 * 60 * 1000).max(base);
+                let doublings = (failures - 1).min(31) as u32;
+                self.cooldown_remaining_ms = base.saturating_mul(2u32.saturating_pow(doublings)).min(max);
+                let link = ctx.link().clone();
+                self.cooldown_timer = Some(gloo_timers::callback::Interval::new(1000, move || {
+                    link.send_message(#component_msg_ident::CooldownTick);
+                }));
+                if let Some(oncooldown) = &ctx.props().oncooldown {
+                    oncooldown.emit(self.cooldown_remaining_ms);
+                }
+            }
+
+            #throttle_method
+
+            #unit_methods
+
+            // Count down to the deadline prop, or stop counting when it's taken away. A later
+            // deadline gives an expired form more time.
+            fn start_deadline(&mut self, ctx: &Context<Self>) {
+                self.deadline = ctx.props().deadline;
+                self.deadline_timer = None;
+                self.expired = false;
+                if self.deadline.is_some() {
+                    let link = ctx.link().clone();
+                    self.deadline_timer = Some(gloo_timers::callback::Interval::new(1000, move || {
+                        link.send_message(#component_msg_ident::DeadlineTick);
+                    }));
+                    self.check_deadline(ctx);
+                }
+            }
+
+            // Once the deadline passes the form stops counting and can't be changed or submitted,
+            // and onexpire is passed the values as they stand
+            fn check_deadline(&mut self, ctx: &Context<Self>) {
+                if self.expired || !self.deadline.map_or(false, |deadline| deadline.is_past()) {
+                    return;
+                }
+                self.expired = true;
+                self.deadline_timer = None;
+                if let Some(onexpire) = &ctx.props().onexpire {
+                    onexpire.emit(self.current_value());
+                }
+            }
+
+            // Everything the form holds beyond its props, for the snapshot prop to restore later
+            pub fn snapshot(&self) -> #snapshot_ident {
+                #snapshot_ident {
+                    value: self.current_value(),
+                    unparsed: self.invalid_numbers.clone(),
+                    validation_errors: #snapshot_validation_errors,
+                    required_warnings: self.display_required_warnings,
+                    dirty: self.dirty,
+                    edited: self.edited.clone(),
+                    step: #snapshot_step,
+                }
+            }
+
+            // Pick up where a snapshot left off
+            pub fn restore(&mut self, state: #snapshot_ident) {
+                self.set_value(state.value);
+                self.invalid_numbers = state.unparsed;
+                #restore_validation_errors
+                self.display_required_warnings = state.required_warnings;
+                self.dirty = state.dirty;
+                self.edited = state.edited;
+                #restore_step
+            }
+
+            // Whether the deadline prop has passed, leaving the form read only
+            pub fn is_expired(&self) -> bool {
+                self.expired
+            }
+
+            // How long until the form can be submitted again after a failed submit, or 0 when it
+            // can be submitted now
+            pub fn cooldown_remaining_ms(&self) -> u32 {
+                self.cooldown_remaining_ms
+            }
+
+            // Bookkeeping shared by every field update
+            fn after_field_update(&mut self) {
+                self.dirty = true;
+                self.abandoned = false;
+                self.inline_changed = true;
+                self.value_changed = true;
+
+                // The class flagging a failed submit is dropped as soon as the user makes a change
+                self.submit_failed = false;
+                #revalidate_struct
+            }
+
+            fn form_class(&self) -> String {
+                let mut class = #form_class.to_string();
+                if self.submit_failed {
+                    class.push_str(" formula-y-submit-failed");
+                }
+                if self.submitted {
+                    class.push_str(" formula-y-submitted");
+                }
+                if self.locked {
+                    class.push_str(" formula-y-locked");
+                }
+                if self.expired {
+                    class.push_str(" formula-y-expired");
+                }
+                class
+            }
+
+            // Apply the msgs which only touch the component's own state, returning whether to rerender
+            fn apply_msg(&mut self, msg: #component_msg_ident) -> bool {
+                match msg {
+                    #(#match_arms_update,)*
+
+                    #component_msg_ident::InvalidNumber(field, text) => {
+                        self.invalid_numbers.insert(field, text);
+                        self.dirty = true;
+                        true
+                    }
+
+                    #component_msg_ident::NestedValidity(field, key, valid) => {
+                        if valid {
+                            self.invalid_nested.remove(&(field, key));
+                        } else {
+                            self.invalid_nested.insert((field, key));
+                        }
+                        false
+                    }
+
+                    #item_apply_arms
+                    #format_apply_arm
+                    #login_apply_arm
+                    #init_merge_apply_arm
+                    #step_apply_arm
+
+                    // Changing a masked field starts it over empty, rather than editing the value
+                    // it was masking
+                    #component_msg_ident::UnmaskField(field) => {
+                        self.focus_on_render = Some(Self::field_id_suffix(field));
+                        match field {
+                            #(#unmask_arms,)*
+                            #unmask_fallback
+                        }
+                    }
+
+                    #component_msg_ident::EditInline(field) => {
+                        self.inline_editing = Some(field);
+                        self.inline_changed = false;
+                        self.focus_on_render = Some(Self::field_id_suffix(field));
+                        true
+                    }
+
+                    #component_msg_ident::FocusField(field) => {
+                        self.focus_snapshot = self.field_snapshot(field).map(|revert| (field, revert));
+                        false
+                    }
+
+                    // The snapshot is taken again once applied, so Escape keeps reverting to the
+                    // value at focus time until the field loses focus
+                    #component_msg_ident::RevertField(field) => match self.focus_snapshot.take() {
+                        Some((focused, revert)) if focused == field => {
+                            let rerender = self.apply_msg(revert);
+                            self.focus_snapshot = self.field_snapshot(field).map(|revert| (field, revert));
+                            rerender
+                        }
+                        snapshot => {
+                            self.focus_snapshot = snapshot;
+                            false
+                        }
+                    },
+
+                    #component_msg_ident::DisplayRequiredWarnings => {
+                        self.display_required_warnings = true;
+                        self.submit_failed = true;
+                        true
+                    }
+
+                    #component_msg_ident::Reset => {
+                        self.reset();
+                        self.value_changed = true;
+                        true
+                    }
+
+                    #copy_apply_arm
+
+                    #unit_apply_arms
+
+                    #time_zone_apply_arm
+
+                    #review_apply_arm
+
+                    // Submitting and its timers need the props, so they are handled in update
+                    #action_apply_arm
+                    #throttle_apply_arm
+                    #component_msg_ident::OnSubmit
+                    | #component_msg_ident::OnSaveDraft
+                    | #component_msg_ident::ConfirmInline(_)
+                    | #component_msg_ident::ScheduleAutoSubmit
+                    | #component_msg_ident::AutoSubmit
+                    | #component_msg_ident::CooldownTick
+                    | #component_msg_ident::DeadlineTick
+                    | #component_msg_ident::InternalError(_)
+                    | #component_msg_ident::CompositionStart(_)
+                    | #component_msg_ident::CompositionEnd(_)
+                    | #component_msg_ident::Autofill(_)
+                    | #component_msg_ident::FieldFocus(_)
+                    | #component_msg_ident::PageHide
+                    | #component_msg_ident::Unlock => false,
+                    #component_msg_ident::ThemeChanged(theme) => {
+                        self.context_theme = Some(theme);
+                        true
+                    }
+                }
+            }
+
+            #(#compute_methods)*
+
+            #(#msg_methods)*
+
+            #(#regex_error_methods)*
+
+            #(#validate_error_methods)*
+
+            #(#get_class_methods)*
+        }
+
+        #tester
+
+        #wasm_tests
+
+        #preview
+
+        #hook
+
+        #nested_impl
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum #field_enum_ident {
+            #(#field_variants,)*
+        }
+
+        // Fields are written as they are named in the struct
+        impl std::fmt::Display for #field_enum_ident {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(match *self {
+                    #(#field_enum_ident::#field_variant_idents => #field_names,)*
+                })
+            }
+        }
+
+        impl std::str::FromStr for #field_enum_ident {
+            type Err = UnknownField;
+
+            fn from_str(name: &str) -> Result<Self, Self::Err> {
+                match name {
+                    #(#field_names => Ok(#field_enum_ident::#field_variant_idents),)*
+                    _ => Err(UnknownField(name.to_string())),
+                }
+            }
+        }
+
+        #action_enum
+
+        pub enum #component_msg_ident {
+            #(#msg_variants,)*
+
+            OnSubmit,
+            OnSaveDraft,
+            #action_msg_variant
+            #copy_msg_variant
+            #review_msg_variant
+            #throttle_msg_variant
+            #unit_msg_variants
+            #time_zone_msg_variant
+            InvalidNumber(#field_enum_ident, String),
+            NestedValidity(#field_enum_ident, usize, bool),
+            #item_msg_variants
+            #format_msg_variant
+            #login_msg_variant
+            #init_merge_msg_variant
+            #step_msg_variant
+            EditInline(#field_enum_ident),
+            UnmaskField(#field_enum_ident),
+            ConfirmInline(#field_enum_ident),
+            FocusField(#field_enum_ident),
+            RevertField(#field_enum_ident),
+            ScheduleAutoSubmit,
+            AutoSubmit,
+            CooldownTick,
+            DeadlineTick,
+            InternalError(String),
+            CompositionStart(web_sys::CompositionEvent),
+            CompositionEnd(web_sys::CompositionEvent),
+            Autofill(web_sys::Event),
+            FieldFocus(web_sys::Event),
+            PageHide,
+            Unlock,
+            ThemeChanged(formula_y::Theme),
+            Reset,
+            DisplayRequiredWarnings
+        }
+
+        #props
+
+        #snapshot_struct
+
+        impl Component for #component_ident {
+            type Message = #component_msg_ident;
+            type Properties = #component_prop_ident;
+
+            fn create(ctx: &Context<Self>) -> Self {
+
+                let inner = if let Some(value) = &ctx.props().value {
+                    value.clone()
+                } else if let Some(init) = &ctx.props().init {
+                    init.clone()
+                } else {
+                    #new_state
+                };
+
+                let mut form = Self::from_inner(inner);
+                if let Some(id_prefix) = &ctx.props().id_prefix {
+                    form.id_prefix = id_prefix.clone();
+                }
+                form.reset_count = ctx.props().reset_count;
+                form.submitted_reset_count = ctx.props().submitted_reset_count;
+                form.failed_submits = ctx.props().failed_submits;
+                if let Some(snapshot) = &ctx.props().snapshot {
+                    form.restore(snapshot.clone());
+                }
+                form.start_deadline(ctx);
+                form.loading = Self::loading_fields(ctx);
+                if let Some((theme, handle)) = ctx.link().context::<formula_y::Theme>(ctx.link().callback(#component_msg_ident::ThemeChanged)) {
+                    form.context_theme = Some(theme);
+                    form.theme_handle = Some(handle);
+                }
+                #(#regex_compile_checks)*
+                #init_seen_on_create
+                form
+            }
+
+            fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+
+                match msg {
+                    #component_msg_ident::OnSubmit => {
+                        // Submits wait out the cooldown after a failed one, i.e. pressing Enter,
+                        // a locked form has to be unlocked first, and an expired one is done
+                        if self.cooldown_remaining_ms > 0 || self.locked || self.expired {
+                            return false;
+                        }
+
+                        let required_policy = ctx.props().enforce_required_fields.unwrap_or_default();
+                        if self.submit_step(required_policy.blocks_submit()) {
+                            if self.submit_failed {
+                                self.announce_errors(ctx);
+                            }
+                            return true;
+                        }
+
+                        if self.try_submit(required_policy) {
+                            if !self.hold_for_review() {
+                                if let Some(onsubmit) = &ctx.props().onsubmit {
+                                    onsubmit.emit(self.submit_value());
+                                }
+                                #emit_onsubmit_rc
+                                if let Some(ontelemetry) = &ctx.props().ontelemetry {
+                                    if let Some(event) = self.completion_timer.finish() {
+                                        ontelemetry.emit(event);
+                                    }
+                                }
+                                if ctx.props().clear_on_submit.unwrap_or_default() {
+                                    self.reset();
+                                    // Clearing the values doesn't undo the submit
+                                    self.submitted = true;
+                                }
+                                self.locked = ctx.props().lock_on_submit.unwrap_or_default() && !#search;
+                                self.announce_submitted(ctx);
+                            }
+                        } else {
+                            ctx.link().send_message(#component_msg_ident::DisplayRequiredWarnings);
+                        }
+                        true
+                    },
+
+                    // Drafts skip the required field checks, since they are expected to be incomplete
+                    #component_msg_ident::OnSaveDraft => {
+                        if let Some(onsavedraft) = &ctx.props().onsavedraft {
+                            onsavedraft.emit(self.current_value());
+                        }
+                        false
+                    },
+                    #component_msg_ident::ConfirmInline(field) => {
+                        // Fields saved on their own have to hold any value they require
+                        let enforce_required = #save_individually && ctx.props().enforce_required_fields.unwrap_or_default().blocks_submit();
+                        let displayed_required_warnings = self.display_required_warnings;
+                        let before = self.audit_snapshot(Self::audits(ctx));
+                        match self.confirm_inline(field, enforce_required) {
+                            Some(changed) => {
+                                if changed {
+                                    self.emit_edits(ctx, before);
+                                    self.emit_completed_fields(ctx);
+                                    if let Some(onfieldchange) = &ctx.props().onfieldchange {
+                                        onfieldchange.emit((field, self.current_value()));
+                                    }
+                                    #emit_onfieldsave
+                                }
+                                true
+                            }
+                            None => self.display_required_warnings != displayed_required_warnings,
+                        }
+                    },
+                    // Every input restarts the countdown, replacing the timer which was running. None
+                    // is started while composing, as the composition ending starts one.
+                    #component_msg_ident::ScheduleAutoSubmit => {
+                        if let Some(delay) = ctx.props().auto_submit_after_ms.filter(|_| !self.composing) {
+                            let link = ctx.link().clone();
+                            self.auto_submit_timer = Some(gloo_timers::callback::Timeout::new(delay, move || {
+                                link.send_message(#component_msg_ident::AutoSubmit);
+                            }));
+                        }
+                        false
+                    },
+                    // Text inputs only store their value on change, so the focused input is sent a change
+                    // event first. The update it queues runs before the submit queued after it.
+                    #component_msg_ident::AutoSubmit => {
+                        self.auto_submit_timer = None;
+                        let document = web_sys::window().and_then(|window| window.document());
+                        if let Some(document) = document {
+                            let form = document.get_element_by_id(&self.id_prefix);
+                            if let (Some(form), Some(active)) = (form, document.active_element()) {
+                                if form.contains(Some(&active)) {
+                                    // Submitting without the input's value would submit a stale one
+                                    if let Err(error) = Self::dispatch_change(&active) {
+                                        ctx.link().send_message(#component_msg_ident::InternalError(error));
+                                        return false;
+                                    }
+                                }
+                            }
+                        }
+                        ctx.link().send_message(#component_msg_ident::OnSubmit);
+                        false
+                    },
+                    #component_msg_ident::DeadlineTick => {
+                        self.check_deadline(ctx);
+                        true
+                    },
+                    #component_msg_ident::CooldownTick => {
+                        self.cooldown_remaining_ms = self.cooldown_remaining_ms.saturating_sub(1000);
+                        if self.cooldown_remaining_ms == 0 {
+                            self.cooldown_timer = None;
+                        }
+                        if let Some(oncooldown) = &ctx.props().oncooldown {
+                            oncooldown.emit(self.cooldown_remaining_ms);
+                        }
+                        true
+                    },
+                    // Failures which would otherwise panic are shown in a banner and passed on
+                    #component_msg_ident::InternalError(error) => {
+                        if let Some(onerror) = &ctx.props().onerror {
+                            onerror.emit(error.clone());
+                        }
+                        self.internal_error = Some(error);
+                        true
+                    },
+                    // Input events keep coming while text is composed through an IME, before the
+                    // text is picked, so nothing is submitted until the composition ends
+                    #component_msg_ident::CompositionStart(event) => {
+                        self.composing = true;
+                        self.auto_submit_timer = None;
+                        if let Some(oncompositionstart) = &ctx.props().oncompositionstart {
+                            oncompositionstart.emit(event);
+                        }
+                        false
+                    },
+                    #component_msg_ident::CompositionEnd(event) => {
+                        self.composing = false;
+                        #composition_commit
+                        if let Some(oncompositionend) = &ctx.props().oncompositionend {
+                            oncompositionend.emit(event);
+                        }
+                        if ctx.props().auto_submit_after_ms.is_some() {
+                            ctx.link().send_message(#component_msg_ident::ScheduleAutoSubmit);
+                        }
+                        false
+                    },
+                    // Autofilled values are stored straight away, the way a change would store them,
+                    // so required fields the browser filled in don't wait for the user to visit them
+                    #component_msg_ident::Autofill(event) => {
+                        let autofilled = match event.dyn_ref::<web_sys::AnimationEvent>() {
+                            Some(event) => event.animation_name() == "formula-y-autofill",
+                            // Typing and pasting send InputEvents, while Firefox sends a plain
+                            // Event for the inputs it fills in
+                            None => !event.is_instance_of::<web_sys::InputEvent>(),
+                        };
+                        let target = match event.target() {
+                            Some(target) if autofilled => target,
+                            _ => return false,
+                        };
+                        if let Err(error) = Self::dispatch_change(&target) {
+                            ctx.link().send_message(#component_msg_ident::InternalError(error));
+                            return false;
+                        }
+                        if let (Some(field), Some(onautofill)) = (self.event_field(&event), &ctx.props().onautofill) {
+                            onautofill.emit(field);
+                        }
+                        false
+                    },
+                    #component_msg_ident::Unlock => {
+                        self.locked = false;
+                        if let Some(onunlock) = &ctx.props().onunlock {
+                            onunlock.emit(());
+                        }
+                        true
+                    },
+                    #component_msg_ident::PageHide => {
+                        self.report_abandon(ctx);
+                        false
+                    },
+                    // Each field is timed from when it is first focused, only while someone is
+                    // listening
+                    #component_msg_ident::FieldFocus(event) => {
+                        if ctx.props().ontelemetry.is_some() {
+                            if let Some(field) = self.event_field(&event) {
+                                self.completion_timer.focus(field);
+                            }
+                        }
+                        false
+                    },
+                    #action_update_arm
+                    // The warnings are shown before counting the errors to announce
+                    #component_msg_ident::DisplayRequiredWarnings => {
+                        self.apply_msg(#component_msg_ident::DisplayRequiredWarnings);
+                        self.announce_errors(ctx);
+                        true
+                    }
+                    #throttle_arms
+                    msg => {
+                        let before = self.audit_snapshot(Self::audits(ctx));
+                        let rerender = self.apply_msg(msg);
+                        let changed = std::mem::take(&mut self.value_changed);
+                        if changed {
+                            if let Some(onchange) = &ctx.props().onchange {
+                                onchange.emit(self.current_value());
+                            }
+                            self.emit_edits(ctx, before);
+                            self.emit_completed_fields(ctx);
+                        }
+                        #nested_notify
+                        rerender
+                    }
+                }
+            }
+
+            // A controlled form shows whatever its value prop holds
+            fn changed(&mut self, ctx: &Context<Self>) -> bool {
+                #nested_sync
+                let loading = Self::loading_fields(ctx);
+                if loading != self.loading {
+                    let loaded = self.loading.difference(&loading).copied().collect::<Vec<_>>();
+                    self.loading = loading;
+                    #load_fields
+                }
+                #merge_init
+                if ctx.props().reset_count != self.reset_count {
+                    self.reset_count = ctx.props().reset_count;
+                    self.reset();
+                }
+                if ctx.props().failed_submits != self.failed_submits {
+                    let failures = ctx.props().failed_submits.unwrap_or_default();
+                    let increased = failures > self.failed_submits.unwrap_or_default();
+                    self.failed_submits = ctx.props().failed_submits;
+                    if failures == 0 {
+                        self.cooldown_remaining_ms = 0;
+                        self.cooldown_timer = None;
+                    } else if increased {
+                        self.start_cooldown(ctx, failures);
+                    }
+                }
+                if ctx.props().deadline != self.deadline {
+                    self.start_deadline(ctx);
+                }
+                if ctx.props().submitted_reset_count != self.submitted_reset_count {
+                    self.submitted_reset_count = ctx.props().submitted_reset_count;
+                    self.submitted = false;
+                }
+                if let Some(value) = &ctx.props().value {
+                    self.set_value(value.clone());
+                }
+                // Every other prop is read as it is used, so takes effect on the next render. These
+                // are the ones the form keeps state for.
+                if let Some(id_prefix) = &ctx.props().id_prefix {
+                    if *id_prefix != self.id_prefix {
+                        self.id_prefix = id_prefix.clone();
+                        // The document listeners pick out the form by its id, so are added again
+                        self.document_listeners.clear();
+                    }
+                }
+                let disabled = ctx.props().disabled.unwrap_or_default();
+                if disabled || ctx.props().auto_submit_after_ms.is_none() {
+                    self.auto_submit_timer = None;
+                }
+                if disabled {
+                    self.inline_editing = None;
+                }
+                if ctx.props().enforce_required_fields == Some(RequiredPolicy::Ignore) {
+                    self.display_required_warnings = false;
+                }
+                true
+            }
+
+            fn view(&self, ctx: &Context<Self>) -> Html {
+
+                let link = ctx.link();
+                let theme = self.theme(ctx);
+                let keyboard_navigation = ctx
+                    .props()
+                    .keyboard_navigation
+                    .unwrap_or_default()
+                    .then(|| #keyboard_navigation_callback);
+                #oninput
+                let submit_disabled = ctx.props().disabled.unwrap_or_default()
+                    || ctx.props().submit_disabled.unwrap_or_default()
+                    || ctx.props().loading.unwrap_or_default()
+                    || self.cooldown_remaining_ms > 0
+                    || self.expired;
+
+                html! {
+                    <>
+                    #form_view
+                    #debug_panel
+                    </>
+                }
+            }
+
+            fn destroy(&mut self, ctx: &Context<Self>) {
+                self.report_abandon(ctx);
+                // The parent can keep what the form held, i.e. when switching to another tab
+                if let Some(onsnapshot) = &ctx.props().onsnapshot {
+                    onsnapshot.emit(self.snapshot());
+                }
+            }
+
+            fn rendered(&mut self, ctx: &Context<Self>, #first_render_param: bool) {
+                // yew can't listen for compositions, so the document is listened to instead, and
+                // compositions in the form's inputs are passed on. The listeners are added again
+                // whenever the form's id changes.
+                if self.document_listeners.is_empty() {
+                    if let Some(document) = web_sys::window().and_then(|window| window.document()) {
+                        let listen = |event_type: &'static str, msg: fn(web_sys::Event) -> #component_msg_ident| {
+                            let link = ctx.link().clone();
+                            let id_prefix = self.id_prefix.clone();
+                            gloo_events::EventListener::new(&document, event_type, move |event| {
+                                let form = web_sys::window()
+                                    .and_then(|window| window.document())
+                                    .and_then(|document| document.get_element_by_id(&id_prefix));
+                                let target = event.target().and_then(|target| target.dyn_into::<web_sys::Node>().ok());
+                                if let (Some(form), Some(target)) = (form, target) {
+                                    if form.contains(Some(&target)) {
+                                        link.send_message(msg(event.clone()));
+                                    }
+                                }
+                            })
+                        };
+                        self.document_listeners = vec![
+                            listen("compositionstart", |event| #component_msg_ident::CompositionStart(event.unchecked_into())),
+                            listen("compositionend", |event| #component_msg_ident::CompositionEnd(event.unchecked_into())),
+                            listen("animationstart", #component_msg_ident::Autofill),
+                            listen("input", #component_msg_ident::Autofill),
+                            listen("focusin", #component_msg_ident::FieldFocus),
+                        ];
+                        // A page being left hides it, which is the last chance to report leaving the
+                        // form unsubmitted
+                        if let Some(window) = web_sys::window() {
+                            let link = ctx.link().clone();
+                            self.document_listeners.push(gloo_events::EventListener::new(&window, "pagehide", move |_| {
+                                link.send_message(#component_msg_ident::PageHide);
+                            }));
+                        }
+                        #strict_numeric_listener
+                    }
+                }
+                // Inputs swapped in by a rerender, i.e. when returning from the review step or
+                // editing a field inline, are focused once they are in the page
+                if let Some(field) = self.focus_on_render.take() {
+                    let input = web_sys::window()
+                        .and_then(|window| window.document())
+                        .and_then(|document| document.get_element_by_id(&self.field_id(field)));
+                    if let Some(input) = input {
+                        let _ = input.unchecked_into::<web_sys::HtmlElement>().focus();
+                    }
+                }
+                #step_rendered
+                #nested_first_render
+            }
+        }
+    }
+    .into()
+}
@@ -0,0 +1,52 @@
+use convert_case::{Case, Casing};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{punctuated::Punctuated, token::Comma, Field, Ident};
+
+use crate::attrs::StructAttrs;
+use crate::testing::{sample_options, sample_value};
+use crate::util::append_to_ident;
+use crate::{get_state_type, wrap_state};
+
+/// Generate a <Name>FormPreview function component which mounts the form filled in with sample
+/// values and logs whatever is submitted, for building catalog pages of every form in an app.
+pub fn preview(
+    input_struct_ident: &Ident,
+    component_ident: &Ident,
+    fields: &Punctuated<Field, Comma>,
+    unsupported_inits: &[TokenStream],
+    struct_attrs: &StructAttrs,
+) -> TokenStream {
+    let state_type = get_state_type(input_struct_ident, struct_attrs);
+    let preview_ident = append_to_ident(component_ident, "Preview"); // Data -> DataFormPreview
+    let preview_fn_ident = Ident::new(
+        &format!("{}", preview_ident).to_case(Case::Snake),
+        input_struct_ident.span(),
+    );
+    let title = format!("{}", component_ident);
+
+    let sample_options = sample_options(fields);
+
+    let init = wrap_state(
+        sample_value(input_struct_ident, fields, unsupported_inits),
+        struct_attrs,
+    );
+
+    quote! {
+        #[function_component(#preview_ident)]
+        pub fn #preview_fn_ident() -> Html {
+            let onsubmit = Callback::from(|data: #state_type| {
+                web_sys::console::log_1(&format!("{} submitted: {:?}", #title, data).into());
+            });
+
+            let init = #init;
+
+            html! {
+                <div class="formula-y-preview">
+                    <h2 class="formula-y-preview-title">{#title}</h2>
+                    <#component_ident {onsubmit} {init} #(#sample_options)* />
+                </div>
+            }
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use convert_case::{Case, Casing};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{punctuated::Punctuated, token::Comma, Field, Ident};
+
+use crate::util::{
+    field_is_bool, field_is_date, field_is_enum, field_is_nested, field_is_nested_vec,
+    field_is_number, field_is_option_bool, field_is_option_date, field_is_option_enum,
+    field_is_option_number, field_is_option_string, field_is_string, field_is_string_vec,
+    field_name,
+};
+use crate::{get_field_variant_ident, get_label};
+
+/// The expression showing a field's value as text, given the expression for the value itself
+pub fn display_value(field: &Field, value: TokenStream) -> TokenStream {
+    if field_is_string(field) {
+        quote! { #value.clone() }
+    } else if field_is_option_string(field) {
+        quote! { #value.clone().unwrap_or_default() }
+    } else if field_is_bool(field) {
+        quote! { if #value { "Yes" } else { "No" } }
+    } else if field_is_option_bool(field) {
+        quote! { if #value.unwrap_or_default() { "Yes" } else { "No" } }
+    } else if field_is_number(field) || field_is_date(field) {
+        quote! { #value.to_string() }
+    } else if field_is_option_number(field) || field_is_option_date(field) {
+        quote! { #value.map(|value| value.to_string()).unwrap_or_default() }
+    } else if field_is_enum(field) {
+        quote! { #value.label() }
+    } else if field_is_option_enum(field) {
+        quote! { #value.as_ref().map_or("", YFormSelect::label) }
+    } else if field_is_nested(field) {
+        // Nested structs are shown as their own summary
+        quote! { #value.summary() }
+    } else if field_is_string_vec(field) {
+        quote! { #value.join(", ") }
+    } else if field_is_nested_vec(field) {
+        quote! { #value.iter().map(YFormNested::summary).collect::<Html>() }
+    } else {
+        quote! { "type not supported" }
+    }
+}
+
+/// Generate the read only summary shown by forms with #[yform(review)] once their values pass
+/// the required field checks. Each value has an Edit button leading back to its input, and
+/// Confirm submits the form for real.
+pub fn review_view(
+    input_struct_ident: &Ident,
+    field_enum_ident: &Ident,
+    component_msg_ident: &Ident,
+    fields: &Punctuated<Field, Comma>,
+) -> TokenStream {
+    let review_class = format!(
+        "{}-review formula-y-review",
+        format!("{}", input_struct_ident).to_case(Case::Kebab)
+    );
+
+    let review_items = fields.iter().map(|field| {
+        let field_ident = field.ident.clone().unwrap();
+        let label = get_label(field);
+        let field_id_suffix = field_name(&field_ident).to_case(Case::Kebab);
+
+        let value = display_value(field, quote! { self.inner().#field_ident });
+
+        // Hidden fields are left out of the summary too, and read only ones can't be edited
+        let field_variant = get_field_variant_ident(field);
+        quote! {
+            {{
+                let access = self.field_access(ctx, #field_enum_ident::#field_variant);
+                html! {
+                    if access != FieldAccess::Hidden {
+                        <dt class="formula-y-review-label">{self.label_for(ctx, #field_enum_ident::#field_variant, #label)}</dt>
+                        <dd class="formula-y-review-value">
+                            {#value}
+                            if access == FieldAccess::Editable {
+                                <button type="button" class="formula-y-review-edit" onclick={link.callback(|_| #component_msg_ident::EditField(#field_id_suffix))}>{"Edit"}</button>
+                            }
+                        </dd>
+                    }
+                }
+            }}
+        }
+    });
+
+    quote! {
+        <div id={self.id_prefix.clone()} class={#review_class}>
+            <dl>
+                #(#review_items)*
+            </dl>
+            <button type="button" class="formula-y-review-confirm" onclick={link.callback(|_| #component_msg_ident::OnSubmit)}>{"Confirm"}</button>
+        </div>
+    }
+}
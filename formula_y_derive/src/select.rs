@@ -0,0 +1,85 @@
+use convert_case::{Case, Casing};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, Fields};
+
+use crate::attrs::VariantAttrs;
+use crate::util::field_name;
+
+/// Implement `YFormSelect` for a fieldless enum, so that forms can offer its variants as choices.
+/// Each variant's value is its name, and its label the Title Cased name unless it has a
+/// `#[yform(label = "...")]`.
+pub fn derive_select(ast: &DeriveInput) -> syn::Result<TokenStream> {
+    let enum_ident = &ast.ident;
+    let variants = match &ast.data {
+        syn::Data::Enum(data) => &data.variants,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                enum_ident,
+                "YFormSelect can only be derived for enums",
+            ))
+        }
+    };
+    if variants.is_empty() {
+        return Err(syn::Error::new_spanned(
+            enum_ident,
+            "YFormSelect needs at least one variant to choose from",
+        ));
+    }
+
+    // Report every variant with fields or malformed options at once
+    let variant_errors = variants
+        .iter()
+        .filter_map(|variant| {
+            if matches!(variant.fields, Fields::Unit) {
+                VariantAttrs::parse(variant).err()
+            } else {
+                Some(syn::Error::new_spanned(
+                    variant,
+                    "YFormSelect variants can't hold fields, since there is no input to fill them in",
+                ))
+            }
+        })
+        .reduce(|mut errors, error| {
+            errors.combine(error);
+            errors
+        });
+    if let Some(errors) = variant_errors {
+        return Err(errors);
+    }
+
+    let values = variants
+        .iter()
+        .map(|variant| field_name(&variant.ident))
+        .collect::<Vec<_>>();
+    let labels = variants.iter().zip(&values).map(|(variant, value)| {
+        VariantAttrs::parse(variant)
+            .expect("variant attributes are validated before expansion")
+            .label
+            .unwrap_or_else(|| value.to_case(Case::Title))
+    });
+    let variant_idents = variants
+        .iter()
+        .map(|variant| &variant.ident)
+        .collect::<Vec<_>>();
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics YFormSelect for #enum_ident #ty_generics #where_clause {
+            const VALUES: &'static [&'static str] = &[#(#values),*];
+            const LABELS: &'static [&'static str] = &[#(#labels),*];
+
+            fn value(&self) -> &'static str {
+                match self {
+                    #(#enum_ident::#variant_idents => #values,)*
+                }
+            }
+
+            fn from_value(value: &str) -> Option<Self> {
+                match value {
+                    #(#values => Some(#enum_ident::#variant_idents),)*
+                    _ => None,
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,773 @@
+use convert_case::{Case, Casing};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{punctuated::Punctuated, token::Comma, Field, Ident};
+
+use crate::attrs::{FieldAttrs, StructAttrs};
+use crate::util::{
+    append_to_ident, enum_type, field_is_bool, field_is_date, field_is_datetime, field_is_enum,
+    field_is_nested, field_is_nested_vec, field_is_number, field_is_option_bool,
+    field_is_option_date, field_is_option_enum, field_is_option_number, field_is_option_string,
+    field_is_string, field_is_string_vec, field_is_vec, field_name, item_type,
+};
+use crate::{
+    field_is_parsed, get_field_variant_ident, get_inner_ref_type, get_msg_method_ident,
+    get_options_prop_ident, get_parse, get_state_type, get_steps,
+    get_update_field_msg_variant_ident, get_update_item_msg_variant_ident, wrap_state,
+};
+
+/// The sample value entered into number inputs, which parses as any number type
+pub const SAMPLE_NUMBER: &str = "42";
+
+/// The sample text entered into number inputs, or date and datetime-local inputs, in the format the
+/// browser gives their values in
+pub fn sample_parsed_text(field: &Field) -> &'static str {
+    if field_is_datetime(field) {
+        "2024-01-31T09:30"
+    } else if field_is_date(field) || field_is_option_date(field) {
+        "2024-01-31"
+    } else {
+        SAMPLE_NUMBER
+    }
+}
+
+/// The sample text entered into text inputs, i.e. email -> "Sample Email". A field's placeholder
+/// is used instead where it has one, so fields with a regex can be given an example which matches.
+/// Fields with choices take the last of them, as enums take their last variant.
+pub fn sample_text(field: &Field) -> String {
+    let attrs = FieldAttrs::from_field(field);
+    if let Some(choice) = attrs.choices.last() {
+        return choice.clone();
+    }
+    attrs.placeholder.unwrap_or_else(|| {
+        format!("Sample {}", field_name(field.ident.as_ref().unwrap())).to_case(Case::Title)
+    })
+}
+
+/// A value of the struct with every field filled in with its sample, i.e. for previews. Fields
+/// left out of the form by skip_unsupported are given their `unsupported_inits`.
+pub fn sample_value(
+    input_struct_ident: &Ident,
+    fields: &Punctuated<Field, Comma>,
+    unsupported_inits: &[TokenStream],
+) -> TokenStream {
+    let sample_inits = fields.iter().map(|field| {
+        let field_ident = field.ident.clone().unwrap();
+        let sample = sample_text(field);
+        let sample_date = sample_parsed_text(field);
+
+        if field_is_string(field) {
+            quote! { #field_ident: #sample.to_string() }
+        } else if field_is_option_string(field) {
+            quote! { #field_ident: Some(#sample.to_string()) }
+        } else if field_is_bool(field) {
+            quote! { #field_ident: true }
+        } else if field_is_option_bool(field) {
+            quote! { #field_ident: Some(true) }
+        } else if field_is_number(field) {
+            quote! { #field_ident: #SAMPLE_NUMBER.parse().unwrap() }
+        } else if field_is_option_number(field) {
+            quote! { #field_ident: #SAMPLE_NUMBER.parse().ok() }
+        } else if field_is_date(field) {
+            let parse = get_parse(field, quote! { #sample_date });
+            quote! { #field_ident: #parse.unwrap() }
+        } else if field_is_option_date(field) {
+            let parse = get_parse(field, quote! { #sample_date });
+            quote! { #field_ident: #parse.ok() }
+        } else if field_is_nested(field) {
+            let field_type = &field.ty;
+            quote! { #field_ident: <#field_type as YFormNested>::sample() }
+        } else if field_is_string_vec(field) {
+            quote! { #field_ident: vec![#sample.to_string()] }
+        } else if field_is_nested_vec(field) {
+            let item_type = item_type(field);
+            quote! { #field_ident: vec![<#item_type as YFormNested>::sample()] }
+        } else if field_is_option_enum(field) {
+            let enum_type = enum_type(field);
+            quote! { #field_ident: Some(<#enum_type as YFormSelect>::first()) }
+        } else {
+            let enum_type = enum_type(field);
+            quote! { #field_ident: <#enum_type as YFormSelect>::first() }
+        }
+    });
+    quote! {
+        #input_struct_ident {
+            #(#sample_inits,)*
+            #(#unsupported_inits,)*
+        }
+    }
+}
+
+/// Props offering each field with #[yform(options_prop)] its sample value as the only choice
+pub fn sample_options(fields: &Punctuated<Field, Comma>) -> Vec<TokenStream> {
+    fields
+        .iter()
+        .filter_map(|field| {
+            let attrs = FieldAttrs::from_field(field);
+            // The prop is named from here rather than the field, so lints on it aren't blamed on
+            // the user's struct
+            let options_ident = Ident::new(
+                &get_options_prop_ident(field).to_string(),
+                proc_macro2::Span::call_site(),
+            );
+            let sample = sample_text(field);
+            if attrs.grouped {
+                Some(quote! {
+                    #options_ident={vec![("Sample".to_string(), #sample.to_string(), #sample.to_string())]}
+                })
+            } else if attrs.options_prop {
+                Some(quote! { #options_ident={vec![(#sample.to_string(), #sample.to_string())]} })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Generate a <Name>FormTester which drives the component through its msgs the same way
+/// the inputs do, collecting everything passed to onsubmit.
+pub fn tester(
+    input_struct_ident: &Ident,
+    component_ident: &Ident,
+    component_msg_ident: &Ident,
+    fields: &Punctuated<Field, Comma>,
+    struct_attrs: &StructAttrs,
+) -> TokenStream {
+    let state_type = get_state_type(input_struct_ident, struct_attrs);
+    let init_state = wrap_state(quote! { init }, struct_attrs);
+    let inner_ref_type = get_inner_ref_type(input_struct_ident, struct_attrs);
+
+    let tester_ident = append_to_ident(component_ident, "Tester"); // Data -> DataFormTester
+    let snapshot_ident = append_to_ident(component_ident, "State"); // Data -> DataFormState
+    let save_individually = struct_attrs.save_individually;
+    let field_enum_ident = append_to_ident(input_struct_ident, "Field"); // Data -> DataField
+
+    // Named actions are submitted the same way as the form, but collected separately with the
+    // action they were chosen with
+    let action_ident = append_to_ident(component_ident, "Action");
+    let (actions_field, actions_init, action_arm, action_methods) =
+        if struct_attrs.actions.is_empty() {
+            (quote! {}, quote! {}, quote! {}, quote! {})
+        } else {
+            (
+                quote! { actions: Vec<(#action_ident, #state_type)>, },
+                quote! { actions: Vec::new(), },
+                quote! {
+                    #component_msg_ident::OnAction(action) => {
+                        if self.form.try_submit(self.enforce_required_fields) {
+                            self.actions.push((action, self.form.submit_value()));
+                        } else {
+                            self.form.apply_msg(#component_msg_ident::DisplayRequiredWarnings);
+                        }
+                    }
+                },
+                quote! {
+                    pub fn act(self, action: #action_ident) -> Self {
+                        self.send(#component_msg_ident::OnAction(action))
+                    }
+
+                    /// Every action and value passed to onaction so far, oldest first
+                    pub fn emitted_actions(&self) -> &[(#action_ident, #state_type)] {
+                        &self.actions
+                    }
+                },
+            )
+        };
+
+    let tester_methods = fields.iter().map(|field| {
+        let field_ident = field.ident.clone().unwrap();
+        let msg_method_ident = get_msg_method_ident(field, input_struct_ident);
+
+        if field_is_string(field) || field_is_option_string(field) || field_is_parsed(field) {
+            let set_ident = syn::Ident::new(
+                &format!("set_{}", field_name(&field_ident)),
+                input_struct_ident.span(),
+            );
+            quote! {
+                pub fn #set_ident(self, value: &str) -> Self {
+                    self.send(#component_ident::#msg_method_ident(value.to_string()))
+                }
+            }
+        } else if field_is_bool(field) || field_is_option_bool(field) {
+            let check_ident = syn::Ident::new(
+                &format!("check_{}", field_name(&field_ident)),
+                input_struct_ident.span(),
+            );
+            let uncheck_ident = syn::Ident::new(
+                &format!("uncheck_{}", field_name(&field_ident)),
+                input_struct_ident.span(),
+            );
+            quote! {
+                pub fn #check_ident(self) -> Self {
+                    self.send(#component_ident::#msg_method_ident(true))
+                }
+
+                pub fn #uncheck_ident(self) -> Self {
+                    self.send(#component_ident::#msg_method_ident(false))
+                }
+            }
+        } else if field_is_string_vec(field) || field_is_nested_vec(field) {
+            // Items are set by their index, once added with add_item
+            let set_ident = syn::Ident::new(
+                &format!("set_{}_item", field_name(&field_ident)),
+                input_struct_ident.span(),
+            );
+            let item_msg_variant_ident =
+                get_update_item_msg_variant_ident(field, input_struct_ident);
+            if field_is_string_vec(field) {
+                quote! {
+                    pub fn #set_ident(self, index: usize, value: &str) -> Self {
+                        self.send(#component_msg_ident::#item_msg_variant_ident(index, value.to_string()))
+                    }
+                }
+            } else {
+                let item_type = item_type(field);
+                quote! {
+                    pub fn #set_ident(self, index: usize, value: #item_type) -> Self {
+                        self.send(#component_msg_ident::#item_msg_variant_ident(index, value))
+                    }
+                }
+            }
+        } else if field_is_enum(field) || field_is_option_enum(field) || field_is_nested(field) {
+            // Variants and nested structs are set directly, as there is no text to convert
+            let set_ident = syn::Ident::new(
+                &format!("set_{}", field_name(&field_ident)),
+                input_struct_ident.span(),
+            );
+            let field_type = &field.ty;
+            let msg_variant_ident = get_update_field_msg_variant_ident(field, input_struct_ident);
+            quote! {
+                pub fn #set_ident(self, value: #field_type) -> Self {
+                    self.send(#component_msg_ident::#msg_variant_ident(value))
+                }
+            }
+        } else {
+            quote! {}
+        }
+    });
+
+    let item_methods = if fields.iter().any(field_is_vec) {
+        quote! {
+            /// Click the Add button of a Vec field, appending an empty item
+            pub fn add_item(self, field: #field_enum_ident) -> Self {
+                self.send(#component_msg_ident::AddItem(field))
+            }
+
+            /// Click the Remove button of the item at an index of a Vec field
+            pub fn remove_item(self, field: #field_enum_ident, index: usize) -> Self {
+                self.send(#component_msg_ident::RemoveItem(field, index))
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Forms with a review step need submitting twice, once to reach the summary and once more to
+    // confirm it
+    let review_methods = if struct_attrs.review {
+        quote! {
+            pub fn is_reviewing(&self) -> bool {
+                self.form.reviewing
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Multi-step forms need submitting once per step, with each submit moving on a step
+    let step_methods = if get_steps(fields).is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            /// Click the Back button of a multi-step form
+            pub fn previous_step(self) -> Self {
+                self.send(#component_msg_ident::PreviousStep)
+            }
+
+            /// The step of a multi-step form being shown, counting from 0
+            pub fn current_step(&self) -> usize {
+                self.form.current_step
+            }
+        }
+    };
+
+    quote! {
+        pub struct #tester_ident {
+            form: #component_ident,
+            enforce_required_fields: RequiredPolicy,
+            audit_trail: bool,
+            lock_on_submit: bool,
+            emitted: Vec<#state_type>,
+            #actions_field
+            field_changes: Vec<(#field_enum_ident, #state_type)>,
+            changes: Vec<#state_type>,
+            drafts: Vec<#state_type>
+        }
+
+        impl #tester_ident {
+            pub fn new() -> Self {
+                Self::with_init(#input_struct_ident::new())
+            }
+
+            pub fn with_init(init: #input_struct_ident) -> Self {
+                Self {
+                    form: #component_ident::from_inner(#init_state),
+                    enforce_required_fields: RequiredPolicy::Enforce,
+                    audit_trail: false,
+                    lock_on_submit: false,
+                    emitted: Vec::new(),
+                    #actions_init
+                    field_changes: Vec::new(),
+                    changes: Vec::new(),
+                    drafts: Vec::new()
+                }
+            }
+
+            pub fn enforce_required_fields(mut self, enforce_required_fields: RequiredPolicy) -> Self {
+                self.enforce_required_fields = enforce_required_fields;
+                self
+            }
+
+            /// Record changes to the fields, as the form's audit_trail prop does
+            pub fn audit_trail(mut self, audit_trail: bool) -> Self {
+                self.audit_trail = audit_trail;
+                self
+            }
+
+            /// Make the fields read only once submitted, as the form's lock_on_submit prop does
+            pub fn lock_on_submit(mut self, lock_on_submit: bool) -> Self {
+                self.lock_on_submit = lock_on_submit;
+                self
+            }
+
+            pub fn send(mut self, msg: #component_msg_ident) -> Self {
+                match msg {
+                    #component_msg_ident::OnSubmit => {
+                        if self.form.locked
+                            || self.form.expired
+                            || self.form.submit_step(self.enforce_required_fields.blocks_submit())
+                        {
+                            return self;
+                        }
+                        if self.form.try_submit(self.enforce_required_fields) {
+                            if !self.form.hold_for_review() {
+                                self.emitted.push(self.form.submit_value());
+                                self.form.locked = self.lock_on_submit;
+                            }
+                        } else {
+                            self.form.apply_msg(#component_msg_ident::DisplayRequiredWarnings);
+                        }
+                    }
+                    #action_arm
+                    #component_msg_ident::ConfirmInline(field) => {
+                        let enforce_required = #save_individually && self.enforce_required_fields.blocks_submit();
+                        let before = self.form.audit_snapshot(self.audit_trail);
+                        if self.form.confirm_inline(field, enforce_required) == Some(true) {
+                            self.form.record_edits(before);
+                            self.field_changes.push((field, self.form.current_value()));
+                        }
+                    }
+                    #component_msg_ident::OnSaveDraft => {
+                        self.drafts.push(self.form.current_value());
+                    }
+                    #component_msg_ident::Unlock => {
+                        self.form.locked = false;
+                    }
+                    msg => {
+                        let before = self.form.audit_snapshot(self.audit_trail);
+                        self.form.apply_msg(msg);
+                        if std::mem::take(&mut self.form.value_changed) {
+                            self.form.record_edits(before);
+                            self.changes.push(self.form.current_value());
+                        }
+                    }
+                }
+                self
+            }
+
+            pub fn submit(self) -> Self {
+                self.send(#component_msg_ident::OnSubmit)
+            }
+
+            pub fn save_draft(self) -> Self {
+                self.send(#component_msg_ident::OnSaveDraft)
+            }
+
+            /// Click the Reset button, putting the form back to its initial value
+            pub fn reset(self) -> Self {
+                self.send(#component_msg_ident::Reset)
+            }
+
+            #(#tester_methods)*
+
+            #item_methods
+
+            /// Focus a field's input, snapshotting its value for escape to revert to
+            pub fn focus(self, field: #field_enum_ident) -> Self {
+                self.send(#component_msg_ident::FocusField(field))
+            }
+
+            /// Press Escape in a field's input, reverting it to its value when it was focused
+            pub fn escape(self, field: #field_enum_ident) -> Self {
+                self.send(#component_msg_ident::RevertField(field))
+            }
+
+            /// Click a field's edit button in inline mode
+            pub fn edit(self, field: #field_enum_ident) -> Self {
+                self.send(#component_msg_ident::EditInline(field))
+            }
+
+            /// Confirm an inline edit, as leaving the input or pressing Enter does
+            pub fn confirm(self, field: #field_enum_ident) -> Self {
+                self.send(#component_msg_ident::ConfirmInline(field))
+            }
+
+            /// Click a masked field's Change button, clearing it for a new value
+            pub fn unmask(self, field: #field_enum_ident) -> Self {
+                self.send(#component_msg_ident::UnmaskField(field))
+            }
+
+            /// Click the Edit button of a form locked by its submit
+            pub fn unlock(self) -> Self {
+                self.send(#component_msg_ident::Unlock)
+            }
+
+            pub fn is_locked(&self) -> bool {
+                self.form.is_locked()
+            }
+
+            /// Run the form out of time, as if its deadline prop had passed
+            pub fn expire(mut self) -> Self {
+                self.form.expired = true;
+                self
+            }
+
+            pub fn is_expired(&self) -> bool {
+                self.form.is_expired()
+            }
+
+            /// What the form holds, as it would be passed to onsnapshot
+            pub fn snapshot(&self) -> #snapshot_ident {
+                self.form.snapshot()
+            }
+
+            /// Pick up where a snapshot left off, as the snapshot prop does
+            pub fn restore(mut self, state: #snapshot_ident) -> Self {
+                self.form.restore(state);
+                self
+            }
+
+            /// Whether a field is being edited inline
+            pub fn is_editing(&self, field: #field_enum_ident) -> bool {
+                self.form.inline_editing == Some(field)
+            }
+
+            /// Every value passed to onchange so far, oldest first
+            pub fn changes(&self) -> &[#state_type] {
+                &self.changes
+            }
+
+            /// Every field and value passed to onfieldchange so far, oldest first. Forms which save
+            /// fields individually pass the same to onfieldsave.
+            pub fn field_changes(&self) -> &[(#field_enum_ident, #state_type)] {
+                &self.field_changes
+            }
+
+            /// Every change recorded with audit_trail on, oldest first
+            pub fn edits(&self) -> &[FieldEdit<#field_enum_ident>] {
+                self.form.edits()
+            }
+
+            /// The current value of the form
+            pub fn inner(&self) -> #inner_ref_type {
+                self.form.inner()
+            }
+
+            /// Every value passed to onsubmit so far, oldest first
+            pub fn emitted(&self) -> &[#state_type] {
+                &self.emitted
+            }
+
+            #action_methods
+
+            #review_methods
+
+            #step_methods
+
+            /// Every value passed to onsavedraft so far, oldest first
+            pub fn drafts(&self) -> &[#state_type] {
+                &self.drafts
+            }
+
+            pub fn displays_required_warnings(&self) -> bool {
+                self.form.display_required_warnings
+            }
+        }
+    }
+}
+
+/// Generate a wasm-bindgen-test module which mounts the form, fills in every input with a sample
+/// value through the DOM, submits it, and checks the struct passed to onsubmit.
+pub fn wasm_tests(
+    input_struct_ident: &Ident,
+    component_ident: &Ident,
+    fields: &Punctuated<Field, Comma>,
+    struct_attrs: &StructAttrs,
+) -> TokenStream {
+    let state_type = get_state_type(input_struct_ident, struct_attrs);
+    let component_msg_ident = append_to_ident(component_ident, "Msg"); // Data -> DataFormMsg
+    let field_enum_ident = append_to_ident(input_struct_ident, "Field"); // Data -> DataField
+    let module_ident = Ident::new(
+        &format!("{}_wasm_tests", component_ident).to_case(Case::Snake),
+        input_struct_ident.span(),
+    );
+
+    let fill_inputs = fields.iter().map(|field| {
+        let field_ident = field.ident.clone().unwrap();
+        let msg_method_ident = get_msg_method_ident(field, input_struct_ident);
+        let selector = format!(
+            ".{}",
+            format!("{}-input", field_name(&field_ident)).to_case(Case::Kebab)
+        );
+        let attrs = FieldAttrs::from_field(field);
+        // Skipped, hidden and custom fields have no input of ours to fill, and keep their initial
+        // values
+        if attrs.skip || attrs.hidden || attrs.custom.is_some() {
+            return quote! {};
+        }
+
+        if field_is_string(field) || field_is_option_string(field) {
+            let sample = sample_text(field);
+            let element_type = if attrs.options_prop || !attrs.choices.is_empty() {
+                quote! { HtmlSelectElement }
+            } else if attrs.is_textarea() {
+                quote! { HtmlTextAreaElement }
+            } else {
+                quote! { HtmlInputElement }
+            };
+            let choose = if attrs.radio {
+                let radio_selector = format!("{} input[value=\"{}\"]", selector, sample);
+                quote! {
+                    let input = container.query_selector(#radio_selector).unwrap().unwrap();
+                    input.unchecked_ref::<HtmlInputElement>().set_checked(true);
+                }
+            } else {
+                quote! {
+                    let input = container.query_selector(#selector).unwrap().unwrap();
+                    input.unchecked_ref::<#element_type>().set_value(#sample);
+                }
+            };
+            let event = if attrs.options_prop || !attrs.choices.is_empty() {
+                struct_attrs.widget_event(field, &attrs).name()
+            } else {
+                "change"
+            };
+            quote! {
+                #choose
+                input.dispatch_event(&bubbling_event(#event)).unwrap();
+                expected.apply_msg(#component_ident::#msg_method_ident(#sample.to_string()));
+            }
+        } else if field_is_bool(field) || field_is_option_bool(field) {
+            let event = struct_attrs.widget_event(field, &attrs).name();
+            quote! {
+                let input = container.query_selector(#selector).unwrap().unwrap();
+                input.unchecked_ref::<HtmlInputElement>().set_checked(true);
+                input.dispatch_event(&bubbling_event(#event)).unwrap();
+                expected.apply_msg(#component_ident::#msg_method_ident(true));
+            }
+        } else if field_is_parsed(field) {
+            let sample = sample_parsed_text(field);
+            quote! {
+                let input = container.query_selector(#selector).unwrap().unwrap();
+                input.unchecked_ref::<HtmlInputElement>().set_value(#sample);
+                input.dispatch_event(&change_event()).unwrap();
+                expected.apply_msg(#component_ident::#msg_method_ident(#sample.to_string()));
+            }
+        } else if field_is_string_vec(field) {
+            // A single item is added and filled in
+            let sample = sample_text(field);
+            let field_variant = get_field_variant_ident(field);
+            let item_msg_variant_ident =
+                get_update_item_msg_variant_ident(field, input_struct_ident);
+            let add_selector = format!("{} .formula-y-add", selector);
+            let item_selector = format!("{} .formula-y-repeat-item input", selector);
+            quote! {
+                container
+                    .query_selector(#add_selector)
+                    .unwrap()
+                    .unwrap()
+                    .unchecked_ref::<web_sys::HtmlElement>()
+                    .click();
+                expected.apply_msg(#component_msg_ident::AddItem(#field_enum_ident::#field_variant));
+                let input = container.query_selector(#item_selector).unwrap().unwrap();
+                input.unchecked_ref::<HtmlInputElement>().set_value(#sample);
+                input.dispatch_event(&change_event()).unwrap();
+                expected.apply_msg(#component_msg_ident::#item_msg_variant_ident(0, #sample.to_string()));
+            }
+        } else if field_is_enum(field) || field_is_option_enum(field) {
+            // The last variant is chosen, so that it differs from the first which the form starts on
+            let enum_type = enum_type(field);
+            let choose = if attrs.radio {
+                quote! {
+                    let input = container
+                        .query_selector(&format!("{} input[value=\"{}\"]", #selector, value))
+                        .unwrap()
+                        .unwrap();
+                    input.unchecked_ref::<HtmlInputElement>().set_checked(true);
+                }
+            } else {
+                quote! {
+                    let input = container.query_selector(#selector).unwrap().unwrap();
+                    input.unchecked_ref::<HtmlSelectElement>().set_value(value);
+                }
+            };
+            let event = struct_attrs.widget_event(field, &attrs).name();
+            quote! {
+                let value = <#enum_type as YFormSelect>::VALUES.last().unwrap();
+                #choose
+                input.dispatch_event(&bubbling_event(#event)).unwrap();
+                expected.apply_msg(#component_ident::#msg_method_ident(value.to_string()).unwrap());
+            }
+        } else {
+            quote! {}
+        }
+    });
+
+    // Nested forms start out filled in with their sample, so that they don't block the submit
+    let nested_samples = fields
+        .iter()
+        .filter(|field| field_is_nested(field))
+        .map(|field| {
+            let field_ident = field.ident.clone().unwrap();
+            let field_type = &field.ty;
+            quote! { init.#field_ident = <#field_type as YFormNested>::sample(); }
+        })
+        .collect::<Vec<_>>();
+    let (new_state, harness_init) = if nested_samples.is_empty() {
+        (
+            wrap_state(quote! { #input_struct_ident::new() }, struct_attrs),
+            quote! {},
+        )
+    } else {
+        let init = wrap_state(
+            quote! {{
+                let mut init = #input_struct_ident::new();
+                #(#nested_samples)*
+                init
+            }},
+            struct_attrs,
+        );
+        (init.clone(), quote! { init={#init} })
+    };
+
+    // Fields are compared one at a time so the struct itself doesn't need to be PartialEq
+    let (submitted_inner, expected_inner) = if struct_attrs.shared_state {
+        (quote! { submitted.borrow() }, quote! { expected.borrow() })
+    } else {
+        (quote! { submitted }, quote! { expected })
+    };
+    // Nested fields are left as their sample value, which the nested form's own tests fill in
+    let field_asserts = fields
+        .iter()
+        .filter(|field| !field_is_nested(field) && !field_is_nested_vec(field))
+        .map(|field| {
+            let field_ident = field.ident.clone().unwrap();
+            let message = format!(
+                "unexpected value submitted for {}",
+                field_name(&field_ident)
+            );
+            quote! {
+                assert!(submitted.#field_ident == expected.#field_ident, #message);
+            }
+        });
+
+    // Selects only accept values among their options, so the sample values are offered as options
+    let sample_options = sample_options(fields);
+
+    let step_count = get_steps(fields).len().max(1);
+
+    let confirm_review = if struct_attrs.review {
+        quote! {
+            container
+                .query_selector(".formula-y-review-confirm")
+                .unwrap()
+                .expect("the review step was not shown")
+                .unchecked_ref::<web_sys::HtmlElement>()
+                .click();
+        }
+    } else {
+        quote! {}
+    };
+
+    quote! {
+        #[cfg(all(test, target_arch = "wasm32"))]
+        mod #module_ident {
+            use super::*;
+            use std::cell::RefCell;
+            use std::rc::Rc;
+            use wasm_bindgen_test::*;
+
+            wasm_bindgen_test_configure!(run_in_browser);
+
+            // yew listens for events at the root of the app, so they have to bubble. A plain event
+            // named click doesn't toggle a checkbox the way a MouseEvent would.
+            fn bubbling_event(event_type: &str) -> web_sys::Event {
+                let init = web_sys::EventInit::new();
+                init.set_bubbles(true);
+                web_sys::Event::new_with_event_init_dict(event_type, &init).unwrap()
+            }
+
+            fn change_event() -> web_sys::Event {
+                bubbling_event("change")
+            }
+
+            #[derive(PartialEq, Properties)]
+            struct HarnessProps {
+                onsubmit: Callback<#state_type>
+            }
+
+            #[function_component(Harness)]
+            fn harness(props: &HarnessProps) -> Html {
+                let onsubmit = props.onsubmit.clone();
+                html! { <#component_ident {onsubmit} #harness_init #(#sample_options)* /> }
+            }
+
+            #[wasm_bindgen_test]
+            fn submits_entered_values() {
+                let document = web_sys::window().unwrap().document().unwrap();
+                let container = document.create_element("div").unwrap();
+                document.body().unwrap().append_child(&container).unwrap();
+
+                let submitted = Rc::new(RefCell::new(None));
+                let onsubmit = {
+                    let submitted = submitted.clone();
+                    Callback::from(move |data: #state_type| {
+                        *submitted.borrow_mut() = Some(data);
+                    })
+                };
+                yew::start_app_with_props_in_element::<Harness>(container.clone(), HarnessProps { onsubmit });
+
+                // The expected value is built by sending the same msgs the inputs should produce
+                let mut expected = #component_ident::from_inner(#new_state);
+
+                #(#fill_inputs)*
+
+                // Forms with named actions have no submit button, so the form is submitted directly,
+                // once for each step of a multi-step form
+                let form = container.query_selector("form").unwrap().unwrap();
+                for _ in 0..#step_count {
+                    form.unchecked_ref::<web_sys::HtmlFormElement>().request_submit().unwrap();
+                }
+                #confirm_review
+
+                let submitted = submitted.borrow();
+                let submitted = submitted.as_ref().expect("onsubmit was not called");
+                let submitted = #submitted_inner;
+                // Compared against what the expected form would submit, after any submit_with
+                let expected = expected.submit_value();
+                let expected = #expected_inner;
+                #(#field_asserts)*
+            }
+        }
+    }
+}
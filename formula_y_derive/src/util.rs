@@ -0,0 +1,336 @@
+use syn::{
+    ext::IdentExt, punctuated::Punctuated, token::Comma, DeriveInput, Field, GenericArgument,
+    Ident, PathArguments, Type,
+};
+
+// Return whether a type matches a given &str
+fn is_type(type_as_str: &str, ty: &syn::Type) -> bool {
+    if let syn::Type::Path(ref p) = ty {
+        p.path.segments.len() == 1 && p.path.segments[0].ident == type_as_str
+    } else {
+        false
+    }
+}
+
+// Return whether a field has a given type
+fn field_has_type(type_as_str: &str, field: &syn::Field) -> bool {
+    is_type(type_as_str, &field.ty)
+}
+
+// Return whether a field is an optionized type
+fn field_is_optionized(type_as_str: &str, field: &syn::Field) -> bool {
+    field_wraps_type("Option", type_as_str, field)
+}
+
+// Return whether a field is a generic wrapper, i.e. Option or Vec, around a given type
+fn field_wraps_type(wrapper: &str, type_as_str: &str, field: &syn::Field) -> bool {
+    field_has_type(wrapper, field)
+        && wrapped_type(&field.ty).is_some_and(|ty| is_type(type_as_str, ty))
+}
+
+// The single type argument of a wrapper, i.e. String in Option<String>
+fn wrapped_type(ty: &syn::Type) -> Option<&syn::Type> {
+    let Type::Path(typepath) = ty else {
+        return None;
+    };
+    let PathArguments::AngleBracketed(params) = &typepath.path.segments.last()?.arguments else {
+        return None;
+    };
+    match (params.args.len(), params.args.first()) {
+        (1, Some(GenericArgument::Type(ty))) => Some(ty),
+        _ => None,
+    }
+}
+
+pub fn field_is_string(field: &syn::Field) -> bool {
+    field_has_type("String", field)
+}
+
+pub fn field_is_bool(field: &syn::Field) -> bool {
+    field_has_type("bool", field)
+}
+
+pub fn field_is_option(field: &syn::Field) -> bool {
+    field_has_type("Option", field)
+}
+
+pub fn field_is_option_string(field: &syn::Field) -> bool {
+    field_is_optionized("String", field)
+}
+
+pub fn field_is_option_bool(field: &syn::Field) -> bool {
+    field_is_optionized("bool", field)
+}
+
+// The primitive number types which render as number inputs
+const NUMBER_TYPES: [&str; 14] = [
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize", "f32",
+    "f64",
+];
+
+pub fn field_is_number(field: &syn::Field) -> bool {
+    NUMBER_TYPES.iter().any(|ty| field_has_type(ty, field))
+}
+
+pub fn field_is_option_number(field: &syn::Field) -> bool {
+    NUMBER_TYPES.iter().any(|ty| field_is_optionized(ty, field))
+}
+
+// Whether a field is an f32 or f64, or an Option of one
+pub fn field_is_float(field: &syn::Field) -> bool {
+    ["f32", "f64"]
+        .iter()
+        .any(|ty| field_has_type(ty, field) || field_is_optionized(ty, field))
+}
+
+// The chrono types which render as date inputs with the chrono feature enabled, matched by the
+// last segment of their path so that chrono::NaiveDate works as well as NaiveDate
+const DATE_TYPES: [&str; 2] = ["NaiveDate", "NaiveDateTime"];
+
+fn is_date_type(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(p) if cfg!(feature = "chrono") => p
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| DATE_TYPES.iter().any(|date| segment.ident == date)),
+        _ => false,
+    }
+}
+
+pub fn field_is_date(field: &syn::Field) -> bool {
+    is_date_type(&field.ty)
+}
+
+pub fn field_is_option_date(field: &syn::Field) -> bool {
+    field_is_option(field) && is_date_type(&item_type(field))
+}
+
+// Whether a date field, or the date inside an Option, holds a time as well, i.e. NaiveDateTime
+pub fn field_is_datetime(field: &syn::Field) -> bool {
+    let ty = if field_is_option(field) {
+        item_type(field)
+    } else {
+        field.ty.clone()
+    };
+    matches!(ty, Type::Path(p) if p.path.segments.last().is_some_and(|segment| segment.ident == "NaiveDateTime"))
+}
+
+pub fn field_is_vec(field: &syn::Field) -> bool {
+    field_has_type("Vec", field)
+}
+
+// A list of text values, rendered as one input per item
+pub fn field_is_string_vec(field: &syn::Field) -> bool {
+    field_wraps_type("Vec", "String", field)
+}
+
+// Fields marked #[yform(nested)] hold a struct which derives YForm, rendered as a fieldset. This
+// looks for the marker directly rather than parsing every attribute, since the attribute parsing
+// itself relies on knowing how fields render.
+fn field_has_nested_marker(field: &syn::Field) -> bool {
+    field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("yform"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(syn::Meta::List(list)) => Some(list.nested),
+            _ => None,
+        })
+        .flatten()
+        .any(|nested| matches!(nested, syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("nested")))
+}
+
+pub fn field_is_nested(field: &syn::Field) -> bool {
+    field_has_nested_marker(field) && !field_is_vec(field)
+}
+
+// A list of structs which derive YForm, rendered as a group of fieldsets repeated per item
+pub fn field_is_nested_vec(field: &syn::Field) -> bool {
+    field_has_nested_marker(field) && field_is_vec(field)
+}
+
+// Any other type is taken to be an enum implementing YFormSelect, which is offered as a choice
+// between its variants
+pub fn field_is_enum(field: &syn::Field) -> bool {
+    !field_is_string(field)
+        && !field_is_bool(field)
+        && !field_is_number(field)
+        && !field_is_option(field)
+        && !field_is_vec(field)
+        && !field_is_date(field)
+        && !field_has_nested_marker(field)
+}
+
+pub fn field_is_option_enum(field: &syn::Field) -> bool {
+    field_is_option(field)
+        && !field_is_option_string(field)
+        && !field_is_option_bool(field)
+        && !field_is_option_number(field)
+        && !field_is_option_date(field)
+}
+
+// chrono_tz::Tz fields, or Options of them, which render as a searchable select of time zones
+// with the chrono-tz feature enabled. Without it they are taken to be any other enum.
+pub fn field_is_time_zone(field: &syn::Field) -> bool {
+    cfg!(feature = "chrono-tz")
+        && (field_is_enum(field) || field_is_option_enum(field))
+        && matches!(enum_type(field), Type::Path(p) if p.path.segments.last().is_some_and(|segment| segment.ident == "Tz"))
+}
+
+/// The type of an enum field, or of the enum inside an Option, for calling YFormSelect's methods
+pub fn enum_type(field: &syn::Field) -> Type {
+    if field_is_option(field) {
+        item_type(field)
+    } else {
+        field.ty.clone()
+    }
+}
+
+/// The type inside an Option or Vec field
+pub fn item_type(field: &syn::Field) -> Type {
+    if let Type::Path(typepath) = &field.ty {
+        if let PathArguments::AngleBracketed(params) = &typepath.path.segments[0].arguments {
+            if let Some(GenericArgument::Type(ty)) = params.args.first() {
+                return ty.clone();
+            }
+        }
+    }
+    field.ty.clone()
+}
+
+/// Produce a new Ident by appending to the string verison, i.e.
+/// Name -> NameBuilder etc.
+pub fn append_to_ident(ident: &Ident, to_append: &str) -> Ident {
+    let new_name = format!("{}{}", ident, to_append);
+    syn::Ident::new(&new_name, ident.span())
+}
+
+/// The name of a field as used for generated idents, labels and class names. Raw identifiers
+/// lose their `r#` prefix, i.e. r#type -> type
+pub fn field_name(field_ident: &Ident) -> String {
+    field_ident.unraw().to_string()
+}
+
+/// Get the fields of a struct represented as a derive input. Each field's name labels its input,
+/// so tuple structs, unit structs, enums and unions are turned away.
+pub fn get_struct_fields(ast: &DeriveInput) -> syn::Result<Punctuated<Field, Comma>> {
+    const NAMED_FIELDS_ONLY: &str =
+        "YForm can only be derived for structs with named fields, which label their inputs";
+    match &ast.data {
+        syn::Data::Struct(data) => match &data.fields {
+            syn::Fields::Named(fields) => Ok(fields.named.clone()),
+            syn::Fields::Unnamed(fields) => Err(syn::Error::new_spanned(fields, NAMED_FIELDS_ONLY)),
+            syn::Fields::Unit => Err(syn::Error::new_spanned(&ast.ident, NAMED_FIELDS_ONLY)),
+        },
+        syn::Data::Enum(data) => Err(syn::Error::new_spanned(data.enum_token, NAMED_FIELDS_ONLY)),
+        syn::Data::Union(data) => Err(syn::Error::new_spanned(data.union_token, NAMED_FIELDS_ONLY)),
+    }
+}
+
+// The types YForm knows how to render, listed in the errors for the ones it doesn't
+const SUPPORTED_TYPES: &str = "Fields can be String, bool, a number type, an enum which derives \
+    YFormSelect, or an Option of one of these, as well as Vec<String>, and structs which derive \
+    YForm marked #[yform(nested)] or a Vec of them";
+
+// Standard library types which would otherwise be taken for an enum implementing YFormSelect,
+// leaving the user with an unhelpful missing trait error
+const UNSUPPORTED_TYPES: [&str; 14] = [
+    "char", "str", "Box", "Rc", "Arc", "Cell", "RefCell", "Cow", "HashMap", "HashSet", "BTreeMap",
+    "BTreeSet", "VecDeque", "PathBuf",
+];
+
+// Check a type which is rendered as a single input, i.e. a field's own type or the type inside
+// its Option
+fn check_input_type(ty: &syn::Type) -> syn::Result<()> {
+    let segment = match ty {
+        Type::Path(typepath) if typepath.qself.is_none() => typepath.path.segments.last(),
+        _ => None,
+    };
+    let Some(segment) = segment else {
+        return Err(syn::Error::new_spanned(
+            ty,
+            format!("this type isn't supported. {}", SUPPORTED_TYPES),
+        ));
+    };
+    let name = segment.ident.to_string();
+    if name == "Option" {
+        return Err(syn::Error::new_spanned(
+            ty,
+            "Options can't be nested, since an empty input is the only None there is",
+        ));
+    }
+    if name == "Vec" {
+        return Err(syn::Error::new_spanned(
+            ty,
+            "Option<Vec<_>> isn't supported, use the Vec on its own, which can be left empty",
+        ));
+    }
+    if UNSUPPORTED_TYPES.contains(&name.as_str()) {
+        return Err(syn::Error::new_spanned(
+            ty,
+            format!("{} isn't supported. {}", name, SUPPORTED_TYPES),
+        ));
+    }
+    if DATE_TYPES.contains(&name.as_str()) && !cfg!(feature = "chrono") {
+        return Err(syn::Error::new_spanned(
+            ty,
+            format!("{} fields need formula_y's chrono feature", name),
+        ));
+    }
+    Ok(())
+}
+
+/// Check that a field's type is one YForm can render, pointing at the type if it isn't rather
+/// than leaving the generated code to fail to compile
+pub fn check_field_type(field: &syn::Field) -> syn::Result<()> {
+    let qualified_wrapper = match &field.ty {
+        Type::Path(typepath) => {
+            typepath.path.segments.len() > 1
+                && typepath
+                    .path
+                    .segments
+                    .last()
+                    .is_some_and(|segment| segment.ident == "Option" || segment.ident == "Vec")
+        }
+        _ => false,
+    };
+    if qualified_wrapper {
+        return Err(syn::Error::new_spanned(
+            &field.ty,
+            "Option and Vec are recognised by name, so write them without a path, i.e. Option<String>",
+        ));
+    }
+    if !field_is_option(field) && !field_is_vec(field) {
+        // Nested structs are whatever type the user says derives YForm
+        return if field_has_nested_marker(field) {
+            Ok(())
+        } else {
+            check_input_type(&field.ty)
+        };
+    }
+    let wrapper = if field_is_option(field) {
+        "Option"
+    } else {
+        "Vec"
+    };
+    let Some(item) = wrapped_type(&field.ty) else {
+        return Err(syn::Error::new_spanned(
+            &field.ty,
+            format!(
+                "{} needs the type it holds, i.e. {}<String>",
+                wrapper, wrapper
+            ),
+        ));
+    };
+    if field_is_vec(field) {
+        if is_type("String", item) || field_has_nested_marker(field) {
+            return Ok(());
+        }
+        return Err(syn::Error::new_spanned(
+            &field.ty,
+            "Vec fields hold Strings, or structs which derive YForm marked #[yform(nested)]",
+        ));
+    }
+    check_input_type(item)
+}
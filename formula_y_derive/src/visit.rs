@@ -0,0 +1,208 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{punctuated::Punctuated, token::Comma, Field, Ident};
+
+use crate::util::{
+    field_is_bool, field_is_date, field_is_datetime, field_is_enum, field_is_nested,
+    field_is_nested_vec, field_is_number, field_is_option, field_is_option_bool,
+    field_is_option_date, field_is_option_enum, field_is_option_number, field_is_option_string,
+    field_is_string, field_is_string_vec, field_name, item_type,
+};
+use crate::{get_field_variant_ident, get_label, get_parse};
+
+/// The expression converting a field's value into a FieldValue, given the expression for the value
+/// itself
+fn field_value(field: &Field, value: TokenStream) -> TokenStream {
+    if field_is_string(field) {
+        quote! { FieldValue::Text(#value.clone()) }
+    } else if field_is_option_string(field) {
+        quote! { #value.clone().map_or(FieldValue::Empty, FieldValue::Text) }
+    } else if field_is_bool(field) {
+        quote! { FieldValue::Bool(#value) }
+    } else if field_is_option_bool(field) {
+        quote! { #value.map_or(FieldValue::Empty, FieldValue::Bool) }
+    } else if field_is_number(field) {
+        quote! { FieldValue::Number(#value.to_string()) }
+    } else if field_is_option_number(field) {
+        quote! { #value.map_or(FieldValue::Empty, |value| FieldValue::Number(value.to_string())) }
+    } else if field_is_date(field) {
+        quote! { FieldValue::Date(#value.to_string()) }
+    } else if field_is_option_date(field) {
+        quote! { #value.map_or(FieldValue::Empty, |value| FieldValue::Date(value.to_string())) }
+    } else if field_is_enum(field) {
+        quote! { FieldValue::Choice(#value.label().to_string()) }
+    } else if field_is_option_enum(field) {
+        quote! { #value.as_ref().map_or(FieldValue::Empty, |value| FieldValue::Choice(value.label().to_string())) }
+    } else if field_is_nested(field) {
+        quote! { FieldValue::Nested(YFormNested::field_values(&#value)) }
+    } else if field_is_string_vec(field) {
+        quote! { FieldValue::List(#value.clone()) }
+    } else if field_is_nested_vec(field) {
+        quote! { FieldValue::NestedList(#value.iter().map(YFormNested::field_values).collect()) }
+    } else {
+        quote! { FieldValue::Empty }
+    }
+}
+
+/// Generate the struct's visit_fields method, handing each field to a FieldVisitor in the order
+/// they are declared
+pub fn visit_fields_method(
+    field_enum_ident: &Ident,
+    fields: &Punctuated<Field, Comma>,
+) -> TokenStream {
+    let visits = fields.iter().map(|field| {
+        let field_ident = field.ident.clone().unwrap();
+        let field_variant = get_field_variant_ident(field);
+        let name = field_name(&field_ident);
+        let label = get_label(field);
+        let value = field_value(field, quote! { self.#field_ident });
+        quote! {
+            visitor.visit(
+                #field_enum_ident::#field_variant,
+                &FieldMeta { name: #name, label: #label },
+                #value,
+            );
+        }
+    });
+    quote! {
+        /// Hand each field, with its name, label and value, to a visitor, i.e. to export the
+        /// values without code for each form
+        pub fn visit_fields(&self, visitor: &mut impl FieldVisitor<#field_enum_ident>) {
+            #(#visits)*
+        }
+    }
+}
+
+/// Generate the struct's CSV and TSV helpers, which write the values visit_fields walks as a row
+pub fn csv_methods(fields: &Punctuated<Field, Comma>) -> TokenStream {
+    let names = fields
+        .iter()
+        .map(|field| field_name(field.ident.as_ref().unwrap()))
+        .collect::<Vec<_>>();
+    quote! {
+        /// The names of the fields as a line of CSV, for the first line of rows from `to_csv_row`
+        pub fn csv_header() -> String {
+            CsvRow::header(&[#(#names),*], ',')
+        }
+
+        /// The values of the fields as a line of CSV, without a trailing newline
+        pub fn to_csv_row(&self) -> String {
+            let mut row = CsvRow::new(',');
+            self.visit_fields(&mut row);
+            row.finish()
+        }
+
+        /// The names of the fields as a line of tab separated values
+        pub fn tsv_header() -> String {
+            CsvRow::header(&[#(#names),*], '\t')
+        }
+
+        /// The values of the fields as a line of tab separated values, i.e. for pasting into a
+        /// spreadsheet
+        pub fn to_tsv_row(&self) -> String {
+            let mut row = CsvRow::new('\t');
+            self.visit_fields(&mut row);
+            row.finish()
+        }
+    }
+}
+
+/// The expression parsing a field's value from the text of its cell in `cell`, as written by
+/// to_csv_row, into a Result
+fn field_import(field: &Field, name: &str) -> TokenStream {
+    let ty = if field_is_option(field) {
+        item_type(field)
+    } else {
+        field.ty.clone()
+    };
+    let invalid = quote! {
+        |_| ImportError::InvalidValue { field: #name, value: cell.to_string() }
+    };
+    let parse = if field_is_string(field) || field_is_option_string(field) {
+        quote! { Ok::<_, ImportError>(cell.to_string()) }
+    } else if field_is_bool(field) || field_is_option_bool(field) {
+        quote! { cell.trim().parse::<bool>().map_err(#invalid) }
+    } else if field_is_number(field) || field_is_option_number(field) {
+        quote! { cell.trim().parse::<#ty>().map_err(#invalid) }
+    } else if field_is_datetime(field) {
+        // Rows hold dates as they display, and the format of the form's input is accepted as well
+        let parse_input = get_parse(field, quote! { cell.trim() });
+        quote! {
+            chrono::NaiveDateTime::parse_from_str(cell.trim(), "%Y-%m-%d %H:%M:%S%.f")
+                .or_else(|_| #parse_input)
+                .map_err(#invalid)
+        }
+    } else if field_is_date(field) || field_is_option_date(field) {
+        quote! { cell.trim().parse::<#ty>().map_err(#invalid) }
+    } else if field_is_enum(field) || field_is_option_enum(field) {
+        // Rows hold the label of the chosen variant, but its value is accepted as well
+        quote! {
+            <#ty as YFormSelect>::from_label(cell)
+                .or_else(|| <#ty as YFormSelect>::from_value(cell))
+                .ok_or(()).map_err(#invalid)
+        }
+    } else if field_is_string_vec(field) {
+        quote! {
+            Ok::<_, ImportError>(if cell.is_empty() {
+                Vec::new()
+            } else {
+                cell.split(", ").map(str::to_string).collect()
+            })
+        }
+    } else {
+        quote! { Err(ImportError::Unsupported { field: #name }) }
+    };
+    if field_is_option(field) && !field_is_nested(field) && !field_is_nested_vec(field) {
+        quote! {
+            if cell.is_empty() { Ok(None) } else { (#parse).map(Some) }
+        }
+    } else {
+        parse
+    }
+}
+
+/// Generate the struct's CSV and TSV import, the inverse of csv_methods. Fields left out of the
+/// form by skip_unsupported have no column, and are given their `unsupported_inits`.
+pub fn import_methods(
+    fields: &Punctuated<Field, Comma>,
+    unsupported_inits: &[TokenStream],
+) -> TokenStream {
+    let column_count = fields.len();
+    let field_inits = fields.iter().enumerate().map(|(index, field)| {
+        let field_ident = field.ident.clone().unwrap();
+        let import = field_import(field, &field_name(&field_ident));
+        quote! {
+            #field_ident: {
+                let cell = cells[#index].as_str();
+                #import?
+            }
+        }
+    });
+    quote! {
+        /// Read a line of CSV, as written by `to_csv_row`, back into the struct, parsing each cell
+        /// as the type of its field
+        pub fn from_csv_row(row: &str) -> Result<Self, ImportError> {
+            Self::from_delimited_row(row, ',')
+        }
+
+        /// Read a line of tab separated values, i.e. a row pasted from a spreadsheet, into the
+        /// struct
+        pub fn from_tsv_row(row: &str) -> Result<Self, ImportError> {
+            Self::from_delimited_row(row, '\t')
+        }
+
+        fn from_delimited_row(row: &str, delimiter: char) -> Result<Self, ImportError> {
+            let cells = CsvRow::parse(row, delimiter);
+            if cells.len() != #column_count {
+                return Err(ImportError::ColumnCount {
+                    expected: #column_count,
+                    found: cells.len(),
+                });
+            }
+            Ok(Self {
+                #(#field_inits,)*
+                #(#unsupported_inits,)*
+            })
+        }
+    }
+}
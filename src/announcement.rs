@@ -0,0 +1,39 @@
+use std::fmt;
+use std::rc::Rc;
+
+/// Words the number of errors found by a blocked submit, for a form's `errors_announcement` prop,
+/// i.e. to translate the default "3 errors found".
+///
+/// Like `FieldPolicy`, this wraps a plain closure and is compared by pointer, so build it once
+/// rather than on every render.
+pub struct ErrorsAnnouncement(Rc<dyn Fn(usize) -> String>);
+
+impl ErrorsAnnouncement {
+    pub fn message(&self, count: usize) -> String {
+        (self.0)(count)
+    }
+}
+
+impl<T: Fn(usize) -> String + 'static> From<T> for ErrorsAnnouncement {
+    fn from(announcement: T) -> Self {
+        ErrorsAnnouncement(Rc::new(announcement))
+    }
+}
+
+impl Clone for ErrorsAnnouncement {
+    fn clone(&self) -> Self {
+        ErrorsAnnouncement(self.0.clone())
+    }
+}
+
+impl PartialEq for ErrorsAnnouncement {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for ErrorsAnnouncement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ErrorsAnnouncement(..)")
+    }
+}
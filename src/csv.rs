@@ -0,0 +1,209 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::{FieldMeta, FieldValue, FieldVisitor};
+
+/// A `FieldVisitor` writing each field's value as a cell of a delimited row, i.e. a line of CSV
+/// or TSV. The derive's `to_csv_row` and `to_tsv_row` use it, but it can be handed to
+/// `visit_fields` directly for other delimiters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvRow {
+    delimiter: char,
+    cells: Vec<String>,
+}
+
+impl CsvRow {
+    /// An empty row whose cells are separated by `delimiter`, i.e. `','` or `'\t'`
+    pub fn new(delimiter: char) -> Self {
+        Self {
+            delimiter,
+            cells: Vec::new(),
+        }
+    }
+
+    /// The header row for a form, given the names of its fields
+    pub fn header(names: &[&str], delimiter: char) -> String {
+        line(names.iter().copied(), delimiter)
+    }
+
+    /// The row of the visited values, without a trailing newline
+    pub fn finish(self) -> String {
+        line(self.cells.iter().map(String::as_str), self.delimiter)
+    }
+
+    /// Split a row written by `finish` back into its cells, unquoting the quoted ones. A trailing
+    /// line break is ignored, so lines pasted from a spreadsheet parse as they are.
+    pub fn parse(row: &str, delimiter: char) -> Vec<String> {
+        let row = row.strip_suffix('\n').unwrap_or(row);
+        let row = row.strip_suffix('\r').unwrap_or(row);
+        let mut cells = Vec::new();
+        let mut cell = String::new();
+        let mut quoted = false;
+        let mut chars = row.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if quoted && chars.peek() == Some(&'"') => {
+                    chars.next();
+                    cell.push('"');
+                }
+                '"' if quoted => quoted = false,
+                '"' if cell.is_empty() => quoted = true,
+                c if c == delimiter && !quoted => cells.push(std::mem::take(&mut cell)),
+                c => cell.push(c),
+            }
+        }
+        cells.push(cell);
+        cells
+    }
+}
+
+impl<F> FieldVisitor<F> for CsvRow {
+    fn visit(&mut self, _: F, _: &FieldMeta, value: FieldValue) {
+        self.cells.push(value.to_text());
+    }
+}
+
+/// Join cells with the delimiter, quoting the ones holding a delimiter, quote or line break
+fn line<'a>(cells: impl Iterator<Item = &'a str>, delimiter: char) -> String {
+    cells
+        .map(|cell| {
+            if cell.contains([delimiter, '"', '\n', '\r']) {
+                format!("\"{}\"", cell.replace('"', "\"\""))
+            } else {
+                cell.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(&delimiter.to_string())
+}
+
+/// Why a row couldn't be imported with a derived struct's `from_csv_row` or `from_tsv_row`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportError {
+    /// The row doesn't have a cell for each of the struct's fields
+    ColumnCount { expected: usize, found: usize },
+    /// A cell doesn't parse as the type of its field, i.e. `abc` for a number
+    InvalidValue { field: &'static str, value: String },
+    /// The field is a nested struct or of a custom type, which can't be read back from text
+    Unsupported { field: &'static str },
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::ColumnCount { expected, found } => {
+                write!(f, "expected {} columns but found {}", expected, found)
+            }
+            ImportError::InvalidValue { field, value } => {
+                write!(f, "{:?} isn't a valid value for {}", value, field)
+            }
+            ImportError::Unsupported { field } => {
+                write!(f, "{} can't be imported from text", field)
+            }
+        }
+    }
+}
+
+impl Error for ImportError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const META: FieldMeta = FieldMeta {
+        name: "field",
+        label: "Field",
+    };
+
+    fn row(delimiter: char, values: Vec<FieldValue>) -> String {
+        let mut row = CsvRow::new(delimiter);
+        for value in values {
+            row.visit((), &META, value);
+        }
+        row.finish()
+    }
+
+    #[test]
+    fn plain_cells_are_left_unquoted() {
+        let values = vec![
+            FieldValue::Text("Ann".to_string()),
+            FieldValue::Number("42".to_string()),
+            FieldValue::Bool(true),
+            FieldValue::Empty,
+        ];
+        assert_eq!(row(',', values), "Ann,42,true,");
+        assert_eq!(CsvRow::header(&["name", "age"], ','), "name,age");
+    }
+
+    #[test]
+    fn cells_holding_delimiters_quotes_or_line_breaks_are_quoted() {
+        let values = vec![
+            FieldValue::Text("Smith, Ann".to_string()),
+            FieldValue::Text("say \"hi\"".to_string()),
+            FieldValue::Text("two\nlines".to_string()),
+            FieldValue::Text("\r".to_string()),
+        ];
+        assert_eq!(
+            row(',', values),
+            "\"Smith, Ann\",\"say \"\"hi\"\"\",\"two\nlines\",\"\r\""
+        );
+    }
+
+    #[test]
+    fn tsv_only_quotes_tabs() {
+        let values = vec![
+            FieldValue::Text("Smith, Ann".to_string()),
+            FieldValue::Text("a\tb".to_string()),
+            FieldValue::List(vec!["x".to_string(), "y".to_string()]),
+        ];
+        assert_eq!(row('\t', values), "Smith, Ann\t\"a\tb\"\tx, y");
+    }
+
+    #[test]
+    fn parse_reads_back_what_finish_writes() {
+        for delimiter in [',', '\t'] {
+            let cells = [
+                "Ann",
+                "",
+                "Smith, Ann",
+                "a\tb",
+                "say \"hi\"",
+                "\"",
+                "two\nlines",
+                "ends with\r\n",
+            ];
+            let values = cells
+                .iter()
+                .map(|cell| FieldValue::Text(cell.to_string()))
+                .collect();
+            assert_eq!(CsvRow::parse(&row(delimiter, values), delimiter), cells);
+        }
+    }
+
+    #[test]
+    fn parse_ignores_a_trailing_line_break() {
+        assert_eq!(CsvRow::parse("a,b\n", ','), ["a", "b"]);
+        assert_eq!(CsvRow::parse("a\tb\r\n", '\t'), ["a", "b"]);
+        assert_eq!(CsvRow::parse("a,\"b\r\n\"", ','), ["a", "b\r\n"]);
+    }
+
+    #[test]
+    fn parse_keeps_quotes_inside_unquoted_cells() {
+        assert_eq!(CsvRow::parse("5\" disk,x", ','), ["5\" disk", "x"]);
+        assert_eq!(CsvRow::parse("", ','), [""]);
+    }
+
+    #[test]
+    fn import_errors_describe_the_row() {
+        let error = ImportError::ColumnCount {
+            expected: 3,
+            found: 2,
+        };
+        assert_eq!(error.to_string(), "expected 3 columns but found 2");
+        let error = ImportError::InvalidValue {
+            field: "age",
+            value: "abc".to_string(),
+        };
+        assert_eq!(error.to_string(), "\"abc\" isn't a valid value for age");
+    }
+}
@@ -0,0 +1,218 @@
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+/// The order the day, month and year of a date are typed in, which a date field with
+/// `#[yform(date_text)]` takes from the browser's locale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DateOrder {
+    /// 14/02/2025, as in most of the world
+    DayMonthYear,
+    /// 02/14/2025, as in the US
+    MonthDayYear,
+    /// 2025-02-14, as in ISO 8601, China, Japan or Korea
+    YearMonthDay,
+}
+
+impl DateOrder {
+    /// The order dates are written in for a BCP 47 language tag, i.e. `"en-US"` or `"de"`
+    pub fn for_locale(locale: &str) -> Self {
+        let locale = locale.to_ascii_lowercase().replace('_', "-");
+        let language = locale.split('-').next().unwrap_or_default();
+        if ["en", "en-us", "en-ph"].contains(&locale.as_str()) {
+            DateOrder::MonthDayYear
+        } else if ["zh", "ja", "ko", "hu", "lt", "mn", "sv"].contains(&language)
+            || ["en-ca", "fr-ca"].contains(&locale.as_str())
+        {
+            DateOrder::YearMonthDay
+        } else {
+            DateOrder::DayMonthYear
+        }
+    }
+
+    /// The order for the browser's language, or ISO's when it has none
+    #[cfg(target_arch = "wasm32")]
+    pub fn detect() -> Self {
+        web_sys::window()
+            .and_then(|window| window.navigator().language())
+            .map_or(DateOrder::YearMonthDay, |locale| Self::for_locale(&locale))
+    }
+
+    /// The order for the browser's language, or ISO's when it has none, as when the form is driven
+    /// natively, i.e. by its tester
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn detect() -> Self {
+        DateOrder::YearMonthDay
+    }
+
+    /// The chrono format a date is shown in
+    pub fn date_format(self) -> &'static str {
+        match self {
+            DateOrder::DayMonthYear => "%d/%m/%Y",
+            DateOrder::MonthDayYear => "%m/%d/%Y",
+            DateOrder::YearMonthDay => "%Y-%m-%d",
+        }
+    }
+
+    /// The chrono format a date and time is shown in
+    pub fn datetime_format(self) -> &'static str {
+        match self {
+            DateOrder::DayMonthYear => "%d/%m/%Y %H:%M",
+            DateOrder::MonthDayYear => "%m/%d/%Y %H:%M",
+            DateOrder::YearMonthDay => "%Y-%m-%d %H:%M",
+        }
+    }
+
+    /// The hint shown in an empty date input, i.e. `dd/mm/yyyy`
+    pub fn placeholder(self) -> &'static str {
+        match self {
+            DateOrder::DayMonthYear => "dd/mm/yyyy",
+            DateOrder::MonthDayYear => "mm/dd/yyyy",
+            DateOrder::YearMonthDay => "yyyy-mm-dd",
+        }
+    }
+
+    /// Parse a typed date. The parts can be separated by `/`, `.`, `-` or spaces, and a date
+    /// starting with a four digit year is read as ISO whatever the order. Two digit years are in
+    /// 1969 to 2068, as with chrono's `%y`.
+    pub fn parse_date(self, text: &str) -> Option<NaiveDate> {
+        let parts = text
+            .trim()
+            .split(|c: char| ['/', '.', '-', ' '].contains(&c))
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>();
+        let [first, second, third] = parts[..] else {
+            return None;
+        };
+        if !parts
+            .iter()
+            .all(|part| part.chars().all(|c| c.is_ascii_digit()))
+        {
+            return None;
+        }
+        let (year, month, day) = match self {
+            _ if first.len() == 4 => (first, second, third),
+            DateOrder::DayMonthYear => (third, second, first),
+            DateOrder::MonthDayYear => (third, first, second),
+            DateOrder::YearMonthDay => (first, second, third),
+        };
+        let year = match (year.len(), year.parse::<i32>().ok()?) {
+            (2, year) if year < 69 => 2000 + year,
+            (2, year) => 1900 + year,
+            (4, year) => year,
+            _ => return None,
+        };
+        NaiveDate::from_ymd_opt(year, month.parse().ok()?, day.parse().ok()?)
+    }
+
+    /// Parse a typed date and time, the time following the date after a space as `HH:MM` or
+    /// `HH:MM:SS`
+    pub fn parse_datetime(self, text: &str) -> Option<NaiveDateTime> {
+        let (date, time) = text.trim().rsplit_once(' ')?;
+        let time = NaiveTime::parse_from_str(time, "%H:%M:%S")
+            .or_else(|_| NaiveTime::parse_from_str(time, "%H:%M"))
+            .ok()?;
+        Some(self.parse_date(date)?.and_time(time))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn locales_pick_their_order() {
+        assert_eq!(DateOrder::for_locale("en-US"), DateOrder::MonthDayYear);
+        assert_eq!(DateOrder::for_locale("en-GB"), DateOrder::DayMonthYear);
+        assert_eq!(DateOrder::for_locale("de_DE"), DateOrder::DayMonthYear);
+        assert_eq!(DateOrder::for_locale("ja"), DateOrder::YearMonthDay);
+        assert_eq!(DateOrder::for_locale("fr-CA"), DateOrder::YearMonthDay);
+    }
+
+    #[test]
+    fn dates_are_parsed_in_the_order() {
+        let valentines = date(2025, 2, 14);
+        assert_eq!(
+            DateOrder::DayMonthYear.parse_date("14/02/2025"),
+            Some(valentines)
+        );
+        assert_eq!(
+            DateOrder::DayMonthYear.parse_date("14.2.2025"),
+            Some(valentines)
+        );
+        assert_eq!(
+            DateOrder::MonthDayYear.parse_date("2/14/2025"),
+            Some(valentines)
+        );
+        assert_eq!(
+            DateOrder::YearMonthDay.parse_date("2025-02-14"),
+            Some(valentines)
+        );
+        assert_eq!(DateOrder::MonthDayYear.parse_date("14/02/2025"), None);
+    }
+
+    #[test]
+    fn four_digit_years_first_are_iso() {
+        assert_eq!(
+            DateOrder::DayMonthYear.parse_date("2025-02-14"),
+            Some(date(2025, 2, 14))
+        );
+        assert_eq!(
+            DateOrder::MonthDayYear.parse_date("2025-02-14"),
+            Some(date(2025, 2, 14))
+        );
+    }
+
+    #[test]
+    fn two_digit_years_are_within_a_century() {
+        assert_eq!(
+            DateOrder::DayMonthYear.parse_date("14/02/25"),
+            Some(date(2025, 2, 14))
+        );
+        assert_eq!(
+            DateOrder::DayMonthYear.parse_date("14/02/85"),
+            Some(date(1985, 2, 14))
+        );
+        assert_eq!(DateOrder::DayMonthYear.parse_date("14/02/202"), None);
+    }
+
+    #[test]
+    fn other_text_is_not_a_date() {
+        assert_eq!(DateOrder::DayMonthYear.parse_date(""), None);
+        assert_eq!(DateOrder::DayMonthYear.parse_date("14/02"), None);
+        assert_eq!(DateOrder::DayMonthYear.parse_date("14/feb/2025"), None);
+        assert_eq!(DateOrder::DayMonthYear.parse_date("30/02/2025"), None);
+    }
+
+    #[test]
+    fn datetimes_take_a_time_after_the_date() {
+        let expected = date(2025, 2, 14).and_hms_opt(9, 30, 0).unwrap();
+        assert_eq!(
+            DateOrder::DayMonthYear.parse_datetime("14/02/2025 09:30"),
+            Some(expected)
+        );
+        assert_eq!(
+            DateOrder::DayMonthYear.parse_datetime("14/02/2025 09:30:00"),
+            Some(expected)
+        );
+        assert_eq!(DateOrder::DayMonthYear.parse_datetime("14/02/2025"), None);
+    }
+
+    #[test]
+    fn formatted_dates_parse_back() {
+        for order in [
+            DateOrder::DayMonthYear,
+            DateOrder::MonthDayYear,
+            DateOrder::YearMonthDay,
+        ] {
+            let valentines = date(2025, 2, 14);
+            let text = valentines.format(order.date_format()).to_string();
+            assert_eq!(order.parse_date(&text), Some(valentines));
+            let datetime = valentines.and_hms_opt(9, 30, 0).unwrap();
+            let text = datetime.format(order.datetime_format()).to_string();
+            assert_eq!(order.parse_datetime(&text), Some(datetime));
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use crate::field_edit::now_ms;
+
+/// When a form with the `deadline` prop runs out of time, i.e. for a quiz or an exam. Until then
+/// the form counts down to it, and once it passes the form can't be changed or submitted.
+///
+/// A deadline is a point in time rather than a duration, so create it once (i.e. when the quiz
+/// starts) and keep it, rather than building a new one each time the parent renders.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Deadline {
+    at_ms: f64,
+}
+
+impl Deadline {
+    /// A deadline at a time in milliseconds since the Unix epoch, as `js_sys::Date::now()` gives
+    pub fn at_ms(at_ms: f64) -> Self {
+        Self { at_ms }
+    }
+
+    /// A deadline the given number of milliseconds from now
+    pub fn in_ms(ms: f64) -> Self {
+        Self::at_ms(now_ms() + ms)
+    }
+
+    /// How long is left, or 0 once the deadline has passed
+    pub fn remaining_ms(&self) -> f64 {
+        (self.at_ms - now_ms()).max(0.0)
+    }
+
+    pub fn is_past(&self) -> bool {
+        self.remaining_ms() == 0.0
+    }
+
+    /// The time left as the countdown shows it, i.e. `4:05`, or `1:02:03` with an hour or more to go.
+    /// Seconds are rounded up, so the countdown only reads `0:00` once the time is up.
+    pub fn countdown(&self) -> String {
+        let seconds = (self.remaining_ms() / 1000.0).ceil() as u64;
+        let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+        if hours > 0 {
+            format!("{}:{:02}:{:02}", hours, minutes, seconds)
+        } else {
+            format!("{}:{:02}", minutes, seconds)
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use yew::Callback;
+
+use crate::field_edit::now_ms;
+use crate::SharedStorage;
+
+/// Why a `DraftStore` dropped the draft it found rather than handing it back, passed to its
+/// `ondropped` callback.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DraftDropped {
+    /// The draft was saved by another version of the form, which it may not fit
+    OtherVersion {
+        /// The version the draft was saved with
+        saved: u32,
+        /// The version the store was created with
+        current: u32,
+    },
+    /// The draft is older than the store lets drafts live
+    Expired {
+        /// How long ago the draft was saved
+        age_ms: f64,
+    },
+    /// The draft couldn't be read back, and why, i.e. the `parse` closure rejected it
+    Unreadable(String),
+}
+
+/// Saves a form's drafts in a `FormStorage` along with the version of the form they were saved
+/// from and when, so that drafts from an older version of the form, or older than a time to live,
+/// are dropped on loading rather than restored into a form they no longer fit.
+///
+/// Forms hand their drafts to `onsavedraft` or `onchange` rather than storing them, so the store is
+/// given them serialized, and a `parse` closure of the app's own to read them back. A draft which
+/// doesn't parse is dropped the same way, rather than the app having to panic or keep it around.
+/// Each dropped draft is removed from storage and passed to `ondropped`, i.e. to tell the user.
+///
+/// ```ignore
+/// let drafts = DraftStore::new(SharedStorage::new(LocalStorage), "visit-report", 2, ondropped)
+///     .expire_after_ms(7 * 24 * 60 * 60 * 1000);
+/// drafts.load(
+///     |draft| serde_json::from_str::<Report>(draft).map_err(|error| error.to_string()),
+///     ctx.link().callback(Msg::DraftLoaded),
+/// );
+/// let onsavedraft = Callback::from(move |report: Report| {
+///     drafts.save(serde_json::to_string(&report).unwrap(), Callback::noop());
+/// });
+/// ```
+#[derive(Clone)]
+pub struct DraftStore {
+    storage: SharedStorage,
+    key: String,
+    version: u32,
+    ttl_ms: Option<f64>,
+    ondropped: Callback<DraftDropped>,
+}
+
+impl DraftStore {
+    /// Keep drafts in `storage` under `key`, saved as `version` of the form
+    pub fn new(
+        storage: SharedStorage,
+        key: impl Into<String>,
+        version: u32,
+        ondropped: Callback<DraftDropped>,
+    ) -> Self {
+        Self {
+            storage,
+            key: key.into(),
+            version,
+            ttl_ms: None,
+            ondropped,
+        }
+    }
+
+    /// Drop drafts saved longer ago than this, which are otherwise kept until they are cleared
+    pub fn expire_after_ms(self, ttl_ms: u64) -> Self {
+        Self {
+            ttl_ms: Some(ttl_ms as f64),
+            ..self
+        }
+    }
+
+    /// Save a serialized draft, replacing the one saved before
+    pub fn save(&self, draft: String, done: Callback<Result<(), String>>) {
+        let stored = format!("{}\n{}\n{}", self.version, now_ms(), draft);
+        self.storage.set(&self.key, stored, done);
+    }
+
+    /// Read back the draft last saved, parsed with `parse`, or None if there isn't one or it was
+    /// dropped. Failing to read from storage at all is an error, which leaves the draft alone.
+    pub fn load<T: 'static>(
+        &self,
+        parse: impl FnOnce(&str) -> Result<T, String> + 'static,
+        done: Callback<Result<Option<T>, String>>,
+    ) {
+        let store = self.clone();
+        self.storage.get(
+            &self.key,
+            Callback::once(move |stored: Result<Option<String>, String>| match stored {
+                Ok(Some(stored)) => match store.check(&stored, parse) {
+                    Ok(draft) => done.emit(Ok(Some(draft))),
+                    Err(dropped) => {
+                        store.clear(Callback::noop());
+                        store.ondropped.emit(dropped);
+                        done.emit(Ok(None));
+                    }
+                },
+                Ok(None) => done.emit(Ok(None)),
+                Err(error) => done.emit(Err(error)),
+            }),
+        );
+    }
+
+    /// Remove the saved draft, i.e. once the form is submitted
+    pub fn clear(&self, done: Callback<Result<(), String>>) {
+        self.storage.remove(&self.key, done);
+    }
+
+    // The draft in what was stored, unless it has to be dropped
+    fn check<T>(
+        &self,
+        stored: &str,
+        parse: impl FnOnce(&str) -> Result<T, String>,
+    ) -> Result<T, DraftDropped> {
+        let unreadable =
+            || DraftDropped::Unreadable("The draft isn't one a DraftStore saved".into());
+        let mut parts = stored.splitn(3, '\n');
+        let (Some(version), Some(saved_ms), Some(draft)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(unreadable());
+        };
+        let version = version.parse::<u32>().map_err(|_| unreadable())?;
+        let saved_ms = saved_ms.parse::<f64>().map_err(|_| unreadable())?;
+        if version != self.version {
+            return Err(DraftDropped::OtherVersion {
+                saved: version,
+                current: self.version,
+            });
+        }
+        let age_ms = now_ms() - saved_ms;
+        if self.ttl_ms.is_some_and(|ttl_ms| age_ms > ttl_ms) {
+            return Err(DraftDropped::Expired { age_ms });
+        }
+        parse(draft).map_err(DraftDropped::Unreadable)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::{FormStorage, MemoryStorage};
+
+    // A store over memory, and what it has passed to ondropped
+    fn store(version: u32) -> (DraftStore, MemoryStorage, Rc<RefCell<Vec<DraftDropped>>>) {
+        let memory = MemoryStorage::new();
+        let dropped = Rc::new(RefCell::new(Vec::new()));
+        let ondropped = {
+            let dropped = dropped.clone();
+            Callback::from(move |reason| dropped.borrow_mut().push(reason))
+        };
+        let store = DraftStore::new(
+            SharedStorage::new(memory.clone()),
+            "draft",
+            version,
+            ondropped,
+        );
+        (store, memory, dropped)
+    }
+
+    // Load a draft as a number, returning what load reported
+    fn load(store: &DraftStore) -> Result<Option<u32>, String> {
+        let loaded = Rc::new(RefCell::new(None));
+        let done = {
+            let loaded = loaded.clone();
+            Callback::from(move |result| *loaded.borrow_mut() = Some(result))
+        };
+        store.load(
+            |draft| draft.parse().map_err(|_| "not a number".into()),
+            done,
+        );
+        let loaded = loaded.borrow_mut().take();
+        loaded.unwrap()
+    }
+
+    #[test]
+    fn saved_drafts_load_back() {
+        let (store, _, dropped) = store(1);
+        assert_eq!(load(&store), Ok(None));
+        store.save("42".into(), Callback::noop());
+        assert_eq!(load(&store), Ok(Some(42)));
+        assert!(dropped.borrow().is_empty());
+    }
+
+    #[test]
+    fn drafts_from_other_versions_are_dropped() {
+        let (old, memory, _) = store(1);
+        old.save("42".into(), Callback::noop());
+        let (current, _, dropped) = store(2);
+        let current = DraftStore {
+            storage: SharedStorage::new(memory.clone()),
+            ..current
+        };
+        assert_eq!(load(&current), Ok(None));
+        assert_eq!(
+            *dropped.borrow(),
+            [DraftDropped::OtherVersion {
+                saved: 1,
+                current: 2
+            }]
+        );
+        assert!(memory.values().is_empty());
+    }
+
+    #[test]
+    fn expired_drafts_are_dropped() {
+        let (store, memory, dropped) = store(1);
+        let store = store.expire_after_ms(60_000);
+        let saved_ms = now_ms() - 120_000.0;
+        memory.set("draft", format!("1\n{}\n42", saved_ms), Callback::noop());
+        assert_eq!(load(&store), Ok(None));
+        assert!(
+            matches!(dropped.borrow()[..], [DraftDropped::Expired { age_ms }] if age_ms >= 120_000.0)
+        );
+        assert!(memory.values().is_empty());
+    }
+
+    #[test]
+    fn drafts_within_their_time_to_live_load() {
+        let (store, _, _) = store(1);
+        let store = store.expire_after_ms(60_000);
+        store.save("42".into(), Callback::noop());
+        assert_eq!(load(&store), Ok(Some(42)));
+    }
+
+    #[test]
+    fn unreadable_drafts_are_dropped() {
+        let (store, memory, dropped) = store(1);
+        store.save("forty two".into(), Callback::noop());
+        assert_eq!(load(&store), Ok(None));
+        memory.set("draft", "42".into(), Callback::noop());
+        assert_eq!(load(&store), Ok(None));
+        assert_eq!(
+            *dropped.borrow(),
+            [
+                DraftDropped::Unreadable("not a number".into()),
+                DraftDropped::Unreadable("The draft isn't one a DraftStore saved".into()),
+            ]
+        );
+    }
+}
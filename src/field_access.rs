@@ -0,0 +1,52 @@
+use std::fmt;
+use std::rc::Rc;
+
+/// How a field is shown to the current user, as decided by a form's `field_policy` prop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum FieldAccess {
+    /// The field isn't rendered at all
+    Hidden,
+    /// The field shows its value but can't be changed
+    ReadOnly,
+    /// The field can be changed as normal
+    #[default]
+    Editable,
+}
+
+/// Decides the access to each field of a form at render time. `F` is the enum of fields generated
+/// for the form, i.e. `DataField` for a struct named `Data`.
+///
+/// yew 0.19 callbacks can't return values, so this wraps a plain closure instead. Policies are
+/// compared by pointer, so build them once (i.e. with `use_state` or in a parent's `create`)
+/// rather than on every render to avoid rerendering the form needlessly.
+pub struct FieldPolicy<F>(Rc<dyn Fn(F) -> FieldAccess>);
+
+impl<F> FieldPolicy<F> {
+    pub fn access(&self, field: F) -> FieldAccess {
+        (self.0)(field)
+    }
+}
+
+impl<F, T: Fn(F) -> FieldAccess + 'static> From<T> for FieldPolicy<F> {
+    fn from(policy: T) -> Self {
+        FieldPolicy(Rc::new(policy))
+    }
+}
+
+impl<F> Clone for FieldPolicy<F> {
+    fn clone(&self) -> Self {
+        FieldPolicy(self.0.clone())
+    }
+}
+
+impl<F> PartialEq for FieldPolicy<F> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<F> fmt::Debug for FieldPolicy<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("FieldPolicy(..)")
+    }
+}
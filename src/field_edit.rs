@@ -0,0 +1,40 @@
+use crate::FieldValue;
+
+/// One change to a field's value, as recorded by a form with the `audit_trail` prop set and passed
+/// to its `onedit` callback. `F` is the enum of fields generated for the form.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldEdit<F> {
+    pub field: F,
+    /// The field's name in the struct
+    pub name: &'static str,
+    pub old: FieldValue,
+    pub new: FieldValue,
+    /// When the change was made, in milliseconds since the Unix epoch
+    pub timestamp_ms: f64,
+}
+
+impl<F> FieldEdit<F> {
+    /// A change made just now
+    pub fn now(field: F, name: &'static str, old: FieldValue, new: FieldValue) -> Self {
+        Self {
+            field,
+            name,
+            old,
+            new,
+            timestamp_ms: now_ms(),
+        }
+    }
+}
+
+// The browser's clock, or the system's when the form is driven natively, i.e. by its tester
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn now_ms() -> f64 {
+    js_sys::Date::now()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn now_ms() -> f64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0.0, |elapsed| elapsed.as_secs_f64() * 1000.0)
+}
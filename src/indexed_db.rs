@@ -0,0 +1,188 @@
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{IdbDatabase, IdbObjectStore, IdbRequest, IdbTransaction, IdbTransactionMode};
+use yew::Callback;
+
+use crate::FormStorage;
+
+/// Keeps values in an IndexedDB object store, for values too large for `localStorage`, i.e. drafts
+/// of forms holding long text. Needs the `indexed-db` feature.
+///
+/// The database is opened on each call, creating it and its object store the first time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedDbStorage {
+    database: String,
+    store: String,
+}
+
+impl IndexedDbStorage {
+    /// Store values in the `formula_y` object store of the named database
+    pub fn new(database: impl Into<String>) -> Self {
+        Self::with_store(database, "formula_y")
+    }
+
+    /// Store values in the named object store of the named database
+    pub fn with_store(database: impl Into<String>, store: impl Into<String>) -> Self {
+        IndexedDbStorage {
+            database: database.into(),
+            store: store.into(),
+        }
+    }
+
+    // Open the database, and a transaction on the object store once it is open
+    fn transaction(
+        &self,
+        mode: IdbTransactionMode,
+        done: impl FnOnce(Result<IdbTransaction, String>) + 'static,
+    ) {
+        let factory = web_sys::window()
+            .ok_or_else(|| "There's no window to store values in".to_string())
+            .and_then(|window| {
+                window
+                    .indexed_db()
+                    .map_err(|error| format!("Couldn't access IndexedDB: {:?}", error))?
+                    .ok_or_else(|| "The browser doesn't offer IndexedDB".to_string())
+            });
+        let request = match factory.and_then(|factory| {
+            factory
+                .open_with_u32(&self.database, 1)
+                .map_err(|error| format!("Couldn't open {}: {:?}", self.database, error))
+        }) {
+            Ok(request) => request,
+            Err(error) => return done(Err(error)),
+        };
+
+        // The object store can only be created while the database is first set up
+        let store = self.store.clone();
+        let upgrade_request = request.clone();
+        let upgrade = Closure::once_into_js(move || {
+            if let Ok(database) = upgrade_request.result() {
+                let database = database.unchecked_into::<IdbDatabase>();
+                if !database.object_store_names().contains(&store) {
+                    let _ = database.create_object_store(&store);
+                }
+            }
+        });
+        request.set_onupgradeneeded(Some(upgrade.unchecked_ref()));
+
+        let store = self.store.clone();
+        on_request(&request, move |result| {
+            done(result.and_then(|database| {
+                database
+                    .unchecked_into::<IdbDatabase>()
+                    .transaction_with_str_and_mode(&store, mode)
+                    .map_err(|error| {
+                        format!("Couldn't start a transaction on {}: {:?}", store, error)
+                    })
+            }))
+        });
+    }
+}
+
+// Hand a request's result to done once it succeeds or fails. Only one of the two handlers is ever
+// called, and the closure of the other is leaked, which is the cost of wasm-bindgen's one shot
+// closures.
+fn on_request(request: &IdbRequest, done: impl FnOnce(Result<JsValue, String>) + 'static) {
+    let done = std::rc::Rc::new(std::cell::RefCell::new(Some(done)));
+    let success_request = request.clone();
+    let success_done = done.clone();
+    let success = Closure::once_into_js(move || {
+        if let Some(done) = success_done.borrow_mut().take() {
+            done(
+                success_request
+                    .result()
+                    .map_err(|error| format!("IndexedDB request failed: {:?}", error)),
+            );
+        }
+    });
+    let error_request = request.clone();
+    let error = Closure::once_into_js(move || {
+        if let Some(done) = done.borrow_mut().take() {
+            done(Err(format!(
+                "IndexedDB request failed: {:?}",
+                error_request.error()
+            )));
+        }
+    });
+    request.set_onsuccess(Some(success.unchecked_ref()));
+    request.set_onerror(Some(error.unchecked_ref()));
+}
+
+// Hand whether a transaction was written to done once it completes or fails
+fn on_transaction(transaction: &IdbTransaction, done: Callback<Result<(), String>>) {
+    let complete_done = done.clone();
+    let complete = Closure::once_into_js(move || complete_done.emit(Ok(())));
+    let error_transaction = transaction.clone();
+    let error = Closure::once_into_js(move || {
+        done.emit(Err(format!(
+            "IndexedDB transaction failed: {:?}",
+            error_transaction.error()
+        )))
+    });
+    transaction.set_oncomplete(Some(complete.unchecked_ref()));
+    transaction.set_onerror(Some(error.unchecked_ref()));
+}
+
+fn object_store(transaction: &IdbTransaction, store: &str) -> Result<IdbObjectStore, String> {
+    transaction
+        .object_store(store)
+        .map_err(|error| format!("Couldn't open {}: {:?}", store, error))
+}
+
+impl FormStorage for IndexedDbStorage {
+    fn get(&self, key: &str, done: Callback<Result<Option<String>, String>>) {
+        let key = key.to_string();
+        let store = self.store.clone();
+        self.transaction(IdbTransactionMode::Readonly, move |transaction| {
+            let request = transaction
+                .and_then(|transaction| object_store(&transaction, &store))
+                .and_then(|store| {
+                    store.get(&JsValue::from_str(&key)).map_err(|error| {
+                        format!("Couldn't read {} from IndexedDB: {:?}", key, error)
+                    })
+                });
+            match request {
+                Ok(request) => on_request(&request, move |value| {
+                    done.emit(value.map(|value| value.as_string()))
+                }),
+                Err(error) => done.emit(Err(error)),
+            }
+        });
+    }
+
+    fn set(&self, key: &str, value: String, done: Callback<Result<(), String>>) {
+        let key = key.to_string();
+        let store = self.store.clone();
+        self.transaction(IdbTransactionMode::Readwrite, move |transaction| {
+            let written = transaction.and_then(|transaction| {
+                object_store(&transaction, &store)?
+                    .put_with_key(&JsValue::from_str(&value), &JsValue::from_str(&key))
+                    .map_err(|error| format!("Couldn't write {} to IndexedDB: {:?}", key, error))?;
+                Ok(transaction)
+            });
+            match written {
+                Ok(transaction) => on_transaction(&transaction, done),
+                Err(error) => done.emit(Err(error)),
+            }
+        });
+    }
+
+    fn remove(&self, key: &str, done: Callback<Result<(), String>>) {
+        let key = key.to_string();
+        let store = self.store.clone();
+        self.transaction(IdbTransactionMode::Readwrite, move |transaction| {
+            let removed = transaction.and_then(|transaction| {
+                object_store(&transaction, &store)?
+                    .delete(&JsValue::from_str(&key))
+                    .map_err(|error| {
+                        format!("Couldn't remove {} from IndexedDB: {:?}", key, error)
+                    })?;
+                Ok(transaction)
+            });
+            match removed {
+                Ok(transaction) => on_transaction(&transaction, done),
+                Err(error) => done.emit(Err(error)),
+            }
+        });
+    }
+}
@@ -0,0 +1,15 @@
+/// What a form does when its `init` prop changes after it was created, i.e. when another user
+/// saves the record being edited. Set with the form's `init_merge` prop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum InitMerge {
+    /// Keep showing the values the form already has
+    Ignore,
+    /// Replace every field with its new value, including ones the user has changed
+    Overwrite,
+    /// Take the new values of the fields the user hasn't changed, keeping their edits
+    #[default]
+    KeepEdits,
+    /// Take the new values of the fields the user hasn't changed, and ask whether to replace the
+    /// ones they have
+    Prompt,
+}
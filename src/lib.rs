@@ -6,11 +6,11 @@
 //! - [x] Support passing an onsubmit function as a prop
 //! - [x] Support for initializing form with default values
 //! - [x] Support for custom css styling
-//! - [ ] Support for regex validation for String fields
-//! - [ ] Support for number type fields with automatic parsing validation
+//! - [x] Support for regex validation for String fields
+//! - [x] Support for number type fields with automatic parsing validation
 //! - [x] Support for required and optional fields with Option type
 //! - [x] Auto applied classes for required fields after submit attempt
-//! - [ ] Clean up how user imports requirements
+//! - [x] Clean up how user imports requirements
 //!
 //! # How
 //! Basically, the form will maintain an instance of the struct where each value is equal to the current input
@@ -18,6 +18,189 @@
 //! is the type the form is derived from for the onsubmit. For instance,
 //! said function might make a POST request with the struct as the request body.
 //!
+//! # Field types
+//! - `String` renders a text input, which must be filled in to submit.
+//! - `bool` renders a checkbox, which must be checked to submit.
+//! - Integer and float fields (`u32`, `i64`, `f64` and so on) render a number input. Text which
+//!   doesn't parse is kept in the input, which gets an `invalid` class, and blocks submitting until
+//!   it is fixed.
+//! - With the `chrono` feature enabled, `chrono::NaiveDate` renders a date input and
+//!   `chrono::NaiveDateTime` a datetime-local input, parsed from the browser's value the same way
+//!   as numbers, with text which doesn't parse flagged as `invalid`. A required date starts out
+//!   on the type's default (1970-01-01), so most forms want an `Option` or an `init` value.
+//! - Fieldless enums which `#[derive(YFormSelect)]` render a select with an option per variant,
+//!   starting out on the first. Each variant is labelled with its Title Cased name, or with
+//!   `#[yform(label = "...")]` on the variant.
+//! - With the `chrono-tz` feature enabled, `chrono_tz::Tz` renders a select of time zones grouped
+//!   by region, with a search box above it narrowing them down by name, and starts out on the
+//!   browser's zone, as given by `detect_time_zone`. Zones outside of the regions, i.e. the
+//!   `US/Eastern` style aliases, aren't offered but are kept when given as the init value.
+//! - Structs which derive `YForm` themselves, on fields marked `#[yform(nested)]`, render their
+//!   own form inside a `<fieldset>` (see below).
+//! - `Option` of any of these apart from nested structs is optional, with an empty input or
+//!   unchecked box giving `None`.
+//! - `Vec<String>` renders a text input per item, and `Vec` of a nested struct a nested form per
+//!   item, each with a "Remove" button and followed by an "Add" button, inside a `<fieldset>`
+//!   with a `formula-y-repeat` class. A `Vec` starts out empty and may be submitted empty.
+//!
+//! ```ignore
+//! #[derive(Clone, PartialEq, YFormSelect)]
+//! pub enum Plan {
+//!     Free,
+//!     Pro,
+//!     #[yform(label = "Enterprise (billed yearly)")]
+//!     Enterprise,
+//! }
+//! ```
+//!
+//! Props, callbacks and errors which refer to a field use the `DataField` enum the derive
+//! generates, with a variant per field (i.e. `DataField::AgreeToTerms`). It displays as the
+//! field's name (`agree_to_terms`) and parses back from it, failing with `UnknownField` for any
+//! other name, i.e. for fields named in a URL or a server's validation errors.
+//!
+//! # Props
+//! Every generated component takes the same optional props. Changing a prop after the form is
+//! mounted takes effect on its next render, without remounting it. Turning on `disabled` also
+//! cancels a pending auto submit and an inline edit, and setting `enforce_required_fields` to
+//! `RequiredPolicy::Ignore` clears the required field warnings.
+//!
+//! - `onsubmit: Callback<T>` is called with a clone of the struct when the form is submitted.
+//! - `onsubmit_rc: Callback<Rc<T>>` is called with the struct behind an `Rc` instead, which avoids
+//!   cloning very large structs on submit. The form only copies the struct again if it is edited
+//!   while the callback's owner still holds on to it.
+//! - `onsavedraft: Callback<T>` adds a "Save draft" button next to submit, which is called with a
+//!   clone of the struct without checking required fields, so partially completed forms can be
+//!   saved.
+//! - `init: T` sets the initial value of the form.
+//! - `onchange: Callback<T>` is called with a clone of the struct whenever a field changes, i.e.
+//!   for live previews or autosaving drafts.
+//! - `value: T` makes the form controlled: it shows whatever the prop holds, so the parent owns
+//!   the value, typically updating it from `onchange`. With `no_partial_eq` the form takes the value
+//!   again whenever the parent rerenders.
+//! - `onsnapshot: Callback<DataFormState>` is called as the form is unmounted with everything it
+//!   holds beyond its props: the values, text which didn't parse, errors, which fields were changed
+//!   and the step of a multi-step form. Passing it back as `snapshot: DataFormState` when the form
+//!   is mounted again picks up where the user left off, i.e. for tabs which keep forms in progress
+//!   while another tab is showing. The prop is only read as the form is created, in place of
+//!   `init`. The component's `snapshot()` and `restore()` methods do the same.
+//! - `id_prefix: String` replaces the generated instance id on the form, and is prepended to the
+//!   ids of every input (i.e. `signup` gives `signup-email`), for deterministic selectors in end to
+//!   end tests.
+//! - `escape_reverts: bool` makes pressing Escape in an input revert its field to the value it had
+//!   when the input was focused, undoing any edits made since. Defaults to false.
+//! - `disabled: bool` renders every input without letting it be edited, and disables the submit
+//!   and action buttons, i.e. while a save is in flight. Defaults to false.
+//! - `onerror: Callback<String>` is called when something the form does behind the scenes fails,
+//!   i.e. a `regex` the app's regex crate won't compile, or a browser call throwing. Rather than
+//!   panicking, the form shows the error in a `formula-y-internal-error` banner and carries on,
+//!   letting values through a regex it couldn't compile.
+//! - `announce_submit: bool` renders a visually hidden `formula-y-announcement` live region, which
+//!   screen readers announce after each submit attempt: "Form submitted", or the number of errors
+//!   found, i.e. "3 errors found". Defaults to true. `submitted_announcement: String` replaces the
+//!   first message, and `errors_announcement: ErrorsAnnouncement`, built from any
+//!   `Fn(usize) -> String`, words the second, i.e. to translate them.
+//! - `failed_submits: usize` is how many submits in a row the server has rejected, i.e. wrong
+//!   passwords on a login form, counted by the parent since the form only knows about its own
+//!   checks. With `submit_cooldown_ms: u32` set, each time it goes up the form can't be submitted
+//!   for that long, doubling with every failure in a row up to `submit_cooldown_max_ms: u32`
+//!   (5 minutes by default). The submit button is disabled and a `formula-y-cooldown` paragraph
+//!   reads "Try again in 30s" meanwhile. `oncooldown: Callback<u32>` is called with the time left
+//!   in milliseconds as it counts down, and the component's `cooldown_remaining_ms()` method
+//!   gives it too. Setting `failed_submits` back to 0 ends the cooldown.
+//! - `deadline: Deadline` gives the form a time limit, i.e. for a quiz or an exam, made with
+//!   `Deadline::in_ms(30.0 * 60.0 * 1000.0)` when it starts. A `formula-y-countdown` paragraph
+//!   reads "Time remaining 29:59" until it passes. Then the fields turn read only, the submit
+//!   button is disabled, the form gets a `formula-y-expired` class and the paragraph reads
+//!   "Time is up". `onexpire: Callback<DataStruct>` is passed the values as they stand, so the
+//!   parent can keep them. Resetting the form doesn't give it more time, but a later deadline
+//!   does. The component's `is_expired()` method tells whether time is up.
+//! - `aria_label: String` names the form for screen readers, and `described_by: String` points
+//!   its `aria-describedby` at the ids of elements describing it, so that several forms on one
+//!   page can be told apart. `description: String` renders a `formula-y-description` paragraph
+//!   above the fields, which the form is described by as well.
+//! - `loading: bool` shows every field as a disabled placeholder with a `formula-y-loading` class
+//!   and `aria-busy`, and disables the submit button, while `init` is still being fetched.
+//!   `loading_fields: Vec<DataField>` does the same for just the fields listed. Once a field stops
+//!   loading it takes its value from `init`, unless the user has already changed it.
+//! - `init_merge: InitMerge` decides what happens when `init` changes after the form was
+//!   created, i.e. when another user saves the record being edited. `KeepEdits`, the default,
+//!   takes the new values of the fields the user hasn't changed. `Overwrite` takes every new value,
+//!   `Ignore` none of them, and `Prompt` takes those the user hasn't changed and asks whether to
+//!   load the rest, in a `formula-y-init-changed` notice at the top of the form. Forms with
+//!   `value`, `shared_state` or `no_partial_eq` don't merge, since they are either controlled,
+//!   share the struct itself, or can't tell a new `init` from the old one.
+//! - `submit_label: String` replaces the "Submit" text of the submit button, and `submit_class:
+//!   String` adds classes to it alongside its own `formula-y-submit` class.
+//! - `submit_disabled: bool` disables the submit button, i.e. while a request is in flight,
+//!   leaving the inputs editable. Defaults to false.
+//! - `reset_button: bool` renders a Reset button (with a `formula-y-reset` class) which puts the
+//!   form back to its `init` value, or to `T::new()` without one, clearing any errors. Defaults
+//!   to false.
+//! - `clear_on_submit: bool` resets the form the same way after `onsubmit` is called. Defaults to
+//!   false.
+//! - `reset_count: usize` resets the form whenever it changes, so a parent can clear it from
+//!   outside, i.e. by incrementing a counter once its POST request succeeds. A `shared_state`
+//!   form edits the value it was passed in place, so a reset clears it back to `T::new()`.
+//! - `submitted_reset_count: usize` clears the form's submitted state whenever it changes, keeping
+//!   its values, i.e. so a "Saved" message can be hidden once the user starts on the next entry.
+//! - `keyboard_navigation: bool` moves focus the way desktop data entry software does. Enter moves
+//!   to the next input or button instead of submitting, apart from in textareas, and the arrow
+//!   keys move between the radios of a group and between checkboxes. Defaults to false. Either
+//!   way, only the chosen radio of a group (or its first, while none is) is in the tab order.
+//! - `auto_submit_after_ms: u32` submits the form once the user has stopped typing for that many
+//!   milliseconds, for filter and search forms which should update their results as the user
+//!   types. Each input restarts the countdown. The submit goes through the same checks as
+//!   clicking the submit button, and a blocked one flags its fields the same way too. Text being
+//!   composed through an IME, i.e. Chinese or Japanese input, holds off the countdown until it
+//!   is picked.
+//! - `oncompositionstart: Callback<CompositionEvent>` and `oncompositionend:
+//!   Callback<CompositionEvent>` are called when the user starts and finishes composing text
+//!   through an IME in one of the form's inputs. Neither Enter nor Escape act on the form while
+//!   composing, as the IME uses them to pick or cancel the text.
+//! - `onautofill: Callback<DataField>` is called with each field the browser autofills. The
+//!   autofilled value is stored as soon as the browser fills it in, rather than once the user
+//!   leaves the input, so the required field checks pass without the user clicking through each
+//!   field. Chrome and Safari are noticed through an animation on `:-webkit-autofill` inputs, which
+//!   the form adds a `<style>` for, and Firefox through its input events.
+//! - `ontelemetry: Callback<TelemetryEvent<DataField>>` is passed timings of how long each field
+//!   and the whole form took to fill in, as described under Telemetry below.
+//! - `lock_on_submit: bool` makes every field read only once the form is submitted, for a
+//!   "submitted application" which can still be changed. The buttons are replaced by an "Edit"
+//!   button with a `formula-y-unlock` class, which makes the fields editable again and calls
+//!   `onunlock: Callback<()>`, and the form gets a `formula-y-locked` class meanwhile. The
+//!   component's `is_locked()` method gives the same state. Search forms aren't locked.
+//! - `theme: Theme` adds a theme's classes to the form's elements, as described under Styling.
+//! - `density: Density` spaces the fields as `Density::Compact`, `Comfortable` or `Spacious`, in
+//!   place of the theme's density.
+//! - `labels: HashMap<DataField, String>` replaces the labels of the fields it has an entry for,
+//!   i.e. for a white labelled deployment or an A/B test, without changing the struct. The rest
+//!   keep their own. The labels are looked up as the form renders, in the form and its review
+//!   step, so changing the prop relabels a mounted form. Placeholders showing the label, and the
+//!   "Edit" and "Change" buttons named after it, follow along.
+//! - `onabandon: Callback<Vec<DataField>>` is called with the fields holding a value when the
+//!   form is left changed but unsubmitted, either unmounted or hidden along with the page as the
+//!   user navigates away, so funnels can measure where users give up. It is called once for each
+//!   run of changes, so coming back to the page and leaving again unchanged isn't counted twice.
+//! - `label_placeholders: bool` shows the labels of text and number inputs as their placeholders
+//!   instead, for compact layouts. The labels stay in the page for screen readers, with a
+//!   `formula-y-placeholder-label` class, but are hidden from view. Inputs with a `placeholder`
+//!   attribute keep their label. Defaults to false.
+//! - `inline_edit: bool` shows each field as text with an edit button, for settings pages. Clicking
+//!   the button swaps in the field's input, and leaving the input or pressing Enter confirms the
+//!   edit, unless the value isn't valid (i.e. a number which doesn't parse). Radio groups, nested
+//!   forms and `Vec` fields are always shown as inputs. Defaults to false.
+//! - `onfieldchange: Callback<(DataField, T)>` is called with the field and a clone of the struct
+//!   whenever an inline edit is confirmed which changed the field.
+//! - `field_policy: FieldPolicy<DataField>` decides at render time whether each field is
+//!   `FieldAccess::Hidden`, `ReadOnly` or `Editable`, so one form can serve users with different
+//!   roles. A policy is built from any `Fn(DataField) -> FieldAccess`.
+//!   Hidden fields still count towards the required field checks, so give them a value with
+//!   `init`.
+//! - `enforce_required_fields: RequiredPolicy` decides what happens to required fields left empty
+//!   on submit. `Enforce` (the default) blocks the submit and flags them, `WarnOnly` flags them
+//!   but calls `onsubmit` anyway, i.e. for saving drafts, and `Ignore` submits without flagging
+//!   anything.
+//!
 //! # Why
 //! One of the cool things about using Rust for web is that you can use the same language on the frontend and
 //! the backend, just like JavaScript. One of the driving use cases for this library is to define a struct one time in a
@@ -30,400 +213,572 @@
 //! html, run `trunk serve --open` from the usage directory. Try submitting the form and you should see a log message from the provided onsubmit
 //! in the console.
 //!
+//! # Imports
+//! The generated component relies on a handful of items from yew, web-sys and wasm-bindgen.
+//! A single `use formula_y::prelude::*;` brings all of them into scope along with the derive macro.
+//!
+//! ```ignore
+//! use formula_y::prelude::*;
+//!
+//! #[derive(Clone, PartialEq, YForm)]
+//! pub struct Data {
+//!     pub email: String,
+//! }
+//! ```
+//!
 //! # Styling
 //! For the moment, the easiest way to style the elements is to use the auto-generated classnames. Each field and label get specific class
 //! names and general class names for hooking into.
 //!
+//! The specific ones are also constants on the component, so stylesheets built in Rust (i.e. with
+//! stylist) and tests don't have to spell them out: `DataForm::FORM_CLASS` is `data-form`, and
+//! `DataForm::EMAIL_LABEL_CLASS` and `DataForm::EMAIL_INPUT_CLASS` are `email-label` and
+//! `email-input`. `DataForm::CLASSES` lists the name, label class and input class of every
+//! rendered field.
+//!
+//! When a submit is blocked because required fields are missing, the form gets a
+//! `formula-y-submit-failed` class until the user next changes a field, which can drive an
+//! animation drawing attention to the errors.
+//!
+//! Once a submit goes through, the form gets a `formula-y-submitted` class, which stays until the
+//! form is reset or its `submitted_reset_count` prop changes. `clear_on_submit` keeps the class on
+//! the emptied form. The component's `is_submitted()` method gives the same state.
+//!
+//! Every input also gets an id which its label points to. Ids are unique to each mounted instance of
+//! a form (i.e. `data-form-0-email`, `data-form-1-email`), so the same form can safely appear more than
+//! once on a page. Pass the `id_prefix` prop to choose the ids yourself.
+//!
+//! Each field's errors are rendered in a `<div class="formula-y-errors" aria-live="polite">`
+//! (i.e. with id `data-form-0-email-error`) which screen readers announce as it changes, and which
+//! the input points to with `aria-describedby`. Required inputs get `aria-required`, and inputs
+//! with the `required` or `invalid` class also get `aria-invalid`. Once a submit has been attempted
+//! an empty required field shows "This field is required" in an element with the
+//! `formula-y-required-error` class, or the field's own `required_msg`. The native `required` attribute isn't used, so the browser's
+//! own validation popups don't replace the form's messages.
+//!
+//! Colors alone don't survive forced colors modes like Windows High Contrast, so a flagged field
+//! also gets a `<span class="formula-y-indicator" aria-hidden="true">` right after its label,
+//! reading "⚠ Required" or "⚠ Invalid". Screen readers skip it, since they already hear the
+//! errors. Pass `state_indicators={false}` to leave it out.
+//!
+//! A `Theme` adds classes of its own to the form, its items, labels, inputs, errors and buttons,
+//! i.e. `Theme::bootstrap()` or `Theme::tailwind()` for those frameworks' form classes, or
+//! `Theme::light()` and `Theme::dark()` which only add a `formula-y-theme-light` or
+//! `formula-y-theme-dark` class to the form. Pass one as the `theme` prop, or provide one to every
+//! form below a `ContextProvider<Theme>`, which the prop takes precedence over. The classes are
+//! added as the form renders, so switching themes restyles forms which are already mounted,
+//! without losing what has been entered.
+//!
+//! A theme's `density`, or the `density` prop in its place, adds a `formula-y-density-compact`,
+//! `formula-y-density-comfortable` (the default) or `formula-y-density-spacious` class to the form
+//! and to the element wrapping each field, so a stylesheet can tighten or loosen every form's
+//! layout from those classes alone.
+//!
 //! To see the expanded yew code for the example, run `cargo expand --bin usage`.
-
-use convert_case::{Case, Casing};
-use proc_macro::TokenStream;
-use quote::quote;
-use syn::{parse_macro_input, DeriveInput};
-use util::{
-    append_to_ident, field_is_bool, field_is_option, field_is_option_bool, field_is_option_string,
-    field_is_string, get_struct_fields,
-};
-
-// Utilities
-mod util;
-
-// Generate the MSG variants responsible for updating each field.
-// first_name -> UpdateFirstName
-fn get_update_field_msg_variant_ident(field: &syn::Field, span_ident: &syn::Ident) -> syn::Ident {
-    let field_ident = field.ident.clone().unwrap();
-    let msg_variant = format!("update_{}", field_ident).to_case(Case::UpperCamel);
-    syn::Ident::new(&msg_variant, span_ident.span())
-}
-
-// This function returns the class values for labels and inputs of both String and bool fields
-fn get_label_and_input_classes(field_ident: &syn::Ident) -> (String, String, String, String) {
-    let txt_label_class = format!(
-        "{} formula-y-txt-label",
-        format!("{}-label", field_ident).to_case(Case::Kebab)
-    );
-    let txt_input_class = format!(
-        "{} formula-y-txt-input",
-        format!("{}-input", field_ident).to_case(Case::Kebab)
-    );
-    let bool_label_class = format!(
-        "{} formula-y-checkbox-label",
-        format!("{}-label", field_ident).to_case(Case::Kebab)
-    );
-    let bool_input_class = format!(
-        "{} formula-y-checkbox",
-        format!("{}-input", field_ident).to_case(Case::Kebab)
-    );
-    (
-        txt_label_class,
-        txt_input_class,
-        bool_label_class,
-        bool_input_class,
-    )
-}
-
-fn get_class_getter_method_idents(
-    field_ident: &syn::Ident,
-    span_ident: &syn::Ident,
-) -> (syn::Ident, syn::Ident) {
-    let method_name_label = format!("get_class_for_{}_label", field_ident);
-    let method_name_label_ident = syn::Ident::new(&method_name_label, span_ident.span());
-    let method_name_input = format!("get_class_for_{}", field_ident);
-    let method_name_input_ident = syn::Ident::new(&method_name_input, span_ident.span());
-    (method_name_label_ident, method_name_input_ident)
-}
-
-#[proc_macro_derive(YForm)]
-pub fn derive(input: TokenStream) -> TokenStream {
-    let ast = parse_macro_input!(input as DeriveInput);
-
-    // We are producing a yew component based on the input struct, so we will need
-    // idents for the component struct, its msg enum, and its prop struct.
-    let input_struct_ident = &ast.ident;
-    let component_ident = append_to_ident(input_struct_ident, "Form"); // Data -> DataForm
-    let component_msg_ident = append_to_ident(&component_ident, "Msg"); // Data -> DataFormMsg
-    let component_prop_ident = append_to_ident(&component_ident, "Props"); // Data -> DataFormProps
-
-    // Get the fields of the struct (Not implemented for Enums or TupleStructs)
-    let fields = get_struct_fields(&ast);
-
-    // For convenience, we generate a standard new() method for the struct.
-    // To do so, we iterate over the supported types and produce the appropriate line.
-    let component_field_inits = fields.iter().map(|field| {
-        let field_ident = field.ident.clone().unwrap();
-        if field_is_string(field) {
-            quote! { #field_ident: String::new() }
-        } else if field_is_bool(field) {
-            quote! { #field_ident: false }
-        } else if field_is_option(field) {
-            quote! { #field_ident: None }
-        } else {
-            panic!("Field type not supported");
-        }
-    });
-
-    // Create the msg variants for updating each field
-    let msg_variants = fields.iter().map(|field| {
-        let field_type = field.ty.clone();
-        let msg_variant_ident = get_update_field_msg_variant_ident(field, input_struct_ident);
-        quote! { #msg_variant_ident(#field_type) }
-    });
-
-    // Create the match arms for the update fn for updating each field
-    let match_arms_update = fields.iter().map(|field| {
-        let field_ident = field.ident.clone().unwrap();
-        let msg_variant_ident = get_update_field_msg_variant_ident(field, input_struct_ident);
-
-        quote! { #component_msg_ident::#msg_variant_ident(item) => {
-            self.inner.#field_ident = item;
-            false
-        } }
-    });
-
-    // We need to have a way to check if the required fields have all been provided, so we generate
-    // a series of if checks to confirm string fields are not empty strings and checkboxes are
-    // checked.
-    let required_string_fields: Vec<&syn::Field> = fields
-        .iter()
-        .filter(|field| field_is_string(field))
-        .collect();
-    let checks = required_string_fields.iter().map(|field| {
-        let field_ident = field.ident.clone().unwrap();
-        quote! {
-            if self.inner.#field_ident == "" {
-                return false;
-            }
-        }
-    });
-
-    let required_bool_fields: Vec<&syn::Field> =
-        fields.iter().filter(|field| field_is_bool(field)).collect();
-    let bool_checks = required_bool_fields.iter().map(|field| {
-        let field_ident = field.ident.clone().unwrap();
-        quote! {
-            if !self.inner.#field_ident {
-                return false;
-            }
-        }
-    });
-
-    // Now we are generating methods thats give us the class attributes text for each field. If a form submit occurs
-    // and a required field is empty/unchecked, it gets a class of required appended to it.
-    let get_class_methods = fields.iter().map(|field| {
-        let field_ident = field.ident.clone().unwrap();
-
-        let (txt_label_class, txt_input_class, bool_label_class, bool_input_class) =
-            get_label_and_input_classes(&field_ident);
-
-        let (method_name_label_ident, method_name_input_ident) =
-            get_class_getter_method_idents(&field_ident, input_struct_ident);
-
-        if field_is_string(field) {
-            quote! {
-                pub fn #method_name_label_ident(&self) -> String {
-                    match self.display_required_warnings && self.inner.#field_ident == "" {
-                        true => {
-                            let mut base_name = String::from(#txt_label_class);
-                            base_name.push_str(" required");
-                            base_name
-                        },
-                        false => #txt_label_class.to_string()
-                    }
-                }
-
-                pub fn #method_name_input_ident(&self) -> String {
-                    match self.display_required_warnings && self.inner.#field_ident == "" {
-                        true => {
-                            let mut base_name = String::from(#txt_input_class);
-                            base_name.push_str(" required");
-                            base_name
-                        },
-                        false => #txt_input_class.to_string()
-                    }
-                }
-            }
-        } else if field_is_bool(field) {
-            quote! {
-                pub fn #method_name_label_ident(&self) -> String {
-                    match self.display_required_warnings && !self.inner.#field_ident {
-                        true => {
-                            let mut base_name = String::from(#bool_label_class);
-                            base_name.push_str(" required");
-                            base_name
-                        },
-                        false => #bool_label_class.to_string()
-                    }
-                }
-
-                pub fn #method_name_input_ident(&self) -> String {
-                    match self.display_required_warnings && !self.inner.#field_ident{
-                        true => {
-                            let mut base_name = String::from(#bool_input_class);
-                            base_name.push_str(" required");
-                            base_name
-                        },
-                        false => #bool_input_class.to_string()
-                    }
-                }
-            }
-        } else {
-            quote! {}
-        }
-    });
-
-    // Create the actual html elements for the inside of the form for the view fn
-    let form_fields = fields.iter().map(|field| {
-        let field_ident = field.ident.clone().unwrap();
-        let msg_variant_ident = get_update_field_msg_variant_ident(field, input_struct_ident);
-
-        let label = format!("{}", field_ident).to_case(Case::Title);
-
-        let (txt_label_class, txt_input_class, bool_label_class, bool_input_class) = get_label_and_input_classes(&field_ident);
-
-        let (method_name_label_ident, method_name_input_ident) = get_class_getter_method_idents(&field_ident, input_struct_ident);
-
-        if field_is_string(field) {
-            quote! {
-                <div class="formula-y-form-item">
-                <label class={self.#method_name_label_ident()}>{#label}</label>
-                <input class={self.#method_name_input_ident()} type="text" value={self.inner.#field_ident.clone()} onchange={ctx.link().callback(move |event: Event| {
-                    let new_value = event
-                        .target()
-                        .unwrap()
-                        .unchecked_into::<HtmlInputElement>()
-                        .value();
-    
-                    #component_msg_ident::#msg_variant_ident(new_value)
-                })} />
-                </div>
-            }
-        } else if field_is_bool(field) {
-
-            quote! {
-                <div class="formula-y-form-item">
-                <label class={self.#method_name_label_ident()}>{#label}</label>
-                <input class={self.#method_name_input_ident()} type="checkbox" checked={self.inner.#field_ident} onchange={ctx.link().callback(move |event: Event| {
-                    let new_value = event
-                        .target()
-                        .unwrap()
-                        .unchecked_into::<HtmlInputElement>()
-                        .checked();
-    
-                    #component_msg_ident::#msg_variant_ident(new_value)
-                })} />
-                </div>
-            }
-        } else if field_is_option_string(field) {
-
-            quote! {
-                <div class="formula-y-form-item">
-                <label class={#txt_label_class}>{#label}</label>
-                <input class={#txt_input_class} type="text" value={self.inner.#field_ident.clone().unwrap_or_default()} onchange={ctx.link().callback(move |event: Event| {
-                    let new_value = event
-                        .target()
-                        .unwrap()
-                        .unchecked_into::<HtmlInputElement>()
-                        .value();
-    
-                    if new_value == "" {
-                        #component_msg_ident::#msg_variant_ident(None)
-                    } else {
-                        #component_msg_ident::#msg_variant_ident(Some(new_value))
-                    }
-                })} />
-                </div>
-            }
-        } else if field_is_option_bool(field) {
-
-            quote! {
-                <div class="formula-y-form-item">
-                <label class={#bool_label_class}>{#label}</label>
-                <input class={#bool_input_class} type="checkbox" checked={self.inner.#field_ident.unwrap_or_default()} onchange={ctx.link().callback(move |event: Event| {
-                    let new_value = event
-                        .target()
-                        .unwrap()
-                        .unchecked_into::<HtmlInputElement>()
-                        .checked();
-    
-                    #component_msg_ident::#msg_variant_ident(Some(new_value))
-                })} />
-                </div>
-            }
-        } else {
-            quote! {
-                <p>{"type not supported"}</p> 
-            }
-        }
-    });
-
-    let form_class = format!(
-        "{}-form formula-y-form",
-        format!("{}", input_struct_ident).to_case(Case::Kebab)
-    );
-
-    quote! {
-
-        impl #input_struct_ident {
-            pub fn new() -> Self {
-                Self {
-                    #(#component_field_inits,)*
-                }
-            }
-        }
-
-        pub struct #component_ident {
-            inner: #input_struct_ident,
-            display_required_warnings: bool,
-            submitted: bool
-        }
-
-        impl #component_ident {
-            pub fn required_components_provided(&self) -> bool {
-                #(#checks)* 
-
-                #(#bool_checks)* 
-
-                true
-            }
-
-            #(#get_class_methods)*
-        }
-
-        pub enum #component_msg_ident {
-            #(#msg_variants,)*
-
-            OnSubmit,
-            DisplayRequiredWarnings
-        }
-
-        #[derive(PartialEq, Properties)]
-        pub struct #component_prop_ident {
-            pub onsubmit: Callback<#input_struct_ident>,
-            pub init: Option<#input_struct_ident>,
-            pub enforce_required_fields: Option<bool>
-        }
-
-        impl Component for #component_ident {
-            type Message = #component_msg_ident;
-            type Properties = #component_prop_ident;
-
-            fn create(ctx: &Context<Self>) -> Self {
-
-                let inner = if let Some(init) = &ctx.props().init {
-                    init.clone()
-                } else {
-                    #input_struct_ident::new()
-                };
-
-                Self {
-                    inner,
-                    submitted: false,
-                    display_required_warnings: false
-                }
-            }
-
-            fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
-
-                match msg {
-                    #(#match_arms_update,)*
-
-                    #component_msg_ident::OnSubmit => {
-
-                        let enforce_required = ctx.props().enforce_required_fields.unwrap_or_else(|| true);
-
-                        if self.required_components_provided() || !enforce_required {
-                            ctx.props().onsubmit.emit(self.inner.clone());
-                            self.submitted = true;
-                            self.display_required_warnings = false;
-                        } else {
-                            ctx.link().send_message(#component_msg_ident::DisplayRequiredWarnings);
-                        }
-                        true
-                    },
-                    #component_msg_ident::DisplayRequiredWarnings => {
-                        self.display_required_warnings = true;
-                        true
-                    }
-                }
-            }
-
-            fn view(&self, ctx: &Context<Self>) -> Html {
-
-                let link = ctx.link();
-                html! {
-                    <form class={#form_class} onsubmit={link.callback(|e: FocusEvent| {
-                        e.prevent_default();
-
-                        #component_msg_ident::OnSubmit
-                    })}>
-
-                        #(#form_fields)*
-
-                        <button>{"Submit"}</button>
-                    </form>
-                }
-            }
-        }
-    }
-    .into()
+//!
+//! # Selectors
+//! Test suites and stylesheets can rely on `data-formulay-*` attributes, which are part of the
+//! crate's stable API and only change with a major version, unlike the class names and ids. Every
+//! element of a field carries `data-formulay-field` with the field's name in the struct, i.e.
+//! `data-formulay-field="email"`, and `data-formulay-role` saying what it is:
+//!
+//! - `field` on the element wrapping everything else of the field
+//! - `label` on its label, or the legend of a group
+//! - `input` on its input, select or textarea, or the fieldset around its radios, items or
+//!   nested form
+//! - `error` on the region holding its errors
+//! - `indicator` on the text flagging it as required or invalid
+//! - `item` on each input of a `Vec<String>`, with `add` and `remove` on their buttons
+//! - `unit` on the select of units beside a number with `unit_options`
+//! - `search` on the search box above the select of a `Tz` field
+//! - `value` on the text shown in place of the input when masked or edited inline, with `unmask`
+//!   and `edit` on the buttons swapping the input in
+//!
+//! The form itself has `data-formulay-role="form"`, and its buttons `submit`, `reset`,
+//! `save-draft`, `unlock`, `back`, `next` and `action`, so a test can find an error with
+//! `[data-formulay-field="email"][data-formulay-role="error"]`.
+//!
+//! # Attributes
+//! Individual fields can be configured with the `#[yform(...)]` attribute. Options which don't
+//! apply to the field they are on (i.e. `trim` on a bool), conflict with one another, or are given
+//! more than once are compile errors pointing at the option, rather than being ignored. The
+//! exception is a `placeholder` on a field which isn't typed into (i.e. a checkbox or one with
+//! `choices`), which changes nothing about the form, so it is ignored with a deprecation warning
+//! pointing at it, as `skip_unsupported` warns about the fields it leaves out.
+//!
+//! - `#[yform(autogrow)]` on a String or Option<String> field renders a textarea which grows to fit
+//!   its content as the user types, instead of showing a scrollbar.
+//! - `#[yform(widget = "textarea", rows = 6)]` on a String or Option<String> field renders a
+//!   textarea of a fixed height instead of a single line input, for long form content like a bio.
+//!   It gets the same classes as a text input. `rows` sets how many lines it shows, and also
+//!   applies to `autogrow` textareas, where it sets the height they start from.
+//! - `#[yform(trim)]` on a String or Option<String> field strips leading and trailing whitespace
+//!   before the value is stored, so whitespace alone never satisfies a required field.
+//! - `#[yform(transform = "lowercase")]` or `#[yform(transform = "uppercase")]` on a String or
+//!   Option<String> field changes the case of the value as it is entered.
+//! - `#[yform(transform_with = "path::to::fn")]` applies a `fn(String) -> String` of your own to
+//!   the value as it is entered, after any trim or transform.
+//! - `#[yform(on_paste = "path::to::fn")]` on a String, Option<String> or number field passes
+//!   pasted text through a `fn(String) -> String` of your own before it lands in the input, i.e.
+//!   to strip a pasted phone number down to its digits. The input then holds the cleaned up text
+//!   as if it had been typed, and stores it on change as usual.
+//! - `#[yform(label = "Email address")]` replaces the Title Cased field name in the field's label
+//!   (and in the review summary).
+//! - `#[yform(placeholder = "you@example.com")]` shows placeholder text in the empty input.
+//! - `#[yform(name_attr = "email")]` sets the input's `name` attribute, which browsers use to
+//!   guess what to autofill and which keys the value when the form is submitted without JS.
+//!   Inputs are otherwise named after their field, apart from radios, which keep their id as their
+//!   name so that repeated nested forms don't share a group. Also applies to `hidden` fields.
+//! - `#[yform(input_type = "email")]` on a String or Option<String> field sets the type of its
+//!   input to one of `text`, `email`, `password`, `search`, `tel` or `url`, for the matching
+//!   keyboard on mobile and browser autofill.
+//! - `#[yform(options_prop)]` on a String or Option<String> field renders a select instead of a
+//!   text input, and adds a `<field>_options: Vec<(String, String)>` prop holding its choices as
+//!   `(value, label)` pairs, for choices which are only known at runtime. Adding `grouped` takes
+//!   `(group, value, label)` entries instead, and renders the entries of each group in an
+//!   `<optgroup>`, i.e. time zones grouped by region. Entries with an empty group are left
+//!   ungrouped.
+//! - `#[yform(format = "thousands")]` on a number field shows its value with thousands separators
+//!   (i.e. `1,234,567`) while the input isn't focused, and the plain number while it is being
+//!   edited. The input becomes a text input with `inputmode="decimal"`, since number inputs can't
+//!   show separators, and separators typed into it are ignored. The field's value is the number.
+//! - `#[yform(strict_numeric)]` on a number field keeps characters which can't be part of the
+//!   number from appearing in its input at all, whether typed, pasted or dropped, rather than
+//!   leaving them to fail to parse. Digits are let through, along with a minus sign for signed
+//!   types, a decimal point for floats and separators with `format`.
+//! - `#[yform(no_spinner)]` on a number field renders it without the browser's spin buttons, for
+//!   numbers which are really identifiers, i.e. an account or zip code. The input becomes a text
+//!   input with `inputmode="numeric"` (`"decimal"` for floats), which still brings up a numeric
+//!   keyboard on phones, and gets a `formula-y-no-spinner` class. Its value is parsed as usual.
+//! - `#[yform(event = "input")]` on a checkbox, radio or select field stores its value on that
+//!   event rather than on `change`. `"click"` applies to checkboxes and radios, i.e. for markup
+//!   which replaces the input with a styled label, and `"input"` to all three.
+//! - `#[yform(widget = "range", min = 0, max = 10)]` on a number field renders a slider, which
+//!   stores its value as it moves. `min` and `max` default to the browser's 0 and 100, and take
+//!   negative bounds as strings, i.e. `min = "-10"`. Float sliders move in any step.
+//! - `#[yform(date_text)]` on a date field (or an Option of one) renders a text input in place of
+//!   the browser's date picker, for browsers where it's unusable. The date is typed in the order
+//!   of the browser's language, i.e. `14/02/2025` in `en-GB` and `02/14/2025` in `en-US`, which
+//!   the empty input hints at, and stored as the same `NaiveDate`. Dates starting with a four digit
+//!   year are always read as ISO. A `NaiveDateTime` takes a time after the date, i.e.
+//!   `14/02/2025 09:30`. `DateOrder` reads and writes dates the same way for code of your own.
+//! - `#[yform(unit_options("kg", "lb"), unit_factors(1, 0.45359237))]` on an `f32` or `f64`
+//!   field (or an Option of one) renders a select of units beside its input, with a
+//!   `formula-y-unit` class. The value is always stored in the first unit, and `unit_factors`
+//!   says how many of it each unit is, starting with 1 for itself. Text entered in another unit is
+//!   converted as it's stored, and picking another unit shows the same value converted into it.
+//! - `#[yform(throttle_ms = 100)]` on a slider, or a field with `event = "input"`, updates the
+//!   field at most once every 100 ms while it keeps changing, so that dragging doesn't rerender
+//!   the form and emit `onchange` on every pixel. The first update goes through at once, and the
+//!   last one once the interval is up.
+//! - `#[yform(precision = 2, rounding = "half_up")]` on an `f32` or `f64` field (or an Option of
+//!   one) rounds its value to that many decimal places as it changes and again as it is
+//!   submitted, so that what is stored matches the backend's rules rather than float noise.
+//!   `rounding` is one of `"half_up"` (the default), `"half_even"`, `"down"` or `"up"`, as
+//!   described on `Rounding`. Not rounded on submit with `shared_state`, which submits the struct
+//!   itself.
+//! - `#[yform(regex = "[0-9]{5}", error = "Enter a 5 digit zip code")]` on a String or
+//!   Option<String> field blocks submitting while a value is entered which doesn't match the
+//!   pattern. The whole value has to match, as with the HTML `pattern` attribute. Until it does the
+//!   input gets an `invalid` class and the error (or "Invalid value" without one) is shown after
+//!   it, in an element with a `formula-y-error` class. Empty values are left to the required field
+//!   checks. Generated previews and wasm tests fill the field with its `placeholder` when it has
+//!   one, so give it a placeholder which matches.
+//! - `#[yform(validate = "path::to::fn")]` checks the field's value with a
+//!   `fn(&T) -> Result<(), String>` of your own, for rules a regex can't express. While it returns
+//!   an error the input gets an `invalid` class, the error is shown after it the same way as for a
+//!   regex, and submitting is blocked. Empty values are passed to it too.
+//! - `#[yform(widget = "radio")]` on an enum field renders a group of radio buttons inside a
+//!   `<fieldset>` instead of a select.
+//! - `#[yform(empty_label = "-- select --")]` labels the empty first choice of a select which can
+//!   be left empty, i.e. on an `Option` enum field or with `choices` or `options_prop`. Picking it
+//!   stores `None`, or an empty String. It is blank by default.
+//! - `#[yform(choices("small", "medium", "large"))]` on a String or Option<String> field offers a
+//!   small fixed set of values as a group of radio buttons, each labelled with the value itself.
+//!   Add `widget = "select"` for a select instead. A value which isn't one of the choices, i.e.
+//!   from the `init` prop, flags the field as invalid and blocks submitting.
+//! - `#[yform(nested)]` on a field holding another `YForm` struct renders that struct's fields
+//!   inside a `<fieldset>` with a `formula-y-fieldset` class, legended with the field's label.
+//!   Every change is passed up into the containing struct, and the containing form can't be
+//!   submitted while the nested one couldn't be, i.e. while its own required fields are empty.
+//!   On a `Vec` of them, each item gets its own nested form. Structs with `shared_state` can't be
+//!   nested.
+//! - `#[yform(min_items = 1, max_items = 5)]` on a `Vec` field bounds how many items it holds.
+//!   Add is disabled once it holds `max_items`, and Remove once it holds `min_items`. Holding a
+//!   number outside them, i.e. from the `init` prop, says how many items to add or remove in the
+//!   field's errors and blocks submitting.
+//! - `#[yform(submit_with = "path::to::fn")]` applies a `fn(T) -> T` of your own to the field's
+//!   value only in what is submitted, i.e. to normalize a phone number, while the input keeps
+//!   showing the value as entered. Drafts passed to `onsavedraft` are left as entered. Not
+//!   available with `shared_state`, which submits the struct itself.
+//! - `#[yform(encrypt_with = "path::to::fn")]` on a `String` or `Option<String>` field applies a
+//!   `fn(&str) -> String` of your own to its value as the submitted struct is built, after any
+//!   `submit_with`, i.e. to encrypt or tokenize a card number client side. Whatever is passed to
+//!   `onsubmit`, `onsubmit_rc` and `onaction` holds the encrypted value, while drafts and the values
+//!   passed to `onchange` and `onfieldchange` are left as entered, so leave those unset on forms
+//!   holding secrets. Not available with `shared_state`.
+//! - `#[yform(required_if = "business_account")]` only requires the field while the named field
+//!   is filled in or checked. This makes an Option field required in that case, and a plain field
+//!   optional otherwise.
+//! - `#[yform(required_msg = "Please accept the terms")]` replaces "This field is required" as the
+//!   message a required field left empty shows after a submit attempt. Option and Vec fields need
+//!   `required_if` to take one.
+//! - `#[yform(computed_from = "quantity, unit_price", compute_with = "path::to::fn")]` makes the
+//!   field read only, and recalculates it whenever one of the listed fields changes (and when the
+//!   form is created). The function is passed a reference to each listed field in order and
+//!   returns the new value, i.e. `fn total(quantity: &str, unit_price: &str) -> String`.
+//! - `#[yform(output)]` on a computed field shows its value in an `<output>` element rather than
+//!   a read only input. Its `for` attribute lists the ids of the fields it is computed from, and
+//!   screen readers announce the result as it changes. The field's item gets a
+//!   `formula-y-output` class.
+//! - `#[yform(custom = "path::to::fn")]` renders the field with a
+//!   `fn(&T, Callback<T>) -> Html` of your own, i.e. for a color picker or an autocomplete. It is
+//!   passed the field's value and a callback which updates it, and its output goes under the
+//!   field's label in a `formula-y-custom` element, followed by the usual errors. Required field
+//!   checks, `regex` and `validate` still apply. Generated wasm tests leave the field as it
+//!   starts out, so give a required one an `init` value there. A widget made of several items
+//!   which take focus in turn (i.e. rating stars or a tag input's chips) can get the same
+//!   keyboard behaviour as the form's radios from `RovingFocus`. Give each item
+//!   `tabindex={RovingFocus::tabindex(index, current)}`, and move `current` to the item which
+//!   `RovingFocus::new(Orientation::Horizontal).keydown(&event, current, count)` returns from the
+//!   widget's `onkeydown`.
+//! - `#[yform(skip)]` leaves the field out of the rendered form, i.e. for an id on a struct shared
+//!   with the API. The field keeps its `init` value (or its default) and is submitted as is.
+//! - `#[yform(hidden)]` on a String, bool or number field (or an Option of one) renders an
+//!   `<input type="hidden">` holding its value in place of a label and input. Skipped and hidden
+//!   fields are never required, and neither can be combined with other options.
+//! - `#[yform(readonly)]` renders the field's input without letting it be edited, the same way as
+//!   `FieldAccess::ReadOnly`.
+//! - `#[yform(masked_display)]` on a `String` or `Option<String>` field shows a value the user
+//!   hasn't changed as `••••1234` in place of its input, i.e. an API key passed in through `init`.
+//!   A Change button (`formula-y-masked-change`) clears the field and shows its input for a new
+//!   value, while leaving it alone submits the value as it was. A new `init` or a reset masks it
+//!   again.
+//! - `#[yform(step = 2)]` splits the form into steps shown one at a time, i.e. for onboarding and
+//!   checkout flows. Steps are shown in the order of their numbers, and fields without a step go
+//!   on the first. A "Step 1 of 3" line with a `formula-y-step-indicator` class tops the form,
+//!   and until the last step a Next button (`formula-y-next`) takes the place of the submit
+//!   buttons, with a Back button (`formula-y-back`) from the second step on. Next only moves on
+//!   once the step's own fields pass their required field and validity checks, and `onsubmit` is
+//!   only called from the last step, once the fields of every step pass them again. The form gets
+//!   an `onstepvalidity: Callback<(usize, bool)>` prop, passed the current step (counting from 0)
+//!   and whether Next would move on from it whenever either changes, i.e. to mark the steps of a
+//!   progress bar. Fields on other steps stay in the page with the `hidden` attribute. Can't be
+//!   combined with `save_individually` or the search preset.
+//! - `#[yform(section = "Advanced")]` groups the field with every other field in the same section
+//!   into a collapsible `<details>` block, placed where the first of them appears in the struct.
+//!   Adding `collapsed` to any of them starts the section closed.
+//!
+//! - `#[yform(default = "US")]` starts the field out holding the given literal in the struct's
+//!   `new()`, in place of its empty value. Strings give String fields their text, enum fields the
+//!   variant with that value and date fields the parsed date. Bools and numbers take a literal of
+//!   their own, and an Option field starts out holding `Some` of it.
+//!
+//! The struct itself accepts `#[yform(...)]` options too.
+//!
+//! - `#[yform(name = "SignupForm")]` names the component `SignupForm` (with `SignupFormMsg` and
+//!   `SignupFormProps`) instead of appending `Form` to the struct name.
+//! - `#[yform(shared_state)]` keeps the struct behind an `Rc<RefCell<T>>` instead of cloning it in
+//!   and out of the form, so the struct doesn't need to implement `Clone`. The `init` prop and the
+//!   `onsubmit` callback both take the `Rc<RefCell<T>>`, and when `init` is passed the form edits
+//!   that same value in place.
+//! - `#[yform(actions("Approve", "Reject", "Request changes"))]` replaces the submit button with
+//!   one button per action, and generates a `<Name>FormAction` enum with a variant for each
+//!   (`Approve`, `Reject`, `RequestChanges`). Choosing an action checks required fields the same
+//!   way submitting does, then calls the `onaction: Callback<(<Name>FormAction, T)>` prop.
+//! - `#[yform(review)]` adds a review step. Submitting with every required field filled in swaps
+//!   the form for a read only summary of its values, each with an Edit button leading back to its
+//!   input, and `onsubmit` is only called once the summary is confirmed. Named actions skip it.
+//! - `#[yform(save_individually)]` saves each field on its own, i.e. for profile pages. The form
+//!   starts in `inline_edit` mode without any buttons, and confirming an inline edit which changed
+//!   a field calls the `onfieldsave: Callback<(DataField, T)>` prop with the field and a clone of
+//!   the struct. A required field left empty is flagged and stays open until it is filled in,
+//!   unless `enforce_required_fields` lets it through. Can't be combined with `review` or `actions`.
+//! - `#[yform(validate = "path::to::fn")]` checks the whole struct once every field allows a
+//!   submit, and before `onsubmit` (or `onaction`) is called, with a
+//!   `fn(&T) -> Result<(), Vec<(DataField, String)>>` of your own, i.e. that `confirm_password`
+//!   matches `password`. Each error is shown after the input of the field it names, which gets an
+//!   `invalid` class, and the submit is blocked. The errors are checked again on every change
+//!   until they are fixed.
+//! - `#[yform(preset = "search")]` makes a compact search form, i.e. for filtering a table. The
+//!   fields and a search button are laid out in a single row, with a `formula-y-search` class on
+//!   the form, and labels are shown as placeholders unless the `label_placeholders` prop is false.
+//!   Text inputs update the form as the user types rather than on change, so `onchange` is
+//!   called on every keystroke. The button shows a magnifying glass, or the
+//!   `search_icon: Html` prop when it is passed. Pair it with `auto_submit_after_ms` to submit
+//!   as the user types too. Can't be combined with `review`, `actions` or `save_individually`.
+//! - `#[yform(preset = "login")]` marks a login form up the way browsers and password managers
+//!   expect. A field named `password` (or with `input_type = "password"`) becomes a password
+//!   input with `autocomplete="current-password"`, followed by a Show/Hide button with a
+//!   `formula-y-password-toggle` class which reveals it as text. The first other text field gets
+//!   `autocomplete="username"`. A `bool` field, i.e. `remember_me`, renders an optional checkbox
+//!   rather than one which has to be checked.
+//! - `#[yform(label_placeholders_below = "600px")]` shows labels as placeholders (as with the
+//!   `label_placeholders` prop) only on screens narrower than the given width, i.e. on mobile,
+//!   using a media query.
+//! - `#[yform(button_position = "both", align = "right")]` places the buttons above the fields
+//!   (`"top"`), below them (`"bottom"`, the default) or at both ends, i.e. for long admin forms.
+//!   Each row of buttons is wrapped in a `formula-y-buttons` element, with a
+//!   `formula-y-buttons-top` or `formula-y-buttons-bottom` class, and a `formula-y-align-left`,
+//!   `formula-y-align-center` or `formula-y-align-right` class when `align` is set.
+//! - `#[yform(action_bar)]` wraps each row of buttons in an element with a `formula-y-action-bar`
+//!   class (and `formula-y-action-bar-top` or `formula-y-action-bar-bottom`), placed as a direct
+//!   child of the form. Giving it `position: sticky` with `bottom: 0` (or `top: 0`) and a
+//!   background keeps the buttons in view while scrolling through a long form.
+//! - `#[yform(no_partial_eq)]` compares the generated props by hand so the struct doesn't need to
+//!   implement `PartialEq`. The `init` prop is left out of the comparison since it is only read
+//!   when the form is created, or compared by pointer in `shared_state` mode.
+//! - `#[yform(derive_default)]` implements `Default` for the struct with the same values as the
+//!   generated `new()`, which the form starts out with when no `init` is passed, including any
+//!   `default = ...` on its fields. Backend code building the struct then gets the same defaults.
+//! - `#[yform(computed_outputs)]` shows every computed field of the form in an `<output>`, as
+//!   `output` does for one field.
+//! - `#[yform(skip_unsupported)]` leaves fields of a type the form can't render (i.e. a `char` or
+//!   a `HashMap`) out of it rather than failing the build, so one such field added to a struct
+//!   shared with the backend doesn't break the frontend. Each one starts out from `Default`, is
+//!   submitted as is, and gets a deprecation warning pointing at its type so it isn't left out
+//!   unnoticed. Types the derive can't tell apart from an enum still fail, on the missing
+//!   `YFormSelect` implementation.
+//! - `#[yform(serialize)]` marks a struct which implements `serde::Serialize`, so the
+//!   `debug-panel` feature shows its value and the `clipboard` feature gives its form a
+//!   `copy_button` prop. Without it, neither feature needs the struct to be `Serialize`.
+//! - `#[yform(checkbox_event = "click", radio_event = "input", select_event = "input")]` sets the
+//!   event each kind of input stores its value on for every field of the form, as `event` does
+//!   for a single field, which still takes precedence. Each defaults to `"change"`.
+//!
+//! # Previewing
+//! Adding `#[yform(preview)]` to the struct generates a `<Name>FormPreview` component which mounts
+//! the form filled in with sample values and logs anything submitted to the console. Mounting the
+//! previews of every form on one page makes for a simple catalog. The struct must implement `Debug`.
+//!
+//! # Function components
+//! `#[yform(emit = "both")]` generates a `use_<name>_form` hook (i.e. `use_data_form`) alongside
+//! the struct component, so a code base moving to function components can keep using the same
+//! struct while it migrates. The hook keeps the form's value in the calling component, starting
+//! from its `init` closure, and returns a handle. `view()` renders the struct component
+//! controlled by the handle, `value()` and `set()` read and replace what it shows, and
+//! `submitted()` gives the value last submitted. To pass other props, render the component
+//! yourself with `value={form.value()}`, `onchange={form.onchange()}` and
+//! `onsubmit={form.onsubmit()}`. The default, `emit = "struct"`, only generates the component.
+//!
+//! ```ignore
+//! #[function_component(Signup)]
+//! fn signup() -> Html {
+//!     let form = use_data_form(Data::new);
+//!     html! {
+//!         <>
+//!             {form.view()}
+//!             <p>{format!("Signing up as {}", form.value().email)}</p>
+//!         </>
+//!     }
+//! }
+//! ```
+//!
+//! # Debugging
+//! With the `debug-panel` feature enabled, every form renders a collapsible panel underneath it
+//! showing its validity and dirty state. Forms whose struct is marked `#[yform(serialize)]` show
+//! its live value too, printed with `serde_json`, so the struct must implement
+//! `serde::Serialize`.
+//!
+//! # Copying values
+//! With the `clipboard` feature enabled, every form whose struct is marked `#[yform(serialize)]`
+//! takes a `copy_button: bool` prop which renders a "Copy values" button. Clicking it copies the
+//! current value of the struct to the clipboard as pretty printed JSON, i.e. for pasting the state
+//! of a form into a bug report. The struct must implement `serde::Serialize`.
+//!
+//! # Visiting fields
+//! The derive generates a `visit_fields` method on the struct, and on the component for its current
+//! value, which hands each field to a `FieldVisitor` along with a `FieldMeta` holding its name and
+//! label and its value as a `FieldValue`. A closure taking the field enum, the meta and the value
+//! is a visitor too, so exporters such as CSV or audit logs can be written once for every form.
+//!
+//! One such exporter is built in: the struct's `to_csv_row` writes its values as a line of CSV,
+//! with `csv_header` giving the matching line of field names, so a batch data entry page can
+//! collect a row per submission and offer them all as a download. `to_tsv_row` and `tsv_header`
+//! do the same with tabs, for pasting into a spreadsheet. Lists and nested structs are written as
+//! text in a single cell.
+//!
+//! `from_csv_row` and `from_tsv_row` read a row back into the struct, parsing each cell as the
+//! type of its field, i.e. for a bulk entry page where rows are pasted from a spreadsheet. Choices
+//! are matched by label or value, and an empty cell is `None` for an `Option` field. Rows with
+//! the wrong number of cells, cells which don't parse and fields which can't be read from text,
+//! i.e. nested structs and custom fields, give an `ImportError`.
+//!
+//! # Embedding
+//! A form can also be a part of a bigger form written by hand, rather than a `<form>` of its own.
+//! Pass its component a `nested` prop with a `NestedLink`, and it renders its fields in a
+//! `<fieldset>` with a `formula-y-nested` class, without a `<form>` element or any buttons.
+//! `onchange` is passed the struct as each field changes, and `onvalidity` whether it could be
+//! submitted. Setting `display_required_warnings` shows the required field warnings, i.e. once
+//! the containing form has tried to submit, and `enforce_required_fields` counts empty required
+//! fields as invalid. The form follows its `init` prop while nested, so pass it the latest value
+//! from `onchange`, or no `init` at all.
+//!
+//! ```ignore
+//! html! {
+//!     <AddressForm init={self.address.clone()} nested={NestedLink {
+//!         display_required_warnings: self.tried_to_submit,
+//!         ..NestedLink::new(ctx.link().callback(Msg::Address))
+//!     }} />
+//! }
+//! ```
+//!
+//! `#[yform(nested)]` fields embed a form in another derived one the same way.
+//!
+//! # Audit trail
+//! With the `audit_trail` prop set, the form records every change to a field as a `FieldEdit`,
+//! holding the field, its old and new value and when it changed. The component's `edits` method
+//! returns them oldest first, and the `onedit` callback is passed each one as it is made, which
+//! turns recording on by itself. The form doesn't know who is filling it in, so pair the edits
+//! with the current user when storing them. Resets are recorded as edits rather than clearing the
+//! trail, while values set through the `value` or `init` props aren't recorded.
+//!
+//! # Telemetry
+//! The `ontelemetry` callback is passed a `TelemetryEvent` as the form is filled in, so UX
+//! research can find the fields where users stall. Each field is timed from when it is first
+//! focused until it first holds a valid value, which is reported as `FieldCompleted`, and the
+//! form from when any field is first focused until it is submitted, reported as `FormCompleted`.
+//! Fields which are never focused, i.e. filled in by `init`, aren't timed. Nothing is timed
+//! without the callback.
+//!
+//! # Storage
+//! Features which keep values between page loads go through the `FormStorage` trait, a string
+//! keyed `get`/`set`/`remove` which reports back through callbacks, so that asynchronous backends
+//! fit behind it too. `LocalStorage` and `SessionStorage` use the browser's storage, and
+//! `MemoryStorage` keeps values in memory, i.e. for tests. Wrap one in a `SharedStorage` to pass
+//! it around as a prop.
+//!
+//! With the `indexed-db` feature enabled, `IndexedDbStorage` keeps values in an IndexedDB object
+//! store instead, for values too large for `localStorage`'s few megabytes, i.e. drafts of forms
+//! holding long text.
+//!
+//! A `DraftStore` keeps a form's serialized drafts in a `FormStorage` along with the version of
+//! the form they were saved from and when. Loading drops a draft saved by another version, older
+//! than the time to live set with `expire_after_ms`, or which the app's `parse` closure rejects,
+//! removing it from storage and passing the reason to an `ondropped` callback, rather than
+//! restoring a draft the form no longer fits.
+//!
+//! With the `offline-queue` feature enabled, an `OfflineQueue` sends serialized submissions with a
+//! closure of your own, i.e. from `onsubmit`. While the browser is offline they are queued in a
+//! `FormStorage` instead, and retried in order once it is back online, with a status callback
+//! reporting each one as sent, queued, retried or failed. Submissions made while earlier ones are
+//! still waiting are queued behind them, and a queue which can't be read back is reported as
+//! failed and left as it is rather than overwritten.
+//!
+//! # Testing
+//! With the `test-utils` feature enabled, the derive also generates a tester for each form which
+//! drives the component through the same msgs as its inputs, so form logic can be unit tested
+//! without a browser.
+//!
+//! ```ignore
+//! let tester = DataFormTester::new()
+//!     .set_email("x@y.com")
+//!     .check_agree_to_terms()
+//!     .submit();
+//! assert_eq!(tester.emitted().len(), 1);
+//! ```
+//!
+//! Adding `#[yform(wasm_tests)]` to the struct generates a wasm-bindgen-test which mounts the form,
+//! fills in every input through the DOM, submits it, and checks the value passed to onsubmit. It
+//! needs `wasm-bindgen-test` as a dev dependency.
+//!
+//! With the `expand` feature enabled, `formula_y::expand!` takes a struct definition along with
+//! its `#[yform(...)]` attributes and expands to a string holding what the derive generates for
+//! it, pretty printed. Comparing it against a stored snapshot, i.e. with `insta`, catches changes
+//! to the generated component between versions of formula_y before they ship. formula_y pins its
+//! own output this way in `tests/expand.rs`.
+//!
+//! ```ignore
+//! let generated = formula_y::expand! {
+//!     #[yform(review)]
+//!     struct Data {
+//!         email: String,
+//!     }
+//! };
+//! insta::assert_snapshot!(generated);
+//! ```
+
+pub use announcement::ErrorsAnnouncement;
+pub use csv::{CsvRow, ImportError};
+#[cfg(feature = "chrono")]
+pub use date_text::DateOrder;
+pub use deadline::Deadline;
+pub use drafts::{DraftDropped, DraftStore};
+pub use field_access::{FieldAccess, FieldPolicy};
+pub use field_edit::FieldEdit;
+#[cfg(feature = "expand")]
+pub use formula_y_derive::expand;
+pub use formula_y_derive::{YForm, YFormSelect};
+#[cfg(feature = "indexed-db")]
+pub use indexed_db::IndexedDbStorage;
+pub use init_merge::InitMerge;
+pub use mask::mask_value;
+pub use nested::{NestedLink, YFormNested};
+#[cfg(feature = "offline-queue")]
+pub use offline_queue::{OfflineQueue, QueueStatus};
+pub use required_policy::RequiredPolicy;
+pub use rounding::Rounding;
+pub use roving::{Orientation, RovingFocus};
+pub use select::YFormSelect;
+pub use storage::{FormStorage, LocalStorage, MemoryStorage, SessionStorage, SharedStorage};
+pub use telemetry::{CompletionTimer, TelemetryEvent};
+pub use theme::{Density, Theme};
+#[cfg(feature = "chrono-tz")]
+pub use time_zone::{detect_time_zone, time_zones_by_region};
+pub use unknown_field::UnknownField;
+pub use visit::{FieldMeta, FieldValue, FieldVisitor};
+
+mod announcement;
+mod csv;
+#[cfg(feature = "chrono")]
+mod date_text;
+mod deadline;
+mod drafts;
+mod field_access;
+mod field_edit;
+#[cfg(feature = "indexed-db")]
+mod indexed_db;
+mod init_merge;
+mod mask;
+mod nested;
+#[cfg(feature = "offline-queue")]
+mod offline_queue;
+mod required_policy;
+mod rounding;
+mod roving;
+mod select;
+mod storage;
+mod telemetry;
+mod theme;
+#[cfg(feature = "chrono-tz")]
+mod time_zone;
+mod unknown_field;
+mod visit;
+
+/// Everything a derived form needs in scope to compile.
+pub mod prelude {
+    pub use crate::{
+        mask_value, CompletionTimer, CsvRow, Deadline, Density, ErrorsAnnouncement, FieldAccess,
+        FieldEdit, FieldMeta, FieldPolicy, FieldValue, FieldVisitor, ImportError, InitMerge,
+        NestedLink, Orientation, RequiredPolicy, Rounding, RovingFocus, TelemetryEvent, Theme,
+        UnknownField, YForm, YFormNested, YFormSelect,
+    };
+    pub use gloo_events;
+    pub use gloo_timers;
+    pub use regex;
+    pub use wasm_bindgen::JsCast;
+    pub use web_sys::{self, HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement};
+    pub use yew::prelude::*;
+
+    #[cfg(any(feature = "debug-panel", feature = "clipboard"))]
+    pub use serde_json;
+
+    #[cfg(feature = "chrono-tz")]
+    pub use crate::time_zones_by_region;
+    #[cfg(feature = "chrono")]
+    pub use crate::DateOrder;
+    #[cfg(feature = "chrono")]
+    pub use chrono;
+    #[cfg(feature = "chrono-tz")]
+    pub use chrono_tz;
 }
@@ -0,0 +1,11 @@
+/// How a `masked_display` field shows a value the user hasn't changed: bullets followed by its
+/// last four characters, i.e. `••••1234`. Values of eight characters or fewer are shown as bullets
+/// alone, so that a short secret isn't mostly given away.
+pub fn mask_value(value: &str) -> String {
+    let length = value.chars().count();
+    if length <= 8 {
+        return "••••".to_string();
+    }
+    let last_four = value.chars().skip(length - 4).collect::<String>();
+    format!("••••{}", last_four)
+}
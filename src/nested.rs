@@ -0,0 +1,67 @@
+use yew::{Callback, Html};
+
+use crate::FieldValue;
+
+/// A struct deriving `YForm` which can be rendered inside another form, for fields marked
+/// `#[yform(nested)]`. The derive implements this for every form apart from `shared_state` ones,
+/// since the containing form owns the nested value.
+pub trait YFormNested: Sized {
+    /// Render the struct's form embedded in the containing form, starting from `init`
+    fn nested_view(init: Self, id_prefix: String, link: NestedLink<Self>) -> Html;
+
+    /// A read only summary of the struct's values, for the containing form's review step
+    fn summary(&self) -> Html;
+
+    /// A value with every field filled in, for previews and wasm tests of the containing form
+    fn sample() -> Self;
+
+    /// The values of the struct's fields by name, for the containing form's `visit_fields`
+    fn field_values(&self) -> Vec<(&'static str, FieldValue)>;
+}
+
+/// Connects a form rendered inside a fieldset of another form to the form containing it.
+pub struct NestedLink<T> {
+    /// Called with the nested struct whenever one of its fields changes
+    pub onchange: Callback<T>,
+    /// Called with whether the nested form's values could be submitted, once it is first rendered
+    /// and whenever they change
+    pub onvalidity: Callback<bool>,
+    /// Whether the containing form is showing its required field warnings
+    pub display_required_warnings: bool,
+    /// Whether the containing form blocks submitting while required fields are empty
+    pub enforce_required_fields: bool,
+}
+
+impl<T> NestedLink<T> {
+    /// A link passing changes to `onchange`, for embedding a form in one written by hand. Set the
+    /// other fields with struct update syntax, i.e. `display_required_warnings` once the
+    /// containing form has tried to submit.
+    pub fn new(onchange: Callback<T>) -> Self {
+        NestedLink {
+            onchange,
+            onvalidity: Callback::noop(),
+            display_required_warnings: false,
+            enforce_required_fields: false,
+        }
+    }
+}
+
+impl<T> Clone for NestedLink<T> {
+    fn clone(&self) -> Self {
+        NestedLink {
+            onchange: self.onchange.clone(),
+            onvalidity: self.onvalidity.clone(),
+            display_required_warnings: self.display_required_warnings,
+            enforce_required_fields: self.enforce_required_fields,
+        }
+    }
+}
+
+impl<T> PartialEq for NestedLink<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.onchange == other.onchange
+            && self.onvalidity == other.onvalidity
+            && self.display_required_warnings == other.display_required_warnings
+            && self.enforce_required_fields == other.enforce_required_fields
+    }
+}
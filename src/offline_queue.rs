@@ -0,0 +1,232 @@
+use std::cell::{Cell, RefCell};
+use std::rc::{Rc, Weak};
+
+use gloo_events::EventListener;
+use yew::Callback;
+
+use crate::SharedStorage;
+
+/// What an `OfflineQueue` has done with the submissions given to it, passed to its status callback.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueueStatus {
+    /// A submission was sent straight away
+    Sent,
+    /// The browser is offline, or earlier submissions are still waiting, so a submission was
+    /// queued, leaving this many waiting
+    Queued(usize),
+    /// A queued submission was sent once the browser was back online, leaving this many waiting
+    Retried(usize),
+    /// A submission couldn't be sent or stored, and why
+    Failed(String),
+}
+
+/// Sends serialized submissions, queueing them in a `FormStorage` while the browser is offline and
+/// retrying them in order once it is back online. Needs the `offline-queue` feature.
+///
+/// Forms hand their values to `onsubmit` rather than sending them, so the queue is given the
+/// serialized value along with a `send` closure of the app's own, which reports back whether the
+/// request went through. Submissions queued on an earlier visit are retried when the queue is
+/// created.
+///
+/// ```ignore
+/// let queue = OfflineQueue::new(
+///     SharedStorage::new(LocalStorage),
+///     "visit-reports",
+///     |body, done| post_report(body, done),
+///     onstatus,
+/// );
+/// let onsubmit = Callback::from(move |report: Report| {
+///     queue.submit(serde_json::to_string(&report).unwrap());
+/// });
+/// ```
+#[derive(Clone)]
+pub struct OfflineQueue(Rc<QueueState>);
+
+// The app's own closure sending a submission, which reports back whether it went through
+type SendFn = Box<dyn Fn(String, Callback<Result<(), String>>)>;
+
+struct QueueState {
+    storage: SharedStorage,
+    key: String,
+    send: SendFn,
+    onstatus: Callback<QueueStatus>,
+    // Set while queued submissions are being retried, so that only one retry runs at a time
+    retrying: Cell<bool>,
+    online_listener: RefCell<Option<EventListener>>,
+}
+
+impl OfflineQueue {
+    /// Queue submissions in `storage` under `key`, sending them with `send`
+    pub fn new(
+        storage: SharedStorage,
+        key: impl Into<String>,
+        send: impl Fn(String, Callback<Result<(), String>>) + 'static,
+        onstatus: Callback<QueueStatus>,
+    ) -> Self {
+        let state = Rc::new(QueueState {
+            storage,
+            key: key.into(),
+            send: Box::new(send),
+            onstatus,
+            retrying: Cell::new(false),
+            online_listener: RefCell::new(None),
+        });
+        if let Some(window) = web_sys::window() {
+            // The listener only holds on to the queue weakly, so dropping the queue removes it
+            let weak: Weak<QueueState> = Rc::downgrade(&state);
+            let listener = EventListener::new(&window, "online", move |_| {
+                if let Some(state) = weak.upgrade() {
+                    retry(state);
+                }
+            });
+            *state.online_listener.borrow_mut() = Some(listener);
+        }
+        if is_online() {
+            retry(state.clone());
+        }
+        OfflineQueue(state)
+    }
+
+    /// Send a submission, or queue it if the browser is offline or goes offline while sending it.
+    /// Submissions still waiting from before go first, so it is queued behind them.
+    pub fn submit(&self, submission: String) {
+        let state = self.0.clone();
+        if !is_online() || state.retrying.get() {
+            return enqueue(state, submission);
+        }
+        load(&self.0, move |queue| match queue {
+            Ok(queue) if !queue.is_empty() => enqueue(state, submission),
+            // A queue which can't be read is reported by the retries, and sending this submission
+            // keeps it from being lost along with the queue
+            _ => send(state, submission),
+        });
+    }
+}
+
+fn send(state: Rc<QueueState>, submission: String) {
+    let queued = submission.clone();
+    let sending = state.clone();
+    (sending.send)(
+        submission,
+        Callback::once(move |sent: Result<(), String>| match sent {
+            Ok(()) => state.onstatus.emit(QueueStatus::Sent),
+            Err(_) if !is_online() => enqueue(state, queued),
+            Err(error) => state.onstatus.emit(QueueStatus::Failed(error)),
+        }),
+    );
+}
+
+// Whether the browser thinks it is online, which it is assumed to be without a window to ask
+fn is_online() -> bool {
+    web_sys::window()
+        .map(|window| window.navigator().on_line())
+        .unwrap_or(true)
+}
+
+// The submissions waiting to be sent, oldest first, stored as a JSON array of strings
+fn load(state: &Rc<QueueState>, done: impl FnOnce(Result<Vec<String>, String>) + 'static) {
+    state.storage.get(
+        &state.key,
+        Callback::once(move |stored: Result<Option<String>, String>| {
+            done(stored.and_then(|stored| match stored {
+                Some(stored) => {
+                    serde_json::from_str(&stored).map_err(|error| {
+                        format!("The queued submissions couldn't be read: {}", error)
+                    })
+                }
+                None => Ok(Vec::new()),
+            }))
+        }),
+    );
+}
+
+fn save(state: &Rc<QueueState>, queue: &[String], done: Callback<Result<(), String>>) {
+    match serde_json::to_string(queue) {
+        Ok(stored) => state.storage.set(&state.key, stored, done),
+        Err(error) => done.emit(Err(format!(
+            "The queued submissions couldn't be written: {}",
+            error
+        ))),
+    }
+}
+
+fn enqueue(state: Rc<QueueState>, submission: String) {
+    load(&state.clone(), move |queue| match queue {
+        Ok(mut queue) => {
+            queue.push(submission);
+            let waiting = queue.len();
+            save(
+                &state.clone(),
+                &queue,
+                Callback::once(move |saved: Result<(), String>| match saved {
+                    Ok(()) => {
+                        state.onstatus.emit(QueueStatus::Queued(waiting));
+                        // Queued behind earlier submissions while online, which go out in turn
+                        if is_online() {
+                            retry(state);
+                        }
+                    }
+                    Err(error) => state.onstatus.emit(QueueStatus::Failed(error)),
+                }),
+            );
+        }
+        Err(error) => state.onstatus.emit(QueueStatus::Failed(error)),
+    });
+}
+
+// Send the oldest queued submission, and carry on with the rest once it is sent. Stops at the
+// first one which fails, leaving it queued for the next time the browser comes back online.
+fn retry(state: Rc<QueueState>) {
+    if state.retrying.replace(true) {
+        return;
+    }
+    load(&state.clone(), move |queue| match queue {
+        Ok(queue) if queue.is_empty() => state.retrying.set(false),
+        Ok(mut queue) => {
+            let submission = queue.remove(0);
+            let sending = state.clone();
+            (sending.send)(
+                submission,
+                Callback::once(move |sent: Result<(), String>| {
+                    if let Err(error) = sent {
+                        state.retrying.set(false);
+                        return state.onstatus.emit(QueueStatus::Failed(error));
+                    }
+                    // Submissions queued while this one was being sent are kept
+                    load(&state.clone(), move |latest| {
+                        // Saving over a queue which couldn't be read would lose everything in it
+                        let mut latest = match latest {
+                            Ok(latest) => latest,
+                            Err(error) => {
+                                state.retrying.set(false);
+                                return state.onstatus.emit(QueueStatus::Failed(error));
+                            }
+                        };
+                        if !latest.is_empty() {
+                            latest.remove(0);
+                        }
+                        let waiting = latest.len();
+                        save(
+                            &state.clone(),
+                            &latest,
+                            Callback::once(move |saved: Result<(), String>| {
+                                state.retrying.set(false);
+                                match saved {
+                                    Ok(()) => {
+                                        state.onstatus.emit(QueueStatus::Retried(waiting));
+                                        retry(state);
+                                    }
+                                    Err(error) => state.onstatus.emit(QueueStatus::Failed(error)),
+                                }
+                            }),
+                        );
+                    });
+                }),
+            );
+        }
+        Err(error) => {
+            state.retrying.set(false);
+            state.onstatus.emit(QueueStatus::Failed(error));
+        }
+    });
+}
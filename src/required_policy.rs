@@ -0,0 +1,19 @@
+/// How a form treats required fields left empty when it is submitted. Set with the form's
+/// `enforce_required_fields` prop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RequiredPolicy {
+    /// Block the submit and flag the missing fields
+    #[default]
+    Enforce,
+    /// Flag the missing fields, but submit anyway, i.e. when saving a draft
+    WarnOnly,
+    /// Submit without flagging anything
+    Ignore,
+}
+
+impl RequiredPolicy {
+    /// Whether a required field left empty stops the form from submitting
+    pub fn blocks_submit(self) -> bool {
+        self == RequiredPolicy::Enforce
+    }
+}
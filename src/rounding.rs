@@ -0,0 +1,86 @@
+/// How a float field with `#[yform(precision = ...)]` is rounded to its number of decimal places,
+/// set with `#[yform(rounding = "...")]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Rounding {
+    /// Halves round away from zero, i.e. 2.345 to 2.35
+    #[default]
+    HalfUp,
+    /// Halves round to the even neighbour, i.e. 2.345 to 2.34 and 2.355 to 2.36
+    HalfEven,
+    /// Round towards zero, cutting off the extra places
+    Down,
+    /// Round away from zero, i.e. 2.341 to 2.35
+    Up,
+}
+
+impl Rounding {
+    /// Round a value to the given number of decimal places. The scaled value is first snapped to
+    /// a millionth, so that float noise like 1.005 being stored as 1.00499... doesn't decide which
+    /// way a half goes.
+    pub fn round(self, value: f64, precision: u32) -> f64 {
+        let scale = 10f64.powi(precision as i32);
+        let scaled = (value * scale * 1e6).round() / 1e6;
+        let rounded = match self {
+            Rounding::HalfUp => scaled.round(),
+            Rounding::HalfEven if (scaled - scaled.trunc()).abs() == 0.5 => {
+                let truncated = scaled.trunc();
+                if truncated % 2.0 == 0.0 {
+                    truncated
+                } else {
+                    truncated + scaled.signum()
+                }
+            }
+            Rounding::HalfEven => scaled.round(),
+            Rounding::Down => scaled.trunc(),
+            Rounding::Up if scaled.fract() != 0.0 => scaled.trunc() + scaled.signum(),
+            Rounding::Up => scaled,
+        };
+        rounded / scale
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn half_up_rounds_halves_away_from_zero() {
+        assert_eq!(Rounding::HalfUp.round(2.345, 2), 2.35);
+        assert_eq!(Rounding::HalfUp.round(2.344, 2), 2.34);
+        assert_eq!(Rounding::HalfUp.round(-2.345, 2), -2.35);
+        // Stored as 1.00499..., but still a half
+        assert_eq!(Rounding::HalfUp.round(1.005, 2), 1.01);
+    }
+
+    #[test]
+    fn half_even_rounds_halves_to_the_even_neighbour() {
+        assert_eq!(Rounding::HalfEven.round(2.345, 2), 2.34);
+        assert_eq!(Rounding::HalfEven.round(2.355, 2), 2.36);
+        assert_eq!(Rounding::HalfEven.round(2.346, 2), 2.35);
+        assert_eq!(Rounding::HalfEven.round(-2.345, 2), -2.34);
+        assert_eq!(Rounding::HalfEven.round(-2.355, 2), -2.36);
+    }
+
+    #[test]
+    fn down_cuts_off_towards_zero() {
+        assert_eq!(Rounding::Down.round(2.349, 2), 2.34);
+        assert_eq!(Rounding::Down.round(-2.349, 2), -2.34);
+    }
+
+    #[test]
+    fn up_rounds_away_from_zero() {
+        assert_eq!(Rounding::Up.round(2.341, 2), 2.35);
+        assert_eq!(Rounding::Up.round(2.34, 2), 2.34);
+        assert_eq!(Rounding::Up.round(-2.341, 2), -2.35);
+    }
+
+    #[test]
+    fn precision_zero_rounds_to_whole_numbers() {
+        assert_eq!(Rounding::HalfUp.round(2.5, 0), 3.0);
+        assert_eq!(Rounding::HalfEven.round(2.5, 0), 2.0);
+        assert_eq!(Rounding::HalfEven.round(3.5, 0), 4.0);
+        assert_eq!(Rounding::Down.round(2.9, 0), 2.0);
+        assert_eq!(Rounding::Up.round(2.1, 0), 3.0);
+        assert_eq!(Rounding::Up.round(-2.1, 0), -3.0);
+    }
+}
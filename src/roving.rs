@@ -0,0 +1,162 @@
+/// Which arrow keys move between a widget's items
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// Left and Right, i.e. for a row of rating stars
+    Horizontal,
+    /// Up and Down, i.e. for a listbox
+    Vertical,
+    /// All four, as in a radio group
+    Both,
+}
+
+/// The keyboard behaviour shared by widgets made of several items which take focus in turn, i.e.
+/// a radio group, a row of rating stars, the chips of a tag input or the boxes of a one time code.
+/// It follows the roving tabindex pattern from the WAI-ARIA Authoring Practices: only the current
+/// item is in the tab order, so Tab moves past the widget as a whole, and the arrow keys, Home and
+/// End move between its items.
+///
+/// The form's own radio and checkbox navigation goes through it, and `custom` renderers can use it
+/// the same way rather than reimplementing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RovingFocus {
+    orientation: Orientation,
+    wrap: bool,
+}
+
+impl RovingFocus {
+    /// Movement with the given arrow keys, which wraps around from the last item to the first
+    pub fn new(orientation: Orientation) -> Self {
+        Self {
+            orientation,
+            wrap: true,
+        }
+    }
+
+    /// Whether moving past the last item goes back to the first, and the other way around
+    pub fn wrapping(self, wrap: bool) -> Self {
+        Self { wrap, ..self }
+    }
+
+    /// The item `step` items on from `current` out of `count`, or None past either end of a
+    /// widget which doesn't wrap
+    pub fn step(&self, current: usize, step: isize, count: usize) -> Option<usize> {
+        if count == 0 {
+            return None;
+        }
+        let next = current as isize + step;
+        if self.wrap {
+            Some(next.rem_euclid(count as isize) as usize)
+        } else {
+            Some(next)
+                .filter(|next| (0..count as isize).contains(next))
+                .map(|next| next as usize)
+        }
+    }
+
+    /// The item a key moves focus to from `current` out of `count`, or None when the key doesn't
+    /// move focus, i.e. it is an arrow key for the other orientation
+    pub fn next(&self, key: &str, current: usize, count: usize) -> Option<usize> {
+        let horizontal = self.orientation != Orientation::Vertical;
+        let vertical = self.orientation != Orientation::Horizontal;
+        match key {
+            "ArrowLeft" if horizontal => self.step(current, -1, count),
+            "ArrowUp" if vertical => self.step(current, -1, count),
+            "ArrowRight" if horizontal => self.step(current, 1, count),
+            "ArrowDown" if vertical => self.step(current, 1, count),
+            "Home" if count > 0 => Some(0),
+            "End" if count > 0 => Some(count - 1),
+            _ => None,
+        }
+    }
+
+    /// Handle a keydown on the widget, returning the item to focus and stopping the key from also
+    /// scrolling the page when it moves focus
+    pub fn keydown(
+        &self,
+        event: &web_sys::KeyboardEvent,
+        current: usize,
+        count: usize,
+    ) -> Option<usize> {
+        if event.alt_key() || event.ctrl_key() || event.meta_key() {
+            return None;
+        }
+        let next = self.next(&event.key(), current, count)?;
+        event.prevent_default();
+        Some(next)
+    }
+
+    /// The tabindex of an item, which keeps only the current one in the tab order
+    pub fn tabindex(index: usize, current: usize) -> &'static str {
+        if index == current {
+            "0"
+        } else {
+            "-1"
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn step_wraps_around_both_ends() {
+        let roving = RovingFocus::new(Orientation::Both);
+        assert_eq!(roving.step(0, 1, 3), Some(1));
+        assert_eq!(roving.step(2, 1, 3), Some(0));
+        assert_eq!(roving.step(0, -1, 3), Some(2));
+        assert_eq!(roving.step(1, 5, 3), Some(0));
+    }
+
+    #[test]
+    fn step_stops_at_the_ends_without_wrapping() {
+        let roving = RovingFocus::new(Orientation::Both).wrapping(false);
+        assert_eq!(roving.step(1, 1, 3), Some(2));
+        assert_eq!(roving.step(2, 1, 3), None);
+        assert_eq!(roving.step(0, -1, 3), None);
+    }
+
+    #[test]
+    fn nothing_to_move_to_without_items() {
+        let roving = RovingFocus::new(Orientation::Both);
+        assert_eq!(roving.step(0, 1, 0), None);
+        assert_eq!(roving.next("ArrowDown", 0, 0), None);
+        assert_eq!(roving.next("Home", 0, 0), None);
+        assert_eq!(roving.next("End", 0, 0), None);
+    }
+
+    #[test]
+    fn arrow_keys_follow_the_orientation() {
+        let horizontal = RovingFocus::new(Orientation::Horizontal);
+        assert_eq!(horizontal.next("ArrowRight", 0, 3), Some(1));
+        assert_eq!(horizontal.next("ArrowLeft", 0, 3), Some(2));
+        assert_eq!(horizontal.next("ArrowDown", 0, 3), None);
+        assert_eq!(horizontal.next("ArrowUp", 0, 3), None);
+
+        let vertical = RovingFocus::new(Orientation::Vertical);
+        assert_eq!(vertical.next("ArrowDown", 0, 3), Some(1));
+        assert_eq!(vertical.next("ArrowUp", 0, 3), Some(2));
+        assert_eq!(vertical.next("ArrowRight", 0, 3), None);
+        assert_eq!(vertical.next("ArrowLeft", 0, 3), None);
+
+        let both = RovingFocus::new(Orientation::Both);
+        assert_eq!(both.next("ArrowRight", 1, 3), Some(2));
+        assert_eq!(both.next("ArrowDown", 1, 3), Some(2));
+        assert_eq!(both.next("ArrowLeft", 1, 3), Some(0));
+        assert_eq!(both.next("ArrowUp", 1, 3), Some(0));
+    }
+
+    #[test]
+    fn home_and_end_go_to_the_first_and_last_items() {
+        let roving = RovingFocus::new(Orientation::Vertical).wrapping(false);
+        assert_eq!(roving.next("Home", 2, 4), Some(0));
+        assert_eq!(roving.next("End", 0, 4), Some(3));
+        assert_eq!(roving.next("Enter", 0, 4), None);
+    }
+
+    #[test]
+    fn only_the_current_item_is_in_the_tab_order() {
+        assert_eq!(RovingFocus::tabindex(1, 1), "0");
+        assert_eq!(RovingFocus::tabindex(0, 1), "-1");
+    }
+}
@@ -0,0 +1,34 @@
+/// A fieldless enum which forms render as a choice between its variants, i.e. a select or a radio
+/// group. Implement it with `#[derive(YFormSelect)]` rather than by hand.
+pub trait YFormSelect: Clone + Sized {
+    /// The value of every variant in declaration order, as used for the options' values
+    const VALUES: &'static [&'static str];
+    /// The label shown for every variant, in the same order as `VALUES`
+    const LABELS: &'static [&'static str];
+
+    /// The value of this variant
+    fn value(&self) -> &'static str;
+
+    /// The variant with the given value, if there is one
+    fn from_value(value: &str) -> Option<Self>;
+
+    /// The label shown for this variant
+    fn label(&self) -> &'static str {
+        let index = Self::VALUES
+            .iter()
+            .position(|value| *value == self.value())
+            .unwrap_or_default();
+        Self::LABELS[index]
+    }
+
+    /// The variant shown with the given label, if there is one
+    fn from_label(label: &str) -> Option<Self> {
+        let index = Self::LABELS.iter().position(|shown| *shown == label)?;
+        Self::from_value(Self::VALUES[index])
+    }
+
+    /// The first variant, which a form starts out with
+    fn first() -> Self {
+        Self::from_value(Self::VALUES[0]).expect("YFormSelect enums have at least one variant")
+    }
+}
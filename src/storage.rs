@@ -0,0 +1,154 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+use yew::Callback;
+
+/// Somewhere persistence features keep their values between page loads, keyed by string.
+///
+/// Each call reports its outcome through a callback rather than returning it, so that backends
+/// which can only answer asynchronously (i.e. IndexedDB) fit behind the same trait as
+/// `localStorage`. Failures are described as strings, the same way a form's `onerror` prop
+/// receives them.
+pub trait FormStorage {
+    /// Read the value stored under a key, or None if there isn't one
+    fn get(&self, key: &str, done: Callback<Result<Option<String>, String>>);
+    /// Store a value under a key, replacing any value already there
+    fn set(&self, key: &str, value: String, done: Callback<Result<(), String>>);
+    /// Remove the value stored under a key, succeeding if there wasn't one
+    fn remove(&self, key: &str, done: Callback<Result<(), String>>);
+}
+
+/// A `FormStorage` shared between the parts of an app which use it, compared by pointer so that it
+/// can be passed as a prop.
+#[derive(Clone)]
+pub struct SharedStorage(Rc<dyn FormStorage>);
+
+impl SharedStorage {
+    pub fn new(storage: impl FormStorage + 'static) -> Self {
+        SharedStorage(Rc::new(storage))
+    }
+}
+
+impl std::ops::Deref for SharedStorage {
+    type Target = dyn FormStorage;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+impl PartialEq for SharedStorage {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for SharedStorage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedStorage(..)")
+    }
+}
+
+/// Keeps values in the browser's `localStorage`, which lasts until it is cleared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LocalStorage;
+
+/// Keeps values in the browser's `sessionStorage`, which lasts until the tab is closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SessionStorage;
+
+// The window's localStorage or sessionStorage, which browsers can refuse access to, i.e. in some
+// private browsing modes
+fn web_storage(session: bool) -> Result<web_sys::Storage, String> {
+    let window =
+        web_sys::window().ok_or_else(|| "There's no window to store values in".to_string())?;
+    let storage = if session {
+        window.session_storage()
+    } else {
+        window.local_storage()
+    };
+    storage
+        .map_err(|error| format!("Couldn't access storage: {:?}", error))?
+        .ok_or_else(|| "The browser doesn't offer storage".to_string())
+}
+
+fn web_get(session: bool, key: &str) -> Result<Option<String>, String> {
+    web_storage(session)?
+        .get_item(key)
+        .map_err(|error| format!("Couldn't read {} from storage: {:?}", key, error))
+}
+
+fn web_set(session: bool, key: &str, value: &str) -> Result<(), String> {
+    web_storage(session)?
+        .set_item(key, value)
+        .map_err(|error| format!("Couldn't write {} to storage: {:?}", key, error))
+}
+
+fn web_remove(session: bool, key: &str) -> Result<(), String> {
+    web_storage(session)?
+        .remove_item(key)
+        .map_err(|error| format!("Couldn't remove {} from storage: {:?}", key, error))
+}
+
+impl FormStorage for LocalStorage {
+    fn get(&self, key: &str, done: Callback<Result<Option<String>, String>>) {
+        done.emit(web_get(false, key));
+    }
+
+    fn set(&self, key: &str, value: String, done: Callback<Result<(), String>>) {
+        done.emit(web_set(false, key, &value));
+    }
+
+    fn remove(&self, key: &str, done: Callback<Result<(), String>>) {
+        done.emit(web_remove(false, key));
+    }
+}
+
+impl FormStorage for SessionStorage {
+    fn get(&self, key: &str, done: Callback<Result<Option<String>, String>>) {
+        done.emit(web_get(true, key));
+    }
+
+    fn set(&self, key: &str, value: String, done: Callback<Result<(), String>>) {
+        done.emit(web_set(true, key, &value));
+    }
+
+    fn remove(&self, key: &str, done: Callback<Result<(), String>>) {
+        done.emit(web_remove(true, key));
+    }
+}
+
+/// Keeps values in memory for as long as the page is open, i.e. to drive persistence in tests
+/// without a browser. Clones share the same values.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStorage(Rc<RefCell<HashMap<String, String>>>);
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The values currently stored, by key
+    pub fn values(&self) -> HashMap<String, String> {
+        self.0.borrow().clone()
+    }
+}
+
+impl FormStorage for MemoryStorage {
+    fn get(&self, key: &str, done: Callback<Result<Option<String>, String>>) {
+        let value = self.0.borrow().get(key).cloned();
+        done.emit(Ok(value));
+    }
+
+    fn set(&self, key: &str, value: String, done: Callback<Result<(), String>>) {
+        self.0.borrow_mut().insert(key.to_string(), value);
+        done.emit(Ok(()));
+    }
+
+    fn remove(&self, key: &str, done: Callback<Result<(), String>>) {
+        self.0.borrow_mut().remove(key);
+        done.emit(Ok(()));
+    }
+}
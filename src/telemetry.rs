@@ -0,0 +1,73 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::field_edit::now_ms;
+
+/// What a form with the `ontelemetry` prop reports as it is filled in, for UX research into the
+/// fields where users stall. `F` is the enum of fields generated for the form.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TelemetryEvent<F> {
+    /// A field first held a valid value, `duration_ms` after it was first focused
+    FieldCompleted {
+        field: F,
+        /// The field's name in the struct
+        name: &'static str,
+        duration_ms: f64,
+    },
+    /// The form was submitted, `duration_ms` after its first field was focused
+    FormCompleted { duration_ms: f64 },
+}
+
+/// Times how long each field of a form takes to complete, from when it is first focused, and the
+/// form as a whole, from when any of its fields is first focused.
+#[derive(Debug)]
+pub struct CompletionTimer<F> {
+    started_ms: Option<f64>,
+    focused_ms: HashMap<F, f64>,
+    completed: HashSet<F>,
+}
+
+impl<F> Default for CompletionTimer<F> {
+    fn default() -> Self {
+        Self {
+            started_ms: None,
+            focused_ms: HashMap::new(),
+            completed: HashSet::new(),
+        }
+    }
+}
+
+impl<F: Copy + Eq + Hash> CompletionTimer<F> {
+    /// Start timing a field, unless it has been focused before
+    pub fn focus(&mut self, field: F) {
+        let now = now_ms();
+        self.started_ms.get_or_insert(now);
+        self.focused_ms.entry(field).or_insert(now);
+    }
+
+    /// Whether a field has been focused and is yet to be completed
+    pub fn is_pending(&self, field: F) -> bool {
+        self.focused_ms.contains_key(&field) && !self.completed.contains(&field)
+    }
+
+    /// Stop timing a field which now holds a valid value, giving how long it took
+    pub fn complete(&mut self, field: F, name: &'static str) -> Option<TelemetryEvent<F>> {
+        if !self.is_pending(field) {
+            return None;
+        }
+        self.completed.insert(field);
+        Some(TelemetryEvent::FieldCompleted {
+            field,
+            name,
+            duration_ms: now_ms() - self.focused_ms[&field],
+        })
+    }
+
+    /// Stop timing the form once it is submitted, giving how long it took, and start over
+    pub fn finish(&mut self) -> Option<TelemetryEvent<F>> {
+        let started_ms = std::mem::take(self).started_ms?;
+        Some(TelemetryEvent::FormCompleted {
+            duration_ms: now_ms() - started_ms,
+        })
+    }
+}
@@ -0,0 +1,129 @@
+/// Classes added to a form's elements alongside its own, for a form's `theme` prop or a
+/// `ContextProvider<Theme>` around it. The classes are added as the form renders, so passing a
+/// different theme restyles forms which are already mounted, i.e. when switching to dark mode.
+///
+/// Each field holds space separated classes, empty for none. Start from one of the presets and
+/// change what needs changing with struct update syntax.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Theme {
+    /// Added to the form as `formula-y-theme-{name}`, for stylesheets keyed by theme
+    pub name: String,
+    pub form: String,
+    /// The element wrapping each field
+    pub item: String,
+    pub label: String,
+    /// Text inputs, textareas, selects and the fieldsets around radios and nested forms
+    pub input: String,
+    pub checkbox: String,
+    /// The region holding a field's errors
+    pub error: String,
+    /// The submit button, and Next in a multi-step form
+    pub submit: String,
+    /// Every other button
+    pub button: String,
+    /// How tightly the form's fields are spaced, which a form's `density` prop overrides
+    pub density: Density,
+}
+
+/// How tightly a form's fields are spaced, added to the form and to the element wrapping each
+/// field as a `formula-y-density-{name}` class, i.e. `formula-y-density-compact`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Density {
+    /// Less padding and margins, for data heavy screens
+    Compact,
+    #[default]
+    Comfortable,
+    /// More room between fields, for short forms
+    Spacious,
+}
+
+impl Density {
+    /// The density's name, as used in its class
+    pub fn name(&self) -> &'static str {
+        match self {
+            Density::Compact => "compact",
+            Density::Comfortable => "comfortable",
+            Density::Spacious => "spacious",
+        }
+    }
+
+    /// The modifier class added to the form and its items
+    pub fn class(&self) -> String {
+        format!("formula-y-density-{}", self.name())
+    }
+}
+
+impl Theme {
+    /// The form's own classes, styled by the stylesheet for `formula-y-theme-light`
+    pub fn light() -> Self {
+        Self::named("light")
+    }
+
+    /// The form's own classes, styled by the stylesheet for `formula-y-theme-dark`
+    pub fn dark() -> Self {
+        Self::named("dark")
+    }
+
+    /// Bootstrap 5's form classes
+    pub fn bootstrap() -> Self {
+        Self {
+            name: "bootstrap".to_string(),
+            form: String::new(),
+            item: "mb-3".to_string(),
+            label: "form-label".to_string(),
+            input: "form-control".to_string(),
+            checkbox: "form-check-input".to_string(),
+            error: "invalid-feedback d-block".to_string(),
+            submit: "btn btn-primary".to_string(),
+            button: "btn btn-secondary".to_string(),
+            density: Density::default(),
+        }
+    }
+
+    /// Tailwind utility classes for a plain stacked layout
+    pub fn tailwind() -> Self {
+        Self {
+            name: "tailwind".to_string(),
+            form: "space-y-4".to_string(),
+            item: "flex flex-col gap-1".to_string(),
+            label: "text-sm font-medium text-gray-700".to_string(),
+            input: "rounded-md border border-gray-300 px-3 py-2".to_string(),
+            checkbox: "h-4 w-4 rounded border-gray-300".to_string(),
+            error: "text-sm text-red-600".to_string(),
+            submit: "rounded-md bg-blue-600 px-4 py-2 text-white".to_string(),
+            button: "rounded-md border border-gray-300 px-4 py-2".to_string(),
+            density: Density::default(),
+        }
+    }
+
+    fn named(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Sets how tightly the form's fields are spaced
+    pub fn with_density(self, density: Density) -> Self {
+        Self { density, ..self }
+    }
+
+    /// The classes added to the form element, including the ones naming the theme and density
+    pub fn form_classes(&self) -> String {
+        if self.name.is_empty() {
+            format!("{} {}", self.density.class(), self.form)
+        } else {
+            format!(
+                "formula-y-theme-{} {} {}",
+                self.name,
+                self.density.class(),
+                self.form
+            )
+        }
+    }
+
+    /// The classes added to the element wrapping each field, including the density's
+    pub fn item_classes(&self) -> String {
+        format!("{} {}", self.density.class(), self.item)
+    }
+}
@@ -0,0 +1,137 @@
+use chrono_tz::Tz;
+
+use crate::YFormSelect;
+
+// ZONE_VALUES and ZONE_LABELS, written by the build script from chrono-tz's zones
+include!(concat!(env!("OUT_DIR"), "/time_zones.rs"));
+
+/// Time zones are offered grouped by region, labelled by the place within it, i.e. `Berlin` under
+/// `Europe`. Only zones in a region are offered, along with UTC, though a field can still hold any
+/// other zone chrono-tz knows, i.e. `US/Eastern`. A form starts out with the browser's zone.
+impl YFormSelect for Tz {
+    const VALUES: &'static [&'static str] = ZONE_VALUES;
+    const LABELS: &'static [&'static str] = ZONE_LABELS;
+
+    fn value(&self) -> &'static str {
+        self.name()
+    }
+
+    fn from_value(value: &str) -> Option<Self> {
+        value.parse().ok()
+    }
+
+    fn label(&self) -> &'static str {
+        ZONE_VALUES
+            .iter()
+            .position(|value| *value == self.name())
+            .map_or(self.name(), |index| ZONE_LABELS[index])
+    }
+
+    fn first() -> Self {
+        detect_time_zone()
+    }
+}
+
+/// The browser's time zone, or UTC when it has none chrono-tz knows
+#[cfg(target_arch = "wasm32")]
+pub fn detect_time_zone() -> Tz {
+    let options = js_sys::Intl::DateTimeFormat::new(&js_sys::Array::new(), &js_sys::Object::new())
+        .resolved_options();
+    js_sys::Reflect::get(&options, &"timeZone".into())
+        .ok()
+        .and_then(|zone| zone.as_string())
+        .and_then(|zone| zone.parse().ok())
+        .unwrap_or(Tz::UTC)
+}
+
+/// The browser's time zone, or UTC when it has none chrono-tz knows, as when the form is driven
+/// natively, i.e. by its tester
+#[cfg(not(target_arch = "wasm32"))]
+pub fn detect_time_zone() -> Tz {
+    Tz::UTC
+}
+
+/// The zones offered whose name or label contains `search`, ignoring case, as `(value, label)`
+/// pairs grouped by region in alphabetical order. The `chosen` zone is kept whatever the search, so
+/// the select still shows it. Zones outside of a region, i.e. UTC, have an empty region.
+pub fn time_zones_by_region(
+    search: &str,
+    chosen: &str,
+) -> Vec<(&'static str, Vec<(&'static str, &'static str)>)> {
+    let search = search.trim().to_lowercase();
+    let mut regions: Vec<(&'static str, Vec<(&'static str, &'static str)>)> = Vec::new();
+    for (value, label) in ZONE_VALUES.iter().zip(ZONE_LABELS) {
+        let matches = value.to_lowercase().replace('_', " ").contains(&search)
+            || label.to_lowercase().contains(&search);
+        if !matches && *value != chosen {
+            continue;
+        }
+        let region = value.split_once('/').map_or("", |(region, _)| region);
+        match regions.last_mut() {
+            Some((last, zones)) if *last == region => zones.push((value, label)),
+            _ => regions.push((region, vec![(value, label)])),
+        }
+    }
+    regions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zones_are_labelled_by_their_place() {
+        assert_eq!(Tz::Europe__Berlin.label(), "Berlin");
+        assert_eq!(
+            Tz::America__Argentina__Buenos_Aires.label(),
+            "Argentina / Buenos Aires"
+        );
+        assert_eq!(Tz::UTC.label(), "UTC");
+        assert_eq!(Tz::US__Eastern.label(), "US/Eastern");
+    }
+
+    #[test]
+    fn zones_outside_of_the_offered_ones_still_parse() {
+        assert_eq!(Tz::from_value("US/Eastern"), Some(Tz::US__Eastern));
+        assert_eq!(Tz::from_value("Europe/Atlantis"), None);
+        assert!(!ZONE_VALUES.contains(&"US/Eastern"));
+    }
+
+    #[test]
+    fn zones_are_grouped_by_region() {
+        let regions = time_zones_by_region("", "UTC");
+        let names = regions
+            .iter()
+            .map(|(region, _)| *region)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                "Africa",
+                "America",
+                "Antarctica",
+                "Arctic",
+                "Asia",
+                "Atlantic",
+                "Australia",
+                "Europe",
+                "Indian",
+                "Pacific",
+                ""
+            ]
+        );
+    }
+
+    #[test]
+    fn searches_match_names_and_labels_keeping_the_chosen_zone() {
+        let regions = time_zones_by_region("new york", "Europe/Berlin");
+        assert_eq!(
+            regions,
+            [
+                ("America", vec![("America/New_York", "New York")]),
+                ("Europe", vec![("Europe/Berlin", "Berlin")]),
+            ]
+        );
+        assert!(time_zones_by_region("atlantis", "").is_empty());
+    }
+}
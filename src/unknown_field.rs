@@ -0,0 +1,15 @@
+use std::error::Error;
+use std::fmt;
+
+/// The error from parsing a form's field enum (i.e. `"email".parse::<DataField>()`) from a name
+/// which isn't one of the struct's fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownField(pub String);
+
+impl fmt::Display for UnknownField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "there's no field named {}", self.0)
+    }
+}
+
+impl Error for UnknownField {}
@@ -0,0 +1,75 @@
+/// What a `FieldVisitor` is told about each field besides its value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldMeta {
+    /// The field's name in the struct, i.e. `agree_to_terms`
+    pub name: &'static str,
+    /// The field's label, i.e. `Agree To Terms` or the one given with `#[yform(label = "...")]`
+    pub label: &'static str,
+}
+
+/// A field's value, as passed to a `FieldVisitor`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldValue {
+    /// An `Option` field holding `None`
+    Empty,
+    Text(String),
+    Bool(bool),
+    /// A number, as it displays
+    Number(String),
+    /// A `chrono` date or date and time, as it displays
+    Date(String),
+    /// The label of the variant picked in a `YFormSelect` enum
+    Choice(String),
+    /// The items of a `Vec<String>`
+    List(Vec<String>),
+    /// The values of a nested struct's fields, by name
+    Nested(Vec<(&'static str, FieldValue)>),
+    /// The values of each item of a `Vec` of nested structs
+    NestedList(Vec<Vec<(&'static str, FieldValue)>>),
+}
+
+impl FieldValue {
+    /// The value as a single line of text. Lists are joined with `", "`, and nested values are
+    /// written as `name: value` pairs in braces.
+    pub fn to_text(&self) -> String {
+        match self {
+            FieldValue::Empty => String::new(),
+            FieldValue::Text(text)
+            | FieldValue::Number(text)
+            | FieldValue::Date(text)
+            | FieldValue::Choice(text) => text.clone(),
+            FieldValue::Bool(value) => value.to_string(),
+            FieldValue::List(items) => items.join(", "),
+            FieldValue::Nested(values) => nested_text(values),
+            FieldValue::NestedList(items) => items
+                .iter()
+                .map(|values| nested_text(values))
+                .collect::<Vec<_>>()
+                .join(", "),
+        }
+    }
+}
+
+fn nested_text(values: &[(&'static str, FieldValue)]) -> String {
+    let values = values
+        .iter()
+        .map(|(name, value)| format!("{}: {}", name, value.to_text()))
+        .collect::<Vec<_>>();
+    format!("{{{}}}", values.join(", "))
+}
+
+/// Walks the fields of a struct deriving `YForm`, through the `visit_fields` method the derive
+/// generates on the struct and its component, in the order they are declared. `F` is the enum of
+/// fields generated for the form, i.e. `DataField` for a struct named `Data`.
+///
+/// Exporters which don't care which form they are given can implement it for every `F`, and
+/// closures taking `(F, &FieldMeta, FieldValue)` implement it too.
+pub trait FieldVisitor<F> {
+    fn visit(&mut self, field: F, meta: &FieldMeta, value: FieldValue);
+}
+
+impl<F, T: FnMut(F, &FieldMeta, FieldValue)> FieldVisitor<F> for T {
+    fn visit(&mut self, field: F, meta: &FieldMeta, value: FieldValue) {
+        self(field, meta, value)
+    }
+}
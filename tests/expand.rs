@@ -0,0 +1,40 @@
+// What the derive generates, checked against the snapshots in tests/expand so that changes to the
+// generated code show up in review. Run with SNAPSHOTS=overwrite to accept changed output. The
+// test-utils and debug-panel features add to every form, so the snapshots are only checked
+// without them.
+#![cfg(all(
+    feature = "expand",
+    not(feature = "test-utils"),
+    not(feature = "debug-panel")
+))]
+
+use std::{env, fs};
+
+fn assert_snapshot(name: &str, generated: &str) {
+    let path = format!(
+        "{}/tests/expand/{}.expanded.rs",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    );
+    if env::var("SNAPSHOTS").as_deref() == Ok("overwrite") {
+        fs::write(&path, generated).unwrap();
+        return;
+    }
+    let expected = fs::read_to_string(&path).unwrap_or_default();
+    assert!(
+        expected == generated,
+        "the expansion doesn't match {}, run with SNAPSHOTS=overwrite to accept it",
+        path
+    );
+}
+
+#[test]
+fn signup() {
+    let generated = formula_y::expand! {
+        struct Signup {
+            email: String,
+            age: Option<u32>,
+        }
+    };
+    assert_snapshot("signup", generated);
+}
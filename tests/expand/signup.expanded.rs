@@ -0,0 +1,1798 @@
+impl Signup {
+    pub fn new() -> Self {
+        Self {
+            email: String::new(),
+            age: None,
+        }
+    }
+    /// Hand each field, with its name, label and value, to a visitor, i.e. to export the
+    /// values without code for each form
+    pub fn visit_fields(&self, visitor: &mut impl FieldVisitor<SignupField>) {
+        visitor
+            .visit(
+                SignupField::Email,
+                &FieldMeta {
+                    name: "email",
+                    label: "Email",
+                },
+                FieldValue::Text(self.email.clone()),
+            );
+        visitor
+            .visit(
+                SignupField::Age,
+                &FieldMeta {
+                    name: "age",
+                    label: "Age",
+                },
+                self
+                    .age
+                    .map_or(
+                        FieldValue::Empty,
+                        |value| FieldValue::Number(value.to_string()),
+                    ),
+            );
+    }
+    /// The names of the fields as a line of CSV, for the first line of rows from `to_csv_row`
+    pub fn csv_header() -> String {
+        CsvRow::header(&["email", "age"], ',')
+    }
+    /// The values of the fields as a line of CSV, without a trailing newline
+    pub fn to_csv_row(&self) -> String {
+        let mut row = CsvRow::new(',');
+        self.visit_fields(&mut row);
+        row.finish()
+    }
+    /// The names of the fields as a line of tab separated values
+    pub fn tsv_header() -> String {
+        CsvRow::header(&["email", "age"], '\t')
+    }
+    /// The values of the fields as a line of tab separated values, i.e. for pasting into a
+    /// spreadsheet
+    pub fn to_tsv_row(&self) -> String {
+        let mut row = CsvRow::new('\t');
+        self.visit_fields(&mut row);
+        row.finish()
+    }
+    /// Read a line of CSV, as written by `to_csv_row`, back into the struct, parsing each cell
+    /// as the type of its field
+    pub fn from_csv_row(row: &str) -> Result<Self, ImportError> {
+        Self::from_delimited_row(row, ',')
+    }
+    /// Read a line of tab separated values, i.e. a row pasted from a spreadsheet, into the
+    /// struct
+    pub fn from_tsv_row(row: &str) -> Result<Self, ImportError> {
+        Self::from_delimited_row(row, '\t')
+    }
+    fn from_delimited_row(row: &str, delimiter: char) -> Result<Self, ImportError> {
+        let cells = CsvRow::parse(row, delimiter);
+        if cells.len() != 2usize {
+            return Err(ImportError::ColumnCount {
+                expected: 2usize,
+                found: cells.len(),
+            });
+        }
+        Ok(Self {
+            email: {
+                let cell = cells[0usize].as_str();
+                Ok::<_, ImportError>(cell.to_string())?
+            },
+            age: {
+                let cell = cells[1usize].as_str();
+                if cell.is_empty() {
+                    Ok(None)
+                } else {
+                    (cell
+                        .trim()
+                        .parse::<u32>()
+                        .map_err(|_| ImportError::InvalidValue {
+                            field: "age",
+                            value: cell.to_string(),
+                        }))
+                        .map(Some)
+                }?
+            },
+        })
+    }
+}
+pub struct SignupForm {
+    inner: std::rc::Rc<Signup>,
+    display_required_warnings: bool,
+    submitted: bool,
+    dirty: bool,
+    abandoned: bool,
+    locked: bool,
+    context_theme: Option<formula_y::Theme>,
+    theme_handle: Option<yew::context::ContextHandle<formula_y::Theme>>,
+    id_prefix: String,
+    submit_failed: bool,
+    invalid_numbers: std::collections::HashMap<SignupField, String>,
+    focus_snapshot: Option<(SignupField, SignupFormMsg)>,
+    invalid_nested: std::collections::HashSet<(SignupField, usize)>,
+    init_seen: Option<Signup>,
+    pending_init: Option<Signup>,
+    inline_editing: Option<SignupField>,
+    inline_changed: bool,
+    focus_on_render: Option<&'static str>,
+    value_changed: bool,
+    auto_submit_timer: Option<gloo_timers::callback::Timeout>,
+    failed_submits: Option<usize>,
+    cooldown_remaining_ms: u32,
+    cooldown_timer: Option<gloo_timers::callback::Interval>,
+    deadline: Option<formula_y::Deadline>,
+    deadline_timer: Option<gloo_timers::callback::Interval>,
+    expired: bool,
+    internal_error: Option<String>,
+    announcement: String,
+    edited: std::collections::HashSet<SignupField>,
+    edits: Vec<FieldEdit<SignupField>>,
+    completion_timer: CompletionTimer<SignupField>,
+    loading: std::collections::HashSet<SignupField>,
+    composing: bool,
+    document_listeners: Vec<gloo_events::EventListener>,
+    initial: std::rc::Rc<Signup>,
+    reset_count: Option<usize>,
+    submitted_reset_count: Option<usize>,
+}
+impl SignupForm {
+    /// The class naming this form, alongside `formula-y-form`
+    pub const FORM_CLASS: &'static str = "signup-form";
+    ///The class on the `email` field's label
+    pub const EMAIL_LABEL_CLASS: &'static str = "email-label";
+    ///The class on the `email` field's input
+    pub const EMAIL_INPUT_CLASS: &'static str = "email-input";
+    ///The class on the `age` field's label
+    pub const AGE_LABEL_CLASS: &'static str = "age-label";
+    ///The class on the `age` field's input
+    pub const AGE_INPUT_CLASS: &'static str = "age-input";
+    /// The field name, label class and input class of each rendered field
+    pub const CLASSES: &'static [(&'static str, &'static str, &'static str)] = &[
+        ("email", "email-label", "email-input"),
+        ("age", "age-label", "age-input"),
+    ];
+    fn from_inner(inner: Signup) -> Self {
+        static INSTANCES: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(
+            0,
+        );
+        let instance = INSTANCES.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let form = Self {
+            id_prefix: format!("{}-{}", "signup-form", instance),
+            initial: std::rc::Rc::new(inner.clone()),
+            inner: std::rc::Rc::new(inner),
+            submitted: false,
+            display_required_warnings: false,
+            dirty: false,
+            abandoned: false,
+            locked: false,
+            context_theme: None,
+            theme_handle: None,
+            submit_failed: false,
+            invalid_numbers: std::collections::HashMap::new(),
+            focus_snapshot: None,
+            invalid_nested: std::collections::HashSet::new(),
+            init_seen: None,
+            pending_init: None,
+            inline_editing: None,
+            inline_changed: false,
+            focus_on_render: None,
+            value_changed: false,
+            auto_submit_timer: None,
+            failed_submits: None,
+            cooldown_remaining_ms: 0,
+            cooldown_timer: None,
+            deadline: None,
+            deadline_timer: None,
+            expired: false,
+            internal_error: None,
+            announcement: String::new(),
+            edited: std::collections::HashSet::new(),
+            edits: Vec::new(),
+            completion_timer: CompletionTimer::default(),
+            loading: std::collections::HashSet::new(),
+            composing: false,
+            document_listeners: Vec::new(),
+            reset_count: None,
+            submitted_reset_count: None,
+        };
+        form
+    }
+    fn inner(&self) -> &Signup {
+        &self.inner
+    }
+    fn inner_mut(&mut self) -> &mut Signup {
+        std::rc::Rc::make_mut(&mut self.inner)
+    }
+    fn field_id(&self, field: &str) -> String {
+        format!("{}-{}", self.id_prefix, field)
+    }
+    fn form_described_by(&self, ctx: &Context<Self>) -> Option<String> {
+        let description_id = ctx
+            .props()
+            .description
+            .as_ref()
+            .map(|_| format!("{}-description", self.id_prefix));
+        let ids = ctx
+            .props()
+            .described_by
+            .iter()
+            .cloned()
+            .chain(description_id)
+            .collect::<Vec<_>>();
+        (!ids.is_empty()).then(|| ids.join(" "))
+    }
+    pub fn is_submitted(&self) -> bool {
+        self.submitted
+    }
+    fn label_for(&self, ctx: &Context<Self>, field: SignupField, label: &str) -> String {
+        ctx.props()
+            .labels
+            .as_ref()
+            .and_then(|labels| labels.get(&field))
+            .cloned()
+            .unwrap_or_else(|| label.to_string())
+    }
+    fn theme(&self, ctx: &Context<Self>) -> formula_y::Theme {
+        let theme = ctx
+            .props()
+            .theme
+            .clone()
+            .or_else(|| self.context_theme.clone())
+            .unwrap_or_default();
+        match ctx.props().density {
+            Some(density) => theme.with_density(density),
+            None => theme,
+        }
+    }
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+    pub fn visit_fields(&self, visitor: &mut impl FieldVisitor<SignupField>) {
+        self.inner().visit_fields(visitor)
+    }
+    pub fn edits(&self) -> &[FieldEdit<SignupField>] {
+        &self.edits
+    }
+    fn audits(ctx: &Context<Self>) -> bool {
+        ctx.props().audit_trail.unwrap_or_default() || ctx.props().onedit.is_some()
+    }
+    fn audit_snapshot(&self, audit: bool) -> Option<Vec<FieldValue>> {
+        audit
+            .then(|| {
+                let mut values = Vec::new();
+                self.visit_fields(
+                    &mut |_: SignupField, _: &FieldMeta, value: FieldValue| {
+                        values.push(value);
+                    },
+                );
+                values
+            })
+    }
+    fn record_edits(
+        &mut self,
+        before: Option<Vec<FieldValue>>,
+    ) -> &[FieldEdit<SignupField>] {
+        let recorded = self.edits.len();
+        if let Some(before) = before {
+            let mut before = before.into_iter();
+            let mut edits = Vec::new();
+            self.visit_fields(
+                &mut |field: SignupField, meta: &FieldMeta, value: FieldValue| {
+                    if let Some(old) = before.next().filter(|old| *old != value) {
+                        edits.push(FieldEdit::now(field, meta.name, old, value));
+                    }
+                },
+            );
+            self.edits.extend(edits);
+        }
+        &self.edits[recorded..]
+    }
+    fn emit_edits(&mut self, ctx: &Context<Self>, before: Option<Vec<FieldValue>>) {
+        let edits = self.record_edits(before);
+        if let Some(onedit) = &ctx.props().onedit {
+            for edit in edits {
+                onedit.emit(edit.clone());
+            }
+        }
+    }
+    fn event_field(&self, event: &web_sys::Event) -> Option<SignupField> {
+        let target = event.target()?.dyn_into::<web_sys::Element>().ok()?;
+        let id = target.closest("[id]").ok()??.id();
+        [SignupField::Email, SignupField::Age]
+            .into_iter()
+            .find(|field| self.field_id(Self::field_id_suffix(*field)) == id)
+    }
+    fn report_abandon(&mut self, ctx: &Context<Self>) {
+        if let Some(onabandon) = &ctx.props().onabandon {
+            if self.dirty && !self.submitted && !self.abandoned {
+                self.abandoned = true;
+                let mut filled = Vec::new();
+                self.visit_fields(
+                    &mut |field: SignupField, _: &FieldMeta, value: FieldValue| {
+                        if !value.to_text().is_empty() {
+                            filled.push(field);
+                        }
+                    },
+                );
+                onabandon.emit(filled);
+            }
+        }
+    }
+    fn emit_completed_fields(&mut self, ctx: &Context<Self>) {
+        let ontelemetry = match &ctx.props().ontelemetry {
+            Some(ontelemetry) => ontelemetry,
+            None => return,
+        };
+        let mut completed = Vec::new();
+        self.visit_fields(
+            &mut |field: SignupField, meta: &FieldMeta, value: FieldValue| {
+                if !value.to_text().is_empty() {
+                    completed.push((field, meta.name));
+                }
+            },
+        );
+        for (field, name) in completed {
+            if self.completion_timer.is_pending(field) && self.field_is_valid(field)
+                && !self.field_is_missing(field)
+            {
+                if let Some(event) = self.completion_timer.complete(field, name) {
+                    ontelemetry.emit(event);
+                }
+            }
+        }
+    }
+    pub fn required_components_provided(&self) -> bool {
+        if self.inner().email == "" {
+            return false;
+        }
+        true
+    }
+    fn regexes_match(&self) -> bool {
+        true
+    }
+    fn choices_match(&self) -> bool {
+        true
+    }
+    fn item_counts_fit(&self) -> bool {
+        true
+    }
+    fn fields_validate(&self) -> bool {
+        true
+    }
+    fn can_submit(&self, enforce_required: bool) -> bool {
+        (self.required_components_provided() || !enforce_required)
+            && self.invalid_numbers.is_empty() && self.regexes_match()
+            && self.choices_match() && self.item_counts_fit() && self.fields_validate()
+            && self.invalid_nested.is_empty()
+    }
+    fn try_submit(&mut self, required_policy: RequiredPolicy) -> bool {
+        if self.can_submit(required_policy.blocks_submit()) {
+            self.submitted = true;
+            self
+                .display_required_warnings = required_policy == RequiredPolicy::WarnOnly
+                && !self.required_components_provided();
+            true
+        } else {
+            false
+        }
+    }
+    fn submit_step(&mut self, _enforce_required: bool) -> bool {
+        false
+    }
+    fn hold_for_review(&mut self) -> bool {
+        false
+    }
+    fn dispatch_change(target: &web_sys::EventTarget) -> Result<(), String> {
+        let init = web_sys::EventInit::new();
+        init.set_bubbles(true);
+        let change = web_sys::Event::new_with_event_init_dict("change", &init)
+            .map_err(|error| format!("Couldn't create a change event: {:?}", error))?;
+        target
+            .dispatch_event(&change)
+            .map(|_| ())
+            .map_err(|error| format!("Couldn't send a change event: {:?}", error))
+    }
+    fn loading_fields(ctx: &Context<Self>) -> std::collections::HashSet<SignupField> {
+        if ctx.props().loading.unwrap_or_default() {
+            [SignupField::Email, SignupField::Age].into_iter().collect()
+        } else {
+            ctx.props().loading_fields.iter().flatten().copied().collect()
+        }
+    }
+    fn field_access(&self, ctx: &Context<Self>, field: SignupField) -> FieldAccess {
+        ctx.props()
+            .field_policy
+            .as_ref()
+            .map_or(FieldAccess::Editable, |policy| policy.access(field))
+    }
+    fn field_id_suffix(field: SignupField) -> &'static str {
+        match field {
+            SignupField::Email => "email",
+            SignupField::Age => "age",
+        }
+    }
+    fn field_is_valid(&self, field: SignupField) -> bool {
+        match field {
+            SignupField::Email => true,
+            SignupField::Age => !self.invalid_numbers.contains_key(&SignupField::Age),
+        }
+    }
+    fn field_is_missing(&self, field: SignupField) -> bool {
+        match field {
+            SignupField::Email => self.inner().email == "",
+            SignupField::Age => false,
+        }
+    }
+    fn confirm_inline(
+        &mut self,
+        field: SignupField,
+        enforce_required: bool,
+    ) -> Option<bool> {
+        if self.inline_editing != Some(field) || !self.field_is_valid(field) {
+            return None;
+        }
+        if enforce_required {
+            self.display_required_warnings = self.field_is_missing(field);
+            if self.display_required_warnings {
+                return None;
+            }
+        }
+        self.inline_editing = None;
+        Some(std::mem::take(&mut self.inline_changed))
+    }
+    fn field_snapshot(&self, field: SignupField) -> Option<SignupFormMsg> {
+        match field {
+            SignupField::Email => {
+                Some(SignupFormMsg::UpdateEmail(self.inner().email.clone()))
+            }
+            SignupField::Age => {
+                Some(
+                    match self.invalid_numbers.get(&SignupField::Age) {
+                        Some(text) => {
+                            SignupFormMsg::InvalidNumber(SignupField::Age, text.clone())
+                        }
+                        None => SignupFormMsg::UpdateAge(self.inner().age),
+                    },
+                )
+            }
+        }
+    }
+    fn set_value(&mut self, inner: Signup) {
+        self.inner = std::rc::Rc::new(inner);
+    }
+    fn reset(&mut self) {
+        let inner = (*self.initial).clone();
+        let id_prefix = std::mem::take(&mut self.id_prefix);
+        let reset_count = self.reset_count;
+        let submitted_reset_count = self.submitted_reset_count;
+        let failed_submits = self.failed_submits;
+        let cooldown_remaining_ms = self.cooldown_remaining_ms;
+        let cooldown_timer = self.cooldown_timer.take();
+        let deadline = self.deadline;
+        let deadline_timer = self.deadline_timer.take();
+        let expired = self.expired;
+        let composing = self.composing;
+        let document_listeners = std::mem::take(&mut self.document_listeners);
+        let loading = std::mem::take(&mut self.loading);
+        let edits = std::mem::take(&mut self.edits);
+        let context_theme = self.context_theme.take();
+        let theme_handle = self.theme_handle.take();
+        let init_seen = self.init_seen.take();
+        *self = Self::from_inner(inner);
+        self.id_prefix = id_prefix;
+        self.reset_count = reset_count;
+        self.submitted_reset_count = submitted_reset_count;
+        self.failed_submits = failed_submits;
+        self.cooldown_remaining_ms = cooldown_remaining_ms;
+        self.cooldown_timer = cooldown_timer;
+        self.deadline = deadline;
+        self.deadline_timer = deadline_timer;
+        self.expired = expired;
+        self.composing = composing;
+        self.document_listeners = document_listeners;
+        self.loading = loading;
+        self.edits = edits;
+        self.context_theme = context_theme;
+        self.theme_handle = theme_handle;
+        self.init_seen = init_seen;
+    }
+    fn take_init(
+        &mut self,
+        init: &Signup,
+        fields: &std::collections::HashSet<SignupField>,
+    ) {
+        let Signup { email, age } = init.clone();
+        if fields.contains(&SignupField::Email) {
+            self.inner_mut().email = email;
+            self.edited.remove(&SignupField::Email);
+            self.invalid_numbers.remove(&SignupField::Email);
+        }
+        if fields.contains(&SignupField::Age) {
+            self.inner_mut().age = age;
+            self.edited.remove(&SignupField::Age);
+            self.invalid_numbers.remove(&SignupField::Age);
+        }
+    }
+    fn is_edited(&self, field: SignupField) -> bool {
+        self.edited.contains(&field)
+            || match field {
+                SignupField::Email => {
+                    self.invalid_numbers.contains_key(&SignupField::Email)
+                }
+                SignupField::Age => self.invalid_numbers.contains_key(&SignupField::Age),
+            }
+    }
+    fn submit_value(&self) -> Signup {
+        (*self.inner).clone()
+    }
+    fn current_value(&self) -> Signup {
+        (*self.inner).clone()
+    }
+    fn announce(&mut self, message: String) {
+        self
+            .announcement = if message == self.announcement {
+            format!("{}\u{a0}", message)
+        } else {
+            message
+        };
+    }
+    fn announce_submitted(&mut self, ctx: &Context<Self>) {
+        let message = ctx
+            .props()
+            .submitted_announcement
+            .clone()
+            .unwrap_or_else(|| "Form submitted".to_string());
+        self.announce(message);
+    }
+    fn announce_errors(&mut self, ctx: &Context<Self>) {
+        let count = ([self.email_is_flagged(), self.age_is_flagged()]
+            .into_iter()
+            .filter(|flagged| *flagged)
+            .count() + self.invalid_nested.len())
+            .max(1);
+        let message = match &ctx.props().errors_announcement {
+            Some(errors_announcement) => errors_announcement.message(count),
+            None if count == 1 => "1 error found".to_string(),
+            None => format!("{} errors found", count),
+        };
+        self.announce(message);
+    }
+    fn start_cooldown(&mut self, ctx: &Context<Self>, failures: usize) {
+        let base = match ctx.props().submit_cooldown_ms {
+            Some(base) if base > 0 => base,
+            _ => return,
+        };
+        let max = ctx.props().submit_cooldown_max_ms.unwrap_or(5 * 60 * 1000).max(base);
+        let doublings = (failures - 1).min(31) as u32;
+        self
+            .cooldown_remaining_ms = base
+            .saturating_mul(2u32.saturating_pow(doublings))
+            .min(max);
+        let link = ctx.link().clone();
+        self
+            .cooldown_timer = Some(
+            gloo_timers::callback::Interval::new(
+                1000,
+                move || {
+                    link.send_message(SignupFormMsg::CooldownTick);
+                },
+            ),
+        );
+        if let Some(oncooldown) = &ctx.props().oncooldown {
+            oncooldown.emit(self.cooldown_remaining_ms);
+        }
+    }
+    fn start_deadline(&mut self, ctx: &Context<Self>) {
+        self.deadline = ctx.props().deadline;
+        self.deadline_timer = None;
+        self.expired = false;
+        if self.deadline.is_some() {
+            let link = ctx.link().clone();
+            self
+                .deadline_timer = Some(
+                gloo_timers::callback::Interval::new(
+                    1000,
+                    move || {
+                        link.send_message(SignupFormMsg::DeadlineTick);
+                    },
+                ),
+            );
+            self.check_deadline(ctx);
+        }
+    }
+    fn check_deadline(&mut self, ctx: &Context<Self>) {
+        if self.expired || !self.deadline.map_or(false, |deadline| deadline.is_past()) {
+            return;
+        }
+        self.expired = true;
+        self.deadline_timer = None;
+        if let Some(onexpire) = &ctx.props().onexpire {
+            onexpire.emit(self.current_value());
+        }
+    }
+    pub fn snapshot(&self) -> SignupFormState {
+        SignupFormState {
+            value: self.current_value(),
+            unparsed: self.invalid_numbers.clone(),
+            validation_errors: Vec::new(),
+            required_warnings: self.display_required_warnings,
+            dirty: self.dirty,
+            edited: self.edited.clone(),
+            step: 0,
+        }
+    }
+    pub fn restore(&mut self, state: SignupFormState) {
+        self.set_value(state.value);
+        self.invalid_numbers = state.unparsed;
+        self.display_required_warnings = state.required_warnings;
+        self.dirty = state.dirty;
+        self.edited = state.edited;
+    }
+    pub fn is_expired(&self) -> bool {
+        self.expired
+    }
+    pub fn cooldown_remaining_ms(&self) -> u32 {
+        self.cooldown_remaining_ms
+    }
+    fn after_field_update(&mut self) {
+        self.dirty = true;
+        self.abandoned = false;
+        self.inline_changed = true;
+        self.value_changed = true;
+        self.submit_failed = false;
+    }
+    fn form_class(&self) -> String {
+        let mut class = "signup-form formula-y-form".to_string();
+        if self.submit_failed {
+            class.push_str(" formula-y-submit-failed");
+        }
+        if self.submitted {
+            class.push_str(" formula-y-submitted");
+        }
+        if self.locked {
+            class.push_str(" formula-y-locked");
+        }
+        if self.expired {
+            class.push_str(" formula-y-expired");
+        }
+        class
+    }
+    fn apply_msg(&mut self, msg: SignupFormMsg) -> bool {
+        match msg {
+            SignupFormMsg::UpdateEmail(item) => {
+                self.inner_mut().email = item;
+                self.edited.insert(SignupField::Email);
+                self.after_field_update();
+                true
+            }
+            SignupFormMsg::UpdateAge(item) => {
+                self.inner_mut().age = item;
+                self.edited.insert(SignupField::Age);
+                self.invalid_numbers.remove(&SignupField::Age);
+                self.after_field_update();
+                true
+            }
+            SignupFormMsg::InvalidNumber(field, text) => {
+                self.invalid_numbers.insert(field, text);
+                self.dirty = true;
+                true
+            }
+            SignupFormMsg::NestedValidity(field, key, valid) => {
+                if valid {
+                    self.invalid_nested.remove(&(field, key));
+                } else {
+                    self.invalid_nested.insert((field, key));
+                }
+                false
+            }
+            SignupFormMsg::AcceptInit => {
+                if let Some(init) = self.pending_init.take() {
+                    let fields = [SignupField::Email, SignupField::Age]
+                        .into_iter()
+                        .collect();
+                    self.take_init(&init, &fields);
+                    self.value_changed = true;
+                }
+                true
+            }
+            SignupFormMsg::DismissInit => {
+                self.pending_init = None;
+                true
+            }
+            SignupFormMsg::UnmaskField(field) => {
+                self.focus_on_render = Some(Self::field_id_suffix(field));
+                match field {
+                    _ => true,
+                }
+            }
+            SignupFormMsg::EditInline(field) => {
+                self.inline_editing = Some(field);
+                self.inline_changed = false;
+                self.focus_on_render = Some(Self::field_id_suffix(field));
+                true
+            }
+            SignupFormMsg::FocusField(field) => {
+                self
+                    .focus_snapshot = self
+                    .field_snapshot(field)
+                    .map(|revert| (field, revert));
+                false
+            }
+            SignupFormMsg::RevertField(field) => {
+                match self.focus_snapshot.take() {
+                    Some((focused, revert)) if focused == field => {
+                        let rerender = self.apply_msg(revert);
+                        self
+                            .focus_snapshot = self
+                            .field_snapshot(field)
+                            .map(|revert| (field, revert));
+                        rerender
+                    }
+                    snapshot => {
+                        self.focus_snapshot = snapshot;
+                        false
+                    }
+                }
+            }
+            SignupFormMsg::DisplayRequiredWarnings => {
+                self.display_required_warnings = true;
+                self.submit_failed = true;
+                true
+            }
+            SignupFormMsg::Reset => {
+                self.reset();
+                self.value_changed = true;
+                true
+            }
+            SignupFormMsg::OnSubmit
+            | SignupFormMsg::OnSaveDraft
+            | SignupFormMsg::ConfirmInline(_)
+            | SignupFormMsg::ScheduleAutoSubmit
+            | SignupFormMsg::AutoSubmit
+            | SignupFormMsg::CooldownTick
+            | SignupFormMsg::DeadlineTick
+            | SignupFormMsg::InternalError(_)
+            | SignupFormMsg::CompositionStart(_)
+            | SignupFormMsg::CompositionEnd(_)
+            | SignupFormMsg::Autofill(_)
+            | SignupFormMsg::FieldFocus(_)
+            | SignupFormMsg::PageHide
+            | SignupFormMsg::Unlock => false,
+            SignupFormMsg::ThemeChanged(theme) => {
+                self.context_theme = Some(theme);
+                true
+            }
+        }
+    }
+    fn msg_for_email(new_value: String) -> SignupFormMsg {
+        SignupFormMsg::UpdateEmail(new_value)
+    }
+    fn msg_for_age(new_value: String) -> SignupFormMsg {
+        let parsed: Result<Option<u32>, _> = match new_value.trim() {
+            "" => Ok(None),
+            trimmed => trimmed.parse().map(Some),
+        };
+        match parsed {
+            Ok(value) => SignupFormMsg::UpdateAge(value),
+            Err(_) => SignupFormMsg::InvalidNumber(SignupField::Age, new_value),
+        }
+    }
+    pub fn get_class_for_email_label(&self) -> String {
+        let mut base_name = String::from("email-label formula-y-txt-label");
+        if self.display_required_warnings && self.inner().email == "" {
+            base_name.push_str(" required");
+        }
+        base_name
+    }
+    pub fn get_class_for_email(&self) -> String {
+        let mut base_name = String::from("email-input formula-y-txt-input");
+        if self.display_required_warnings && self.inner().email == "" {
+            base_name.push_str(" required");
+        }
+        base_name
+    }
+    pub fn email_is_flagged(&self) -> bool {
+        (self.display_required_warnings && self.inner().email == "")
+    }
+    pub fn get_class_for_age_label(&self) -> String {
+        "age-label formula-y-txt-label".to_string()
+    }
+    pub fn get_class_for_age(&self) -> String {
+        let mut base_name = String::from("age-input formula-y-txt-input");
+        if self.invalid_numbers.contains_key(&SignupField::Age) {
+            base_name.push_str(" invalid");
+        }
+        base_name
+    }
+    pub fn age_is_flagged(&self) -> bool {
+        self.invalid_numbers.contains_key(&SignupField::Age)
+    }
+}
+impl YFormNested for Signup {
+    fn nested_view(init: Self, id_prefix: String, link: NestedLink<Self>) -> Html {
+        html! {
+            < SignupForm { init } { id_prefix } nested = { link } / >
+        }
+    }
+    fn summary(&self) -> Html {
+        html! {
+            < dl class = { "signup-summary formula-y-summary" } > < dt class =
+            "formula-y-summary-label" > { "Email" } < / dt > < dd class =
+            "formula-y-summary-value" > { self.email.clone() } < / dd > < dt class =
+            "formula-y-summary-label" > { "Age" } < / dt > < dd class =
+            "formula-y-summary-value" > { self.age.map(| value | value.to_string())
+            .unwrap_or_default() } < / dd > < / dl >
+        }
+    }
+    fn sample() -> Self {
+        Signup {
+            email: "Sample Email".to_string(),
+            age: "42".parse().ok(),
+        }
+    }
+    fn field_values(&self) -> Vec<(&'static str, FieldValue)> {
+        let mut values = Vec::new();
+        self.visit_fields(
+            &mut |_: SignupField, meta: &FieldMeta, value: FieldValue| {
+                values.push((meta.name, value));
+            },
+        );
+        values
+    }
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SignupField {
+    Email,
+    Age,
+}
+impl std::fmt::Display for SignupField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            match *self {
+                SignupField::Email => "email",
+                SignupField::Age => "age",
+            },
+        )
+    }
+}
+impl std::str::FromStr for SignupField {
+    type Err = UnknownField;
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "email" => Ok(SignupField::Email),
+            "age" => Ok(SignupField::Age),
+            _ => Err(UnknownField(name.to_string())),
+        }
+    }
+}
+pub enum SignupFormMsg {
+    UpdateEmail(String),
+    UpdateAge(Option<u32>),
+    OnSubmit,
+    OnSaveDraft,
+    InvalidNumber(SignupField, String),
+    NestedValidity(SignupField, usize, bool),
+    AcceptInit,
+    DismissInit,
+    EditInline(SignupField),
+    UnmaskField(SignupField),
+    ConfirmInline(SignupField),
+    FocusField(SignupField),
+    RevertField(SignupField),
+    ScheduleAutoSubmit,
+    AutoSubmit,
+    CooldownTick,
+    DeadlineTick,
+    InternalError(String),
+    CompositionStart(web_sys::CompositionEvent),
+    CompositionEnd(web_sys::CompositionEvent),
+    Autofill(web_sys::Event),
+    FieldFocus(web_sys::Event),
+    PageHide,
+    Unlock,
+    ThemeChanged(formula_y::Theme),
+    Reset,
+    DisplayRequiredWarnings,
+}
+#[derive(PartialEq, Properties)]
+pub struct SignupFormProps {
+    pub onsubmit: Option<Callback<Signup>>,
+    pub onsavedraft: Option<Callback<Signup>>,
+    pub enforce_required_fields: Option<RequiredPolicy>,
+    pub id_prefix: Option<String>,
+    pub escape_reverts: Option<bool>,
+    pub disabled: Option<bool>,
+    pub onerror: Option<Callback<String>>,
+    pub announce_submit: Option<bool>,
+    pub state_indicators: Option<bool>,
+    pub aria_label: Option<String>,
+    pub failed_submits: Option<usize>,
+    pub submit_cooldown_ms: Option<u32>,
+    pub submit_cooldown_max_ms: Option<u32>,
+    pub oncooldown: Option<Callback<u32>>,
+    pub deadline: Option<formula_y::Deadline>,
+    pub onexpire: Option<Callback<Signup>>,
+    pub described_by: Option<String>,
+    pub description: Option<String>,
+    pub submitted_announcement: Option<String>,
+    pub errors_announcement: Option<ErrorsAnnouncement>,
+    pub loading: Option<bool>,
+    pub init_merge: Option<InitMerge>,
+    pub loading_fields: Option<Vec<SignupField>>,
+    pub reset_button: Option<bool>,
+    pub submit_label: Option<String>,
+    pub submit_class: Option<String>,
+    pub submit_disabled: Option<bool>,
+    pub clear_on_submit: Option<bool>,
+    pub reset_count: Option<usize>,
+    pub submitted_reset_count: Option<usize>,
+    pub keyboard_navigation: Option<bool>,
+    pub auto_submit_after_ms: Option<u32>,
+    pub oncompositionstart: Option<Callback<web_sys::CompositionEvent>>,
+    pub oncompositionend: Option<Callback<web_sys::CompositionEvent>>,
+    pub onautofill: Option<Callback<SignupField>>,
+    pub ontelemetry: Option<Callback<TelemetryEvent<SignupField>>>,
+    pub lock_on_submit: Option<bool>,
+    pub theme: Option<formula_y::Theme>,
+    pub density: Option<formula_y::Density>,
+    pub labels: Option<std::collections::HashMap<SignupField, String>>,
+    pub onunlock: Option<Callback<()>>,
+    pub onsnapshot: Option<Callback<SignupFormState>>,
+    pub onabandon: Option<Callback<Vec<SignupField>>>,
+    pub label_placeholders: Option<bool>,
+    pub onchange: Option<Callback<Signup>>,
+    pub inline_edit: Option<bool>,
+    pub audit_trail: Option<bool>,
+    pub onedit: Option<Callback<FieldEdit<SignupField>>>,
+    pub onfieldchange: Option<Callback<(SignupField, Signup)>>,
+    pub field_policy: Option<FieldPolicy<SignupField>>,
+    pub onsubmit_rc: Option<Callback<std::rc::Rc<Signup>>>,
+    pub nested: Option<NestedLink<Signup>>,
+    pub init: Option<Signup>,
+    pub snapshot: Option<SignupFormState>,
+    pub value: Option<Signup>,
+}
+///What a `SignupForm` holds beyond its props, from its `snapshot()` method or `onsnapshot` callback, and restored with `restore()` or the `snapshot` prop
+#[derive(Clone, PartialEq)]
+pub struct SignupFormState {
+    /// The values of the fields
+    pub value: Signup,
+    /// Text typed into number and date inputs which didn't parse, by field
+    pub unparsed: std::collections::HashMap<SignupField, String>,
+    /// The errors the struct's validate fn gave on the last submit attempt, by field
+    pub validation_errors: Vec<(SignupField, String)>,
+    /// Whether required fields left empty are showing their errors
+    pub required_warnings: bool,
+    /// Whether the form has been changed since it was created or reset
+    pub dirty: bool,
+    /// The fields the user has changed
+    pub edited: std::collections::HashSet<SignupField>,
+    /// The step of a multi-step form being shown, counting from 0
+    pub step: usize,
+}
+impl Component for SignupForm {
+    type Message = SignupFormMsg;
+    type Properties = SignupFormProps;
+    fn create(ctx: &Context<Self>) -> Self {
+        let inner = if let Some(value) = &ctx.props().value {
+            value.clone()
+        } else if let Some(init) = &ctx.props().init {
+            init.clone()
+        } else {
+            Signup::new()
+        };
+        let mut form = Self::from_inner(inner);
+        if let Some(id_prefix) = &ctx.props().id_prefix {
+            form.id_prefix = id_prefix.clone();
+        }
+        form.reset_count = ctx.props().reset_count;
+        form.submitted_reset_count = ctx.props().submitted_reset_count;
+        form.failed_submits = ctx.props().failed_submits;
+        if let Some(snapshot) = &ctx.props().snapshot {
+            form.restore(snapshot.clone());
+        }
+        form.start_deadline(ctx);
+        form.loading = Self::loading_fields(ctx);
+        if let Some((theme, handle))
+            = ctx
+                .link()
+                .context::<
+                    formula_y::Theme,
+                >(ctx.link().callback(SignupFormMsg::ThemeChanged))
+        {
+            form.context_theme = Some(theme);
+            form.theme_handle = Some(handle);
+        }
+        form.init_seen = ctx.props().init.clone();
+        form
+    }
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            SignupFormMsg::OnSubmit => {
+                if self.cooldown_remaining_ms > 0 || self.locked || self.expired {
+                    return false;
+                }
+                let required_policy = ctx
+                    .props()
+                    .enforce_required_fields
+                    .unwrap_or_default();
+                if self.submit_step(required_policy.blocks_submit()) {
+                    if self.submit_failed {
+                        self.announce_errors(ctx);
+                    }
+                    return true;
+                }
+                if self.try_submit(required_policy) {
+                    if !self.hold_for_review() {
+                        if let Some(onsubmit) = &ctx.props().onsubmit {
+                            onsubmit.emit(self.submit_value());
+                        }
+                        if let Some(onsubmit_rc) = &ctx.props().onsubmit_rc {
+                            onsubmit_rc.emit(self.inner.clone());
+                        }
+                        if let Some(ontelemetry) = &ctx.props().ontelemetry {
+                            if let Some(event) = self.completion_timer.finish() {
+                                ontelemetry.emit(event);
+                            }
+                        }
+                        if ctx.props().clear_on_submit.unwrap_or_default() {
+                            self.reset();
+                            self.submitted = true;
+                        }
+                        self
+                            .locked = ctx.props().lock_on_submit.unwrap_or_default()
+                            && !false;
+                        self.announce_submitted(ctx);
+                    }
+                } else {
+                    ctx.link().send_message(SignupFormMsg::DisplayRequiredWarnings);
+                }
+                true
+            }
+            SignupFormMsg::OnSaveDraft => {
+                if let Some(onsavedraft) = &ctx.props().onsavedraft {
+                    onsavedraft.emit(self.current_value());
+                }
+                false
+            }
+            SignupFormMsg::ConfirmInline(field) => {
+                let enforce_required = false
+                    && ctx
+                        .props()
+                        .enforce_required_fields
+                        .unwrap_or_default()
+                        .blocks_submit();
+                let displayed_required_warnings = self.display_required_warnings;
+                let before = self.audit_snapshot(Self::audits(ctx));
+                match self.confirm_inline(field, enforce_required) {
+                    Some(changed) => {
+                        if changed {
+                            self.emit_edits(ctx, before);
+                            self.emit_completed_fields(ctx);
+                            if let Some(onfieldchange) = &ctx.props().onfieldchange {
+                                onfieldchange.emit((field, self.current_value()));
+                            }
+                        }
+                        true
+                    }
+                    None => self.display_required_warnings != displayed_required_warnings,
+                }
+            }
+            SignupFormMsg::ScheduleAutoSubmit => {
+                if let Some(delay)
+                    = ctx.props().auto_submit_after_ms.filter(|_| !self.composing)
+                {
+                    let link = ctx.link().clone();
+                    self
+                        .auto_submit_timer = Some(
+                        gloo_timers::callback::Timeout::new(
+                            delay,
+                            move || {
+                                link.send_message(SignupFormMsg::AutoSubmit);
+                            },
+                        ),
+                    );
+                }
+                false
+            }
+            SignupFormMsg::AutoSubmit => {
+                self.auto_submit_timer = None;
+                let document = web_sys::window().and_then(|window| window.document());
+                if let Some(document) = document {
+                    let form = document.get_element_by_id(&self.id_prefix);
+                    if let (Some(form), Some(active))
+                        = (form, document.active_element()) {
+                        if form.contains(Some(&active)) {
+                            if let Err(error) = Self::dispatch_change(&active) {
+                                ctx.link()
+                                    .send_message(SignupFormMsg::InternalError(error));
+                                return false;
+                            }
+                        }
+                    }
+                }
+                ctx.link().send_message(SignupFormMsg::OnSubmit);
+                false
+            }
+            SignupFormMsg::DeadlineTick => {
+                self.check_deadline(ctx);
+                true
+            }
+            SignupFormMsg::CooldownTick => {
+                self
+                    .cooldown_remaining_ms = self
+                    .cooldown_remaining_ms
+                    .saturating_sub(1000);
+                if self.cooldown_remaining_ms == 0 {
+                    self.cooldown_timer = None;
+                }
+                if let Some(oncooldown) = &ctx.props().oncooldown {
+                    oncooldown.emit(self.cooldown_remaining_ms);
+                }
+                true
+            }
+            SignupFormMsg::InternalError(error) => {
+                if let Some(onerror) = &ctx.props().onerror {
+                    onerror.emit(error.clone());
+                }
+                self.internal_error = Some(error);
+                true
+            }
+            SignupFormMsg::CompositionStart(event) => {
+                self.composing = true;
+                self.auto_submit_timer = None;
+                if let Some(oncompositionstart) = &ctx.props().oncompositionstart {
+                    oncompositionstart.emit(event);
+                }
+                false
+            }
+            SignupFormMsg::CompositionEnd(event) => {
+                self.composing = false;
+                if let Some(oncompositionend) = &ctx.props().oncompositionend {
+                    oncompositionend.emit(event);
+                }
+                if ctx.props().auto_submit_after_ms.is_some() {
+                    ctx.link().send_message(SignupFormMsg::ScheduleAutoSubmit);
+                }
+                false
+            }
+            SignupFormMsg::Autofill(event) => {
+                let autofilled = match event.dyn_ref::<web_sys::AnimationEvent>() {
+                    Some(event) => event.animation_name() == "formula-y-autofill",
+                    None => !event.is_instance_of::<web_sys::InputEvent>(),
+                };
+                let target = match event.target() {
+                    Some(target) if autofilled => target,
+                    _ => return false,
+                };
+                if let Err(error) = Self::dispatch_change(&target) {
+                    ctx.link().send_message(SignupFormMsg::InternalError(error));
+                    return false;
+                }
+                if let (Some(field), Some(onautofill))
+                    = (self.event_field(&event), &ctx.props().onautofill) {
+                    onautofill.emit(field);
+                }
+                false
+            }
+            SignupFormMsg::Unlock => {
+                self.locked = false;
+                if let Some(onunlock) = &ctx.props().onunlock {
+                    onunlock.emit(());
+                }
+                true
+            }
+            SignupFormMsg::PageHide => {
+                self.report_abandon(ctx);
+                false
+            }
+            SignupFormMsg::FieldFocus(event) => {
+                if ctx.props().ontelemetry.is_some() {
+                    if let Some(field) = self.event_field(&event) {
+                        self.completion_timer.focus(field);
+                    }
+                }
+                false
+            }
+            SignupFormMsg::DisplayRequiredWarnings => {
+                self.apply_msg(SignupFormMsg::DisplayRequiredWarnings);
+                self.announce_errors(ctx);
+                true
+            }
+            msg => {
+                let before = self.audit_snapshot(Self::audits(ctx));
+                let rerender = self.apply_msg(msg);
+                let changed = std::mem::take(&mut self.value_changed);
+                if changed {
+                    if let Some(onchange) = &ctx.props().onchange {
+                        onchange.emit(self.current_value());
+                    }
+                    self.emit_edits(ctx, before);
+                    self.emit_completed_fields(ctx);
+                }
+                if let Some(nested) = &ctx.props().nested {
+                    if changed {
+                        nested.onchange.emit(self.current_value());
+                    }
+                    nested
+                        .onvalidity
+                        .emit(self.can_submit(nested.enforce_required_fields));
+                }
+                rerender
+            }
+        }
+    }
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        if let Some(nested) = &ctx.props().nested {
+            self.display_required_warnings = nested.display_required_warnings;
+            if let Some(init) = &ctx.props().init {
+                self.set_value(init.clone());
+            }
+        }
+        let loading = Self::loading_fields(ctx);
+        if loading != self.loading {
+            let loaded = self.loading.difference(&loading).copied().collect::<Vec<_>>();
+            self.loading = loading;
+            if let (Some(init), None) = (&ctx.props().init, &ctx.props().value) {
+                let unedited = loaded
+                    .into_iter()
+                    .filter(|field| !self.is_edited(*field))
+                    .collect();
+                self.take_init(init, &unedited);
+                self.init_seen = ctx.props().init.clone();
+            }
+        }
+        if ctx.props().init != self.init_seen {
+            self.init_seen = ctx.props().init.clone();
+            if let (Some(init), None) = (&ctx.props().init, &ctx.props().value) {
+                let fields = [SignupField::Email, SignupField::Age]
+                    .into_iter()
+                    .collect::<std::collections::HashSet<_>>();
+                let unedited = fields
+                    .iter()
+                    .copied()
+                    .filter(|field| !self.is_edited(*field))
+                    .collect::<std::collections::HashSet<_>>();
+                match ctx.props().init_merge.unwrap_or_default() {
+                    InitMerge::Ignore => {}
+                    InitMerge::Overwrite => self.take_init(init, &fields),
+                    InitMerge::KeepEdits => self.take_init(init, &unedited),
+                    InitMerge::Prompt => {
+                        self.take_init(init, &unedited);
+                        if unedited.len() < fields.len() {
+                            self.pending_init = Some(init.clone());
+                        }
+                    }
+                }
+            }
+        }
+        if ctx.props().reset_count != self.reset_count {
+            self.reset_count = ctx.props().reset_count;
+            self.reset();
+        }
+        if ctx.props().failed_submits != self.failed_submits {
+            let failures = ctx.props().failed_submits.unwrap_or_default();
+            let increased = failures > self.failed_submits.unwrap_or_default();
+            self.failed_submits = ctx.props().failed_submits;
+            if failures == 0 {
+                self.cooldown_remaining_ms = 0;
+                self.cooldown_timer = None;
+            } else if increased {
+                self.start_cooldown(ctx, failures);
+            }
+        }
+        if ctx.props().deadline != self.deadline {
+            self.start_deadline(ctx);
+        }
+        if ctx.props().submitted_reset_count != self.submitted_reset_count {
+            self.submitted_reset_count = ctx.props().submitted_reset_count;
+            self.submitted = false;
+        }
+        if let Some(value) = &ctx.props().value {
+            self.set_value(value.clone());
+        }
+        if let Some(id_prefix) = &ctx.props().id_prefix {
+            if *id_prefix != self.id_prefix {
+                self.id_prefix = id_prefix.clone();
+                self.document_listeners.clear();
+            }
+        }
+        let disabled = ctx.props().disabled.unwrap_or_default();
+        if disabled || ctx.props().auto_submit_after_ms.is_none() {
+            self.auto_submit_timer = None;
+        }
+        if disabled {
+            self.inline_editing = None;
+        }
+        if ctx.props().enforce_required_fields == Some(RequiredPolicy::Ignore) {
+            self.display_required_warnings = false;
+        }
+        true
+    }
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let theme = self.theme(ctx);
+        let keyboard_navigation = ctx
+            .props()
+            .keyboard_navigation
+            .unwrap_or_default()
+            .then(|| Callback::from(|event: KeyboardEvent| {
+                if event.default_prevented() || event.alt_key() || event.ctrl_key()
+                    || event.meta_key() || event.shift_key() || event.is_composing()
+                    || event.key_code() == 229
+                {
+                    return;
+                }
+                let target = event
+                    .target()
+                    .unwrap()
+                    .unchecked_into::<web_sys::HtmlElement>();
+                let form = event
+                    .current_target()
+                    .unwrap()
+                    .unchecked_into::<web_sys::Element>();
+                let tag = target.tag_name();
+                let input_type = target.get_attribute("type").unwrap_or_default();
+                let name = target.get_attribute("name").unwrap_or_default();
+                let focusable = |selector: &str| {
+                    let nodes = form.query_selector_all(selector).ok();
+                    (0..nodes.as_ref().map_or(0, |nodes| nodes.length()))
+                        .filter_map(|index| nodes.as_ref()?.item(index))
+                        .map(|node| node.unchecked_into::<web_sys::HtmlElement>())
+                        .filter(|element| element.offset_parent().is_some())
+                        .collect::<Vec<_>>()
+                };
+                let (candidates, step, radio) = match event.key().as_str() {
+                    "Enter" if tag != "TEXTAREA" && tag != "BUTTON" => {
+                        let candidates = focusable(
+                                "input:not(:disabled), select:not(:disabled), textarea:not(:disabled), button:not(:disabled)",
+                            )
+                            .into_iter()
+                            .filter(|element| {
+                                input_type != "radio" || element.is_same_node(Some(&target))
+                                    || element.get_attribute("name").unwrap_or_default() != name
+                            })
+                            .collect();
+                        (candidates, 1, false)
+                    }
+                    key @ (
+                        "ArrowUp" | "ArrowLeft" | "ArrowDown" | "ArrowRight"
+                    ) if input_type == "radio" || input_type == "checkbox" => {
+                        let step = if key == "ArrowUp" || key == "ArrowLeft" {
+                            -1
+                        } else {
+                            1
+                        };
+                        if input_type == "radio" {
+                            let selector = format!(
+                                "input[type=radio][name=\"{}\"]:not(:disabled)", name
+                            );
+                            (focusable(&selector), step, true)
+                        } else {
+                            (
+                                focusable("input[type=checkbox]:not(:disabled)"),
+                                step,
+                                false,
+                            )
+                        }
+                    }
+                    _ => return,
+                };
+                let position = match candidates
+                    .iter()
+                    .position(|element| element.is_same_node(Some(&target)))
+                {
+                    Some(position) => position,
+                    None => return,
+                };
+                let next = RovingFocus::new(Orientation::Both)
+                    .wrapping(radio)
+                    .step(position, step, candidates.len());
+                if let Some(next) = next.and_then(|next| candidates.get(next)) {
+                    event.prevent_default();
+                    let _ = next.focus();
+                    if radio {
+                        next.click();
+                    }
+                }
+            }));
+        let oninput = ctx
+            .props()
+            .auto_submit_after_ms
+            .map(|_| link.callback(|_: InputEvent| SignupFormMsg::ScheduleAutoSubmit));
+        let submit_disabled = ctx.props().disabled.unwrap_or_default()
+            || ctx.props().submit_disabled.unwrap_or_default()
+            || ctx.props().loading.unwrap_or_default() || self.cooldown_remaining_ms > 0
+            || self.expired;
+        html! {
+            < > if ctx.props().nested.is_some() { < fieldset id = { self.id_prefix
+            .clone() } class = { classes!(self.form_class(), "formula-y-nested", theme
+            .form_classes()) } data - formulay - role = "form" > if ctx.props()
+            .label_placeholders.unwrap_or(false) { < style > {
+            ".signup-form .formula-y-placeholder-label { position: absolute; width: 1px; height: 1px; padding: 0; margin: -1px; overflow: hidden; clip: rect(0, 0, 0, 0); white-space: nowrap; border: 0; }"
+            } < / style > } { { let field_id = self.field_id("email"); let error_id =
+            format!("{}-error", field_id); let access = self.field_access(ctx,
+            SignupField::Email); let loading = self.loading.contains(&
+            SignupField::Email); let read_only = false || ctx.props().disabled
+            .unwrap_or_default() || access == FieldAccess::ReadOnly || loading || self
+            .locked || self.expired; let field_label = self.label_for(ctx,
+            SignupField::Email, "Email"); let inline = ctx.props().inline_edit
+            .unwrap_or(false); let label_placeholder = false || ctx.props()
+            .label_placeholders.unwrap_or(false); let label_class = if label_placeholder
+            { format!("{} formula-y-placeholder-label", self.get_class_for_email_label())
+            } else { self.get_class_for_email_label() }; let label_class =
+            classes!(label_class, & theme.label) .to_string(); let input_class =
+            classes!(self.get_class_for_email(), & theme.input) .to_string(); let
+            (onfocus, onkeydown) = if ctx.props().escape_reverts.unwrap_or_default() && !
+            read_only { let shown = self.inner().email.clone(); (Some(ctx.link()
+            .callback(| _ : FocusEvent | { SignupFormMsg::FocusField(SignupField::Email)
+            })), Some(ctx.link().batch_callback(move | event : KeyboardEvent | { if event
+            .key() != "Escape" || event.is_composing() { return None; } event.target()
+            .unwrap().unchecked_into:: < HtmlInputElement > ().set_value(& shown);
+            Some(SignupFormMsg::RevertField(SignupField::Email)) })),) } else { (None,
+            None) }; let onblur = inline.then(|| { ctx.link().callback(| _ : FocusEvent |
+            { SignupFormMsg::ConfirmInline(SignupField::Email) }) }); let onkeydown = if
+            inline { let escape = onkeydown; Some(Callback::from(move | event :
+            KeyboardEvent | { if event.key() == "Enter" && ! event.is_composing() { event
+            .prevent_default(); let _ = event.target().unwrap().unchecked_into:: <
+            web_sys::HtmlElement > ().blur(); return; } if let Some(escape) = & escape {
+            escape.emit(event); } })) } else { onkeydown }; if access ==
+            FieldAccess::Hidden { html! {} } else if inline && self.inline_editing !=
+            Some(SignupField::Email) { html! { < div data - formulay - field = "email"
+            data - formulay - role = "field" class = { classes!("formula-y-form-item",
+            "formula-y-inline", theme.item_classes()) } > < span class = { classes!(self
+            .get_class_for_email_label(), & theme.label) } data - formulay - field =
+            "email" data - formulay - role = "label" > { field_label.clone() } < / span >
+            < span id = { field_id } class = "formula-y-inline-value" data - formulay -
+            field = "email" data - formulay - role = "value" > { self.inner().email
+            .clone() } < / span > if ! read_only { < button type = "button" class = {
+            classes!("formula-y-inline-edit", & theme.button) } data - formulay - field =
+            "email" data - formulay - role = "edit" aria - label = { format!("Edit {}",
+            field_label) } onclick = { ctx.link().callback(| _ |
+            SignupFormMsg::EditInline(SignupField::Email)) } > { "✎" } < / button > } <
+            / div > } } else { let item = html! { < div data - formulay - field = "email"
+            data - formulay - role = "field" class = { classes!("formula-y-form-item",
+            theme.item_classes()) } > < label for = { field_id.clone() } class = {
+            label_class } data - formulay - field = "email" data - formulay - role =
+            "label" > { field_label.clone() } < / label > if ctx.props().state_indicators
+            .unwrap_or(true) && self.email_is_flagged() { < span class =
+            "formula-y-indicator" aria - hidden = "true" data - formulay - field =
+            "email" data - formulay - role = "indicator" > { if self
+            .display_required_warnings && self.inner().email == "" { "⚠ Required" }
+            else { "⚠ Invalid" } } < / span > } < input id = { field_id } name = {
+            "email" } aria - required = { (true).then(|| "true") } aria - invalid = {
+            self.email_is_flagged().then(|| "true") } aria - describedby = { error_id
+            .clone() } data - formulay - field = "email" data - formulay - role = "input"
+            class = { input_class.clone() } type = { "text" } readonly = { read_only }
+            placeholder = { label_placeholder.then(|| field_label.clone()) } onfocus = {
+            onfocus } onkeydown = { onkeydown } onblur = { onblur } value = { self
+            .inner().email.clone() } onchange = { ctx.link().callback(move | event :
+            Event | { let element = event.target().unwrap().unchecked_into:: <
+            HtmlInputElement > (); let new_value = element.value();
+            SignupForm::msg_for_email(new_value) }) } / > < div id = { error_id.clone() }
+            class = { classes!("formula-y-errors", & theme.error) } aria - live =
+            "polite" data - formulay - field = "email" data - formulay - role = "error" >
+            if self.display_required_warnings && self.inner().email == "" { < p class = {
+            "email-error formula-y-error formula-y-required-error" } > {
+            "This field is required" } < / p > } < / div > < / div > }; if loading {
+            html! { < div class = "formula-y-loading" aria - busy = "true" > { item } < /
+            div > } } else { item } } } } { { let field_id = self.field_id("age"); let
+            error_id = format!("{}-error", field_id); let access = self.field_access(ctx,
+            SignupField::Age); let loading = self.loading.contains(& SignupField::Age);
+            let read_only = false || ctx.props().disabled.unwrap_or_default() || access
+            == FieldAccess::ReadOnly || loading || self.locked || self.expired; let
+            field_label = self.label_for(ctx, SignupField::Age, "Age"); let inline = ctx
+            .props().inline_edit.unwrap_or(false); let label_placeholder = false || ctx
+            .props().label_placeholders.unwrap_or(false); let label_class = if
+            label_placeholder { format!("{} formula-y-placeholder-label", self
+            .get_class_for_age_label()) } else { self.get_class_for_age_label() }; let
+            label_class = classes!(label_class, & theme.label) .to_string(); let
+            input_class = classes!(self.get_class_for_age(), & theme.input) .to_string();
+            let (onfocus, onkeydown) = if ctx.props().escape_reverts.unwrap_or_default()
+            && ! read_only { let shown = self.invalid_numbers.get(& SignupField::Age)
+            .cloned().unwrap_or_else(|| self.inner().age.map(| number | number
+            .to_string()).unwrap_or_default()); (Some(ctx.link().callback(| _ :
+            FocusEvent | { SignupFormMsg::FocusField(SignupField::Age) })), Some(ctx
+            .link().batch_callback(move | event : KeyboardEvent | { if event.key() !=
+            "Escape" || event.is_composing() { return None; } event.target().unwrap()
+            .unchecked_into:: < HtmlInputElement > ().set_value(& shown);
+            Some(SignupFormMsg::RevertField(SignupField::Age)) })),) } else { (None,
+            None) }; let onblur = inline.then(|| { ctx.link().callback(| _ : FocusEvent |
+            { SignupFormMsg::ConfirmInline(SignupField::Age) }) }); let onkeydown = if
+            inline { let escape = onkeydown; Some(Callback::from(move | event :
+            KeyboardEvent | { if event.key() == "Enter" && ! event.is_composing() { event
+            .prevent_default(); let _ = event.target().unwrap().unchecked_into:: <
+            web_sys::HtmlElement > ().blur(); return; } if let Some(escape) = & escape {
+            escape.emit(event); } })) } else { onkeydown }; if access ==
+            FieldAccess::Hidden { html! {} } else if inline && self.inline_editing !=
+            Some(SignupField::Age) { html! { < div data - formulay - field = "age" data -
+            formulay - role = "field" class = { classes!("formula-y-form-item",
+            "formula-y-inline", theme.item_classes()) } > < span class = { classes!(self
+            .get_class_for_age_label(), & theme.label) } data - formulay - field = "age"
+            data - formulay - role = "label" > { field_label.clone() } < / span > < span
+            id = { field_id } class = "formula-y-inline-value" data - formulay - field =
+            "age" data - formulay - role = "value" > { self.inner().age.map(| value |
+            value.to_string()).unwrap_or_default() } < / span > if ! read_only { < button
+            type = "button" class = { classes!("formula-y-inline-edit", & theme.button) }
+            data - formulay - field = "age" data - formulay - role = "edit" aria - label
+            = { format!("Edit {}", field_label) } onclick = { ctx.link().callback(| _ |
+            SignupFormMsg::EditInline(SignupField::Age)) } > { "✎" } < / button > } < /
+            div > } } else { let item = html! { < div data - formulay - field = "age"
+            data - formulay - role = "field" class = { classes!("formula-y-form-item",
+            theme.item_classes()) } > < label for = { field_id.clone() } class = {
+            label_class } data - formulay - field = "age" data - formulay - role =
+            "label" > { field_label.clone() } < / label > if ctx.props().state_indicators
+            .unwrap_or(true) && self.age_is_flagged() { < span class =
+            "formula-y-indicator" aria - hidden = "true" data - formulay - field = "age"
+            data - formulay - role = "indicator" > { "⚠ Invalid" } < / span > } < input
+            id = { field_id } name = { "age" } aria - invalid = { self.age_is_flagged()
+            .then(|| "true") } aria - describedby = { error_id.clone() } data - formulay
+            - field = "age" data - formulay - role = "input" class = { input_class
+            .clone() } type = "number" readonly = { read_only } placeholder = {
+            label_placeholder.then(|| field_label.clone()) } onfocus = { onfocus }
+            onkeydown = { onkeydown } onblur = { onblur } value = { self.invalid_numbers
+            .get(& SignupField::Age).cloned().unwrap_or_else(|| self.inner().age.map(|
+            number | number.to_string()).unwrap_or_default()) } onchange = { ctx.link()
+            .callback(move | event : Event | { let element = event.target().unwrap()
+            .unchecked_into:: < HtmlInputElement > (); let new_value = element.value();
+            let msg = SignupForm::msg_for_age(new_value); msg }) } / > < div id = {
+            error_id.clone() } class = { classes!("formula-y-errors", & theme.error) }
+            aria - live = "polite" data - formulay - field = "age" data - formulay - role
+            = "error" > < / div > < / div > }; if loading { html! { < div class =
+            "formula-y-loading" aria - busy = "true" > { item } < / div > } } else { item
+            } } } } < / fieldset > } else { < form id = { self.id_prefix.clone() } class
+            = { classes!(self.form_class(), theme.form_classes()) } data - formulay -
+            role = "form" aria - label = { ctx.props().aria_label.clone() } aria -
+            describedby = { self.form_described_by(ctx) } onkeydown = {
+            keyboard_navigation } oninput = { oninput } onsubmit = { link.callback(| e :
+            FocusEvent | { e.prevent_default(); SignupFormMsg::OnSubmit }) } > if ctx
+            .props().label_placeholders.unwrap_or(false) { < style > {
+            ".signup-form .formula-y-placeholder-label { position: absolute; width: 1px; height: 1px; padding: 0; margin: -1px; overflow: hidden; clip: rect(0, 0, 0, 0); white-space: nowrap; border: 0; }"
+            } < / style > } < style > {
+            "@keyframes formula-y-autofill { from {} to {} } .signup-form input:-webkit-autofill, .signup-form select:-webkit-autofill, .signup-form textarea:-webkit-autofill { animation-name: formula-y-autofill; }"
+            } < / style > if ! self.loading.is_empty() { < style > {
+            ".signup-form .formula-y-loading input, .signup-form .formula-y-loading select, .signup-form .formula-y-loading textarea { color: transparent; background: linear-gradient(90deg, #eee 25%, #f6f6f6 50%, #eee 75%) 0 0 / 200% 100%; animation: formula-y-loading 1.5s linear infinite; } .signup-form .formula-y-loading ::placeholder { color: transparent; } @keyframes formula-y-loading { to { background-position: -200% 0; } }"
+            } < / style > } if let Some(description) = & ctx.props().description { < p id
+            = { format!("{}-description", self.id_prefix) } class =
+            "formula-y-description" > { description } < / p > } if let Some(error) = &
+            self.internal_error { < div class = "formula-y-internal-error" role = "alert"
+            > { error } < / div > } if self.cooldown_remaining_ms > 0 { < p class =
+            "formula-y-cooldown" > { format!("Try again in {}s", (self
+            .cooldown_remaining_ms + 999) / 1000) } < / p > } if self.expired { < p class
+            = "formula-y-expired-notice" data - formulay - role = "countdown" role =
+            "alert" > { "Time is up" } < / p > } else if let Some(deadline) = self
+            .deadline { < p class = "formula-y-countdown" data - formulay - role =
+            "countdown" role = "timer" > { format!("Time remaining {}", deadline
+            .countdown()) } < / p > } if ctx.props().announce_submit.unwrap_or(true) { <
+            div class = "formula-y-announcement" role = "status" aria - live = "polite"
+            aria - atomic = "true" style = {
+            "position: absolute; width: 1px; height: 1px; overflow: hidden; clip: rect(0 0 0 0); white-space: nowrap;"
+            } > { & self.announcement } < / div > } if self.pending_init.is_some() { <
+            div class = "formula-y-init-changed" role = "alert" > < span > {
+            "This has been changed elsewhere since you started editing." } < / span > <
+            button type = "button" class = "formula-y-init-accept" onclick = { link
+            .callback(| _ | SignupFormMsg::AcceptInit) } > { "Load the changes" } < /
+            button > < button type = "button" class = "formula-y-init-dismiss" onclick =
+            { link.callback(| _ | SignupFormMsg::DismissInit) } > { "Keep my edits" } < /
+            button > < / div > } { { let field_id = self.field_id("email"); let error_id
+            = format!("{}-error", field_id); let access = self.field_access(ctx,
+            SignupField::Email); let loading = self.loading.contains(&
+            SignupField::Email); let read_only = false || ctx.props().disabled
+            .unwrap_or_default() || access == FieldAccess::ReadOnly || loading || self
+            .locked || self.expired; let field_label = self.label_for(ctx,
+            SignupField::Email, "Email"); let inline = ctx.props().inline_edit
+            .unwrap_or(false); let label_placeholder = false || ctx.props()
+            .label_placeholders.unwrap_or(false); let label_class = if label_placeholder
+            { format!("{} formula-y-placeholder-label", self.get_class_for_email_label())
+            } else { self.get_class_for_email_label() }; let label_class =
+            classes!(label_class, & theme.label) .to_string(); let input_class =
+            classes!(self.get_class_for_email(), & theme.input) .to_string(); let
+            (onfocus, onkeydown) = if ctx.props().escape_reverts.unwrap_or_default() && !
+            read_only { let shown = self.inner().email.clone(); (Some(ctx.link()
+            .callback(| _ : FocusEvent | { SignupFormMsg::FocusField(SignupField::Email)
+            })), Some(ctx.link().batch_callback(move | event : KeyboardEvent | { if event
+            .key() != "Escape" || event.is_composing() { return None; } event.target()
+            .unwrap().unchecked_into:: < HtmlInputElement > ().set_value(& shown);
+            Some(SignupFormMsg::RevertField(SignupField::Email)) })),) } else { (None,
+            None) }; let onblur = inline.then(|| { ctx.link().callback(| _ : FocusEvent |
+            { SignupFormMsg::ConfirmInline(SignupField::Email) }) }); let onkeydown = if
+            inline { let escape = onkeydown; Some(Callback::from(move | event :
+            KeyboardEvent | { if event.key() == "Enter" && ! event.is_composing() { event
+            .prevent_default(); let _ = event.target().unwrap().unchecked_into:: <
+            web_sys::HtmlElement > ().blur(); return; } if let Some(escape) = & escape {
+            escape.emit(event); } })) } else { onkeydown }; if access ==
+            FieldAccess::Hidden { html! {} } else if inline && self.inline_editing !=
+            Some(SignupField::Email) { html! { < div data - formulay - field = "email"
+            data - formulay - role = "field" class = { classes!("formula-y-form-item",
+            "formula-y-inline", theme.item_classes()) } > < span class = { classes!(self
+            .get_class_for_email_label(), & theme.label) } data - formulay - field =
+            "email" data - formulay - role = "label" > { field_label.clone() } < / span >
+            < span id = { field_id } class = "formula-y-inline-value" data - formulay -
+            field = "email" data - formulay - role = "value" > { self.inner().email
+            .clone() } < / span > if ! read_only { < button type = "button" class = {
+            classes!("formula-y-inline-edit", & theme.button) } data - formulay - field =
+            "email" data - formulay - role = "edit" aria - label = { format!("Edit {}",
+            field_label) } onclick = { ctx.link().callback(| _ |
+            SignupFormMsg::EditInline(SignupField::Email)) } > { "✎" } < / button > } <
+            / div > } } else { let item = html! { < div data - formulay - field = "email"
+            data - formulay - role = "field" class = { classes!("formula-y-form-item",
+            theme.item_classes()) } > < label for = { field_id.clone() } class = {
+            label_class } data - formulay - field = "email" data - formulay - role =
+            "label" > { field_label.clone() } < / label > if ctx.props().state_indicators
+            .unwrap_or(true) && self.email_is_flagged() { < span class =
+            "formula-y-indicator" aria - hidden = "true" data - formulay - field =
+            "email" data - formulay - role = "indicator" > { if self
+            .display_required_warnings && self.inner().email == "" { "⚠ Required" }
+            else { "⚠ Invalid" } } < / span > } < input id = { field_id } name = {
+            "email" } aria - required = { (true).then(|| "true") } aria - invalid = {
+            self.email_is_flagged().then(|| "true") } aria - describedby = { error_id
+            .clone() } data - formulay - field = "email" data - formulay - role = "input"
+            class = { input_class.clone() } type = { "text" } readonly = { read_only }
+            placeholder = { label_placeholder.then(|| field_label.clone()) } onfocus = {
+            onfocus } onkeydown = { onkeydown } onblur = { onblur } value = { self
+            .inner().email.clone() } onchange = { ctx.link().callback(move | event :
+            Event | { let element = event.target().unwrap().unchecked_into:: <
+            HtmlInputElement > (); let new_value = element.value();
+            SignupForm::msg_for_email(new_value) }) } / > < div id = { error_id.clone() }
+            class = { classes!("formula-y-errors", & theme.error) } aria - live =
+            "polite" data - formulay - field = "email" data - formulay - role = "error" >
+            if self.display_required_warnings && self.inner().email == "" { < p class = {
+            "email-error formula-y-error formula-y-required-error" } > {
+            "This field is required" } < / p > } < / div > < / div > }; if loading {
+            html! { < div class = "formula-y-loading" aria - busy = "true" > { item } < /
+            div > } } else { item } } } } { { let field_id = self.field_id("age"); let
+            error_id = format!("{}-error", field_id); let access = self.field_access(ctx,
+            SignupField::Age); let loading = self.loading.contains(& SignupField::Age);
+            let read_only = false || ctx.props().disabled.unwrap_or_default() || access
+            == FieldAccess::ReadOnly || loading || self.locked || self.expired; let
+            field_label = self.label_for(ctx, SignupField::Age, "Age"); let inline = ctx
+            .props().inline_edit.unwrap_or(false); let label_placeholder = false || ctx
+            .props().label_placeholders.unwrap_or(false); let label_class = if
+            label_placeholder { format!("{} formula-y-placeholder-label", self
+            .get_class_for_age_label()) } else { self.get_class_for_age_label() }; let
+            label_class = classes!(label_class, & theme.label) .to_string(); let
+            input_class = classes!(self.get_class_for_age(), & theme.input) .to_string();
+            let (onfocus, onkeydown) = if ctx.props().escape_reverts.unwrap_or_default()
+            && ! read_only { let shown = self.invalid_numbers.get(& SignupField::Age)
+            .cloned().unwrap_or_else(|| self.inner().age.map(| number | number
+            .to_string()).unwrap_or_default()); (Some(ctx.link().callback(| _ :
+            FocusEvent | { SignupFormMsg::FocusField(SignupField::Age) })), Some(ctx
+            .link().batch_callback(move | event : KeyboardEvent | { if event.key() !=
+            "Escape" || event.is_composing() { return None; } event.target().unwrap()
+            .unchecked_into:: < HtmlInputElement > ().set_value(& shown);
+            Some(SignupFormMsg::RevertField(SignupField::Age)) })),) } else { (None,
+            None) }; let onblur = inline.then(|| { ctx.link().callback(| _ : FocusEvent |
+            { SignupFormMsg::ConfirmInline(SignupField::Age) }) }); let onkeydown = if
+            inline { let escape = onkeydown; Some(Callback::from(move | event :
+            KeyboardEvent | { if event.key() == "Enter" && ! event.is_composing() { event
+            .prevent_default(); let _ = event.target().unwrap().unchecked_into:: <
+            web_sys::HtmlElement > ().blur(); return; } if let Some(escape) = & escape {
+            escape.emit(event); } })) } else { onkeydown }; if access ==
+            FieldAccess::Hidden { html! {} } else if inline && self.inline_editing !=
+            Some(SignupField::Age) { html! { < div data - formulay - field = "age" data -
+            formulay - role = "field" class = { classes!("formula-y-form-item",
+            "formula-y-inline", theme.item_classes()) } > < span class = { classes!(self
+            .get_class_for_age_label(), & theme.label) } data - formulay - field = "age"
+            data - formulay - role = "label" > { field_label.clone() } < / span > < span
+            id = { field_id } class = "formula-y-inline-value" data - formulay - field =
+            "age" data - formulay - role = "value" > { self.inner().age.map(| value |
+            value.to_string()).unwrap_or_default() } < / span > if ! read_only { < button
+            type = "button" class = { classes!("formula-y-inline-edit", & theme.button) }
+            data - formulay - field = "age" data - formulay - role = "edit" aria - label
+            = { format!("Edit {}", field_label) } onclick = { ctx.link().callback(| _ |
+            SignupFormMsg::EditInline(SignupField::Age)) } > { "✎" } < / button > } < /
+            div > } } else { let item = html! { < div data - formulay - field = "age"
+            data - formulay - role = "field" class = { classes!("formula-y-form-item",
+            theme.item_classes()) } > < label for = { field_id.clone() } class = {
+            label_class } data - formulay - field = "age" data - formulay - role =
+            "label" > { field_label.clone() } < / label > if ctx.props().state_indicators
+            .unwrap_or(true) && self.age_is_flagged() { < span class =
+            "formula-y-indicator" aria - hidden = "true" data - formulay - field = "age"
+            data - formulay - role = "indicator" > { "⚠ Invalid" } < / span > } < input
+            id = { field_id } name = { "age" } aria - invalid = { self.age_is_flagged()
+            .then(|| "true") } aria - describedby = { error_id.clone() } data - formulay
+            - field = "age" data - formulay - role = "input" class = { input_class
+            .clone() } type = "number" readonly = { read_only } placeholder = {
+            label_placeholder.then(|| field_label.clone()) } onfocus = { onfocus }
+            onkeydown = { onkeydown } onblur = { onblur } value = { self.invalid_numbers
+            .get(& SignupField::Age).cloned().unwrap_or_else(|| self.inner().age.map(|
+            number | number.to_string()).unwrap_or_default()) } onchange = { ctx.link()
+            .callback(move | event : Event | { let element = event.target().unwrap()
+            .unchecked_into:: < HtmlInputElement > (); let new_value = element.value();
+            let msg = SignupForm::msg_for_age(new_value); msg }) } / > < div id = {
+            error_id.clone() } class = { classes!("formula-y-errors", & theme.error) }
+            aria - live = "polite" data - formulay - field = "age" data - formulay - role
+            = "error" > < / div > < / div > }; if loading { html! { < div class =
+            "formula-y-loading" aria - busy = "true" > { item } < / div > } } else { item
+            } } } } < div class = { "formula-y-buttons formula-y-buttons-bottom" } > if
+            self.locked { < button type = "button" class = { classes!("formula-y-unlock",
+            & theme.button) } data - formulay - role = "unlock" onclick = { link
+            .callback(| _ | SignupFormMsg::Unlock) } > { "Edit" } < / button > } else { <
+            button class = { classes!("formula-y-submit", & theme.submit, ctx.props()
+            .submit_class.clone()) } data - formulay - role = "submit" disabled = {
+            submit_disabled } > { ctx.props().submit_label.clone().unwrap_or_else(||
+            "Submit".to_string()) } < / button > if ctx.props().onsavedraft.is_some() { <
+            button type = "button" class = { classes!("formula-y-save-draft", & theme
+            .button) } data - formulay - role = "save-draft" onclick = { link.callback(|
+            _ | SignupFormMsg::OnSaveDraft) } > { "Save draft" } < / button > } if ctx
+            .props().reset_button.unwrap_or_default() { < button type = "button" class =
+            { classes!("formula-y-reset", & theme.button) } data - formulay - role =
+            "reset" onclick = { link.callback(| _ | SignupFormMsg::Reset) } > { "Reset" }
+            < / button > } } < / div > < / form > } < / >
+        }
+    }
+    fn destroy(&mut self, ctx: &Context<Self>) {
+        self.report_abandon(ctx);
+        if let Some(onsnapshot) = &ctx.props().onsnapshot {
+            onsnapshot.emit(self.snapshot());
+        }
+    }
+    fn rendered(&mut self, ctx: &Context<Self>, first_render: bool) {
+        if self.document_listeners.is_empty() {
+            if let Some(document)
+                = web_sys::window().and_then(|window| window.document())
+            {
+                let listen = |
+                    event_type: &'static str,
+                    msg: fn(web_sys::Event) -> SignupFormMsg|
+                {
+                    let link = ctx.link().clone();
+                    let id_prefix = self.id_prefix.clone();
+                    gloo_events::EventListener::new(
+                        &document,
+                        event_type,
+                        move |event| {
+                            let form = web_sys::window()
+                                .and_then(|window| window.document())
+                                .and_then(|document| {
+                                    document.get_element_by_id(&id_prefix)
+                                });
+                            let target = event
+                                .target()
+                                .and_then(|target| target.dyn_into::<web_sys::Node>().ok());
+                            if let (Some(form), Some(target)) = (form, target) {
+                                if form.contains(Some(&target)) {
+                                    link.send_message(msg(event.clone()));
+                                }
+                            }
+                        },
+                    )
+                };
+                self
+                    .document_listeners = vec![
+                    listen("compositionstart", | event |
+                    SignupFormMsg::CompositionStart(event.unchecked_into())),
+                    listen("compositionend", | event |
+                    SignupFormMsg::CompositionEnd(event.unchecked_into())),
+                    listen("animationstart", SignupFormMsg::Autofill), listen("input",
+                    SignupFormMsg::Autofill), listen("focusin",
+                    SignupFormMsg::FieldFocus),
+                ];
+                if let Some(window) = web_sys::window() {
+                    let link = ctx.link().clone();
+                    self.document_listeners
+                        .push(
+                            gloo_events::EventListener::new(
+                                &window,
+                                "pagehide",
+                                move |_| {
+                                    link.send_message(SignupFormMsg::PageHide);
+                                },
+                            ),
+                        );
+                }
+            }
+        }
+        if let Some(field) = self.focus_on_render.take() {
+            let input = web_sys::window()
+                .and_then(|window| window.document())
+                .and_then(|document| document.get_element_by_id(&self.field_id(field)));
+            if let Some(input) = input {
+                let _ = input.unchecked_into::<web_sys::HtmlElement>().focus();
+            }
+        }
+        if first_render {
+            if let Some(nested) = &ctx.props().nested {
+                nested.onvalidity.emit(self.can_submit(nested.enforce_required_fields));
+            }
+        }
+    }
+}
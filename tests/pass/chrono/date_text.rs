@@ -0,0 +1,20 @@
+use formula_y::prelude::*;
+
+#[derive(Clone, PartialEq, YForm)]
+pub struct Booking {
+    #[yform(date_text)]
+    pub arrival: chrono::NaiveDate,
+    #[yform(date_text, placeholder = "When you leave")]
+    pub departure: Option<chrono::NaiveDate>,
+    #[yform(date_text, default = "2025-02-14 09:30:00")]
+    pub check_in: chrono::NaiveDateTime,
+    pub booked: Option<chrono::NaiveDate>,
+}
+
+fn main() {
+    let order = DateOrder::for_locale("en-GB");
+    assert_eq!(
+        order.parse_date("14/02/2025"),
+        chrono::NaiveDate::from_ymd_opt(2025, 2, 14)
+    );
+}
@@ -0,0 +1,18 @@
+use formula_y::detect_time_zone;
+use formula_y::prelude::chrono_tz::Tz;
+use formula_y::prelude::*;
+
+#[derive(Clone, PartialEq, YForm)]
+pub struct Meeting {
+    pub title: String,
+    pub zone: Tz,
+    #[yform(empty_label = "Same as the organizer")]
+    pub attendee_zone: Option<chrono_tz::Tz>,
+}
+
+fn main() {
+    assert_eq!(<Tz as YFormSelect>::first(), detect_time_zone());
+    assert_eq!(Tz::from_value("Europe/Berlin"), Some(Tz::Europe__Berlin));
+    let regions = time_zones_by_region("berlin", "");
+    assert_eq!(regions, [("Europe", vec![("Europe/Berlin", "Berlin")])]);
+}
@@ -0,0 +1,58 @@
+use formula_y::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, YFormSelect)]
+pub enum Plan {
+    Free,
+    #[yform(label = "Pro plan")]
+    Pro,
+}
+
+#[derive(Clone, Debug, PartialEq, YForm)]
+pub struct Customer {
+    pub name: String,
+    pub age: u32,
+    pub discount: Option<f64>,
+    pub subscribed: bool,
+    pub plan: Plan,
+    pub tags: Vec<String>,
+}
+
+fn main() {
+    let customer = Customer {
+        name: "Smith, \"Ann\"".to_string(),
+        age: 42,
+        discount: None,
+        subscribed: true,
+        plan: Plan::Pro,
+        tags: vec!["a".to_string(), "b".to_string()],
+    };
+    let csv = customer.to_csv_row();
+    assert_eq!(csv, "\"Smith, \"\"Ann\"\"\",42,,true,Pro plan,\"a, b\"");
+    assert_eq!(Customer::from_csv_row(&csv), Ok(customer.clone()));
+    assert_eq!(
+        Customer::from_tsv_row(&format!("{}\r\n", customer.to_tsv_row())),
+        Ok(customer)
+    );
+
+    assert_eq!(
+        Customer::from_csv_row("Ann,42,,true"),
+        Err(ImportError::ColumnCount {
+            expected: 6,
+            found: 4
+        })
+    );
+    assert_eq!(
+        Customer::from_csv_row("Ann,forty,,true,Free,"),
+        Err(ImportError::InvalidValue {
+            field: "age",
+            value: "forty".to_string()
+        })
+    );
+    assert_eq!(
+        Customer::from_csv_row("Ann,42,,true,Gold,"),
+        Err(ImportError::InvalidValue {
+            field: "plan",
+            value: "Gold".to_string()
+        })
+    );
+}
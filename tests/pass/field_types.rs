@@ -0,0 +1,63 @@
+use formula_y::prelude::*;
+
+#[derive(Clone, Copy, PartialEq, YFormSelect)]
+pub enum Plan {
+    Free,
+    Pro,
+    #[yform(label = "Enterprise plan")]
+    Enterprise,
+}
+
+#[derive(Clone, PartialEq, YForm)]
+pub struct Customer {
+    pub name: String,
+    pub plan: Plan,
+}
+
+fn total(quantity: &u32, unit_price: &f64) -> f64 {
+    f64::from(*quantity) * unit_price
+}
+
+#[derive(Clone, PartialEq, YForm)]
+pub struct Everything {
+    pub name: String,
+    #[yform(widget = "textarea")]
+    pub notes: Option<String>,
+    pub subscribed: bool,
+    pub terms: Option<bool>,
+    pub age: u8,
+    pub balance: i64,
+    pub quantity: u32,
+    #[yform(widget = "range", min = 1, max = 10, throttle_ms = 50)]
+    pub rating: u8,
+    #[yform(widget = "range", min = "-1", max = 1.5)]
+    pub balance_ratio: f64,
+    #[yform(precision = 2, rounding = "half_even")]
+    pub unit_price: f64,
+    #[yform(computed_from = "quantity, unit_price", compute_with = "total", output)]
+    pub total: f64,
+    pub discount: Option<f32>,
+    #[yform(unit_options("kg", "lb"), unit_factors(1, 0.45359237), precision = 3)]
+    pub weight: f64,
+    #[yform(unit_options("m", "ft", "in"), unit_factors(1.0, 0.3048, 0.0254))]
+    pub height: Option<f32>,
+    pub plan: Plan,
+    #[yform(widget = "radio", event = "input", throttle_ms = 100)]
+    pub billing: Plan,
+    #[yform(empty_label = "-- select --")]
+    pub upgrade: Option<Plan>,
+    #[yform(widget = "radio")]
+    pub fallback: Option<Plan>,
+    #[yform(choices("s", "m", "l"))]
+    pub size: String,
+    #[yform(choices("post", "courier"), widget = "select", empty_label = "Either")]
+    pub delivery: Option<String>,
+    #[yform(max_items = 5)]
+    pub tags: Vec<String>,
+    #[yform(nested)]
+    pub customer: Customer,
+    #[yform(nested, min_items = 1, max_items = 3)]
+    pub contacts: Vec<Customer>,
+}
+
+fn main() {}
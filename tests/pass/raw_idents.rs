@@ -0,0 +1,17 @@
+use formula_y::prelude::*;
+
+fn label(r#type: &Option<String>, price: &f64) -> String {
+    format!("{} at {}", r#type.as_deref().unwrap_or("Parcel"), price)
+}
+
+#[derive(Clone, PartialEq, YForm)]
+pub struct Shipment {
+    pub r#type: Option<String>,
+    pub price: f64,
+    #[yform(required_if = "r#type")]
+    pub carrier: Option<String>,
+    #[yform(computed_from = "r#type, price", compute_with = "label")]
+    pub summary: String,
+}
+
+fn main() {}
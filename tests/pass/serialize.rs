@@ -0,0 +1,11 @@
+use formula_y::prelude::*;
+use serde::Serialize;
+
+#[derive(Clone, PartialEq, Serialize, YForm)]
+#[yform(serialize)]
+struct BugReport {
+    summary: String,
+    steps: Vec<String>,
+}
+
+fn main() {}
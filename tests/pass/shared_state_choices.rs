@@ -0,0 +1,14 @@
+use formula_y::prelude::*;
+
+#[derive(Clone, PartialEq, YForm)]
+#[yform(shared_state)]
+struct Edit {
+    #[yform(choices("s", "m", "l"))]
+    size: String,
+    #[yform(choices("red", "blue"))]
+    colour: Option<String>,
+    #[yform(choices("post", "courier"), widget = "select")]
+    delivery: String,
+}
+
+fn main() {}
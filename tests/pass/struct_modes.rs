@@ -0,0 +1,86 @@
+use formula_y::prelude::*;
+
+#[derive(Clone, Copy, PartialEq, YFormSelect)]
+pub enum Plan {
+    Free,
+    Pro,
+}
+
+#[derive(Clone, PartialEq, YForm)]
+pub struct Customer {
+    pub name: String,
+    pub plan: Plan,
+}
+
+#[derive(PartialEq, YForm)]
+#[yform(shared_state)]
+pub struct Shared {
+    pub name: String,
+    pub age: Option<u32>,
+    #[yform(widget = "radio")]
+    pub plan: Plan,
+    #[yform(choices("s", "m"))]
+    pub size: Option<String>,
+    pub tags: Vec<String>,
+    #[yform(nested)]
+    pub customer: Customer,
+}
+
+#[derive(Clone, YForm)]
+#[yform(no_partial_eq)]
+pub struct NoEq {
+    pub name: String,
+    pub plan: Option<Plan>,
+}
+
+#[derive(YForm)]
+#[yform(no_partial_eq, shared_state)]
+pub struct NoEqShared {
+    pub name: String,
+    #[yform(nested)]
+    pub contacts: Vec<Customer>,
+}
+
+#[derive(Clone, PartialEq, YForm)]
+#[yform(review, actions("Approve", "Request changes"))]
+pub struct Approval {
+    pub comment: Option<String>,
+    #[yform(widget = "radio")]
+    pub plan: Plan,
+}
+
+#[derive(PartialEq, YForm)]
+#[yform(shared_state, review, actions("Go"))]
+pub struct SharedApproval {
+    pub comment: String,
+}
+
+#[derive(Clone, PartialEq, YForm)]
+#[yform(emit = "both", name = "SignupForm", derive_default)]
+pub struct Signup {
+    pub email: String,
+    #[yform(nested)]
+    pub customer: Customer,
+}
+
+#[derive(Clone, PartialEq, YForm)]
+#[yform(preset = "login")]
+pub struct Login {
+    pub username: String,
+    pub password: String,
+    pub remember_me: bool,
+}
+
+#[derive(Clone, PartialEq, YForm)]
+pub struct Onboarding {
+    pub name: String,
+    #[yform(step = 2)]
+    pub company: Option<String>,
+}
+
+// Multi-step forms take a callback for the validity of each step
+pub fn onboarding(onstepvalidity: Callback<(usize, bool)>) -> Html {
+    html! { <OnboardingForm init={Onboarding::new()} onstepvalidity={onstepvalidity} /> }
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+// The derive's errors for unsupported types and options, checked against the expected output in
+// tests/ui, and attribute combinations in tests/pass which have to expand to code that compiles
+// and runs without panicking, with those needing chrono or chrono-tz in a directory named after
+// the feature. Run with TRYBUILD=overwrite to accept changed output.
+#[test]
+fn ui() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+    cases.pass("tests/pass/*.rs");
+    #[cfg(feature = "chrono")]
+    cases.pass("tests/pass/chrono/*.rs");
+    #[cfg(feature = "chrono-tz")]
+    cases.pass("tests/pass/chrono_tz/*.rs");
+    #[cfg(feature = "chrono-tz")]
+    cases.compile_fail("tests/ui/chrono_tz/*.rs");
+}
@@ -0,0 +1,13 @@
+use formula_y::prelude::*;
+
+#[derive(YForm)]
+struct Signup {
+    #[yform(regex = "[a-z")]
+    username: String,
+    #[yform(widget = "slider")]
+    bio: String,
+    #[yform(rows = 4)]
+    age: u32,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use formula_y::prelude::*;
+use formula_y::prelude::chrono_tz::Tz;
+
+#[derive(Clone, PartialEq, YForm)]
+pub struct Meeting {
+    #[yform(widget = "radio")]
+    pub zone: Tz,
+}
+
+fn main() {}
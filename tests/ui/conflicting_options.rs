@@ -0,0 +1,18 @@
+use formula_y::prelude::*;
+
+#[derive(YForm)]
+struct Profile {
+    #[yform(label = "Name")]
+    #[yform(label = "Full name")]
+    name: String,
+    #[yform(trim)]
+    subscribed: bool,
+    #[yform(rows = 4)]
+    newsletter: bool,
+    #[yform(no_spinner)]
+    nickname: String,
+    #[yform(empty_label = "-- select --")]
+    title: Option<String>,
+}
+
+fn main() {}
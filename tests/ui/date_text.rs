@@ -0,0 +1,9 @@
+use formula_y::prelude::*;
+
+#[derive(YForm)]
+struct Booking {
+    #[yform(date_text)]
+    arrival: String,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use formula_y::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, YForm)]
+pub struct Settings {
+    #[yform(default = "three")]
+    pub seats: u8,
+    #[yform(default = 1.5)]
+    pub count: i32,
+    #[yform(default = 1)]
+    pub newsletter: bool,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use formula_y::prelude::*;
+
+#[derive(Clone, PartialEq, YForm)]
+#[yform(emit = "hook")]
+struct Signup {
+    email: String,
+}
+
+#[derive(Clone, PartialEq, YForm)]
+#[yform(emit = "function")]
+struct Login {
+    email: String,
+}
+
+fn main() {}
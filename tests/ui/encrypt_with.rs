@@ -0,0 +1,15 @@
+use formula_y::prelude::*;
+
+fn tokenize(value: &str) -> String {
+    format!("tok_{}", value.len())
+}
+
+#[derive(YForm)]
+struct Payment {
+    #[yform(encrypt_with = "tokenize")]
+    card_number: String,
+    #[yform(encrypt_with = "tokenize")]
+    save_card: bool,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use formula_y::prelude::*;
+
+#[derive(YForm)]
+enum Shape {
+    Circle,
+    Square,
+}
+
+fn main() {}
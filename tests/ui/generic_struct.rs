@@ -0,0 +1,8 @@
+use formula_y::prelude::*;
+
+#[derive(YForm)]
+struct Wrapper<T> {
+    value: T,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+#![deny(deprecated)]
+
+use formula_y::prelude::*;
+
+#[derive(Clone, PartialEq, YForm)]
+struct Profile {
+    #[yform(placeholder = "Pick one", choices("a", "b"))]
+    plan: String,
+    #[yform(placeholder = "Yes")]
+    subscribed: bool,
+}
+
+fn main() {}
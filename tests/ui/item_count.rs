@@ -0,0 +1,13 @@
+use formula_y::prelude::*;
+
+#[derive(YForm)]
+struct Order {
+    #[yform(min_items = 1)]
+    name: String,
+    #[yform(max_items = "3")]
+    tags: Vec<String>,
+    #[yform(min_items = 4, max_items = 2)]
+    notes: Vec<String>,
+}
+
+fn main() {}
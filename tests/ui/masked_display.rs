@@ -0,0 +1,11 @@
+use formula_y::prelude::*;
+
+#[derive(YForm)]
+struct Integration {
+    #[yform(masked_display)]
+    api_key: String,
+    #[yform(masked_display)]
+    port: u16,
+}
+
+fn main() {}
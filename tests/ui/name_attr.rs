@@ -0,0 +1,15 @@
+use formula_y::prelude::*;
+
+#[derive(YForm)]
+struct Address {
+    #[yform(name_attr = "street address")]
+    street: String,
+}
+
+#[derive(YForm)]
+struct Customer {
+    #[yform(nested, name_attr = "address")]
+    address: Address,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use formula_y::prelude::*;
+
+#[derive(YForm)]
+struct Profile {
+    nickname: Option<Option<String>>,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use formula_y::prelude::*;
+
+fn digits(text: String) -> String {
+    text.chars().filter(char::is_ascii_digit).collect()
+}
+
+#[derive(YForm)]
+struct Contact {
+    #[yform(on_paste = "digits")]
+    subscribed: bool,
+    #[yform(on_paste = "digits", choices("home", "work"))]
+    kind: String,
+}
+
+fn main() {}
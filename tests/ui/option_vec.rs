@@ -0,0 +1,8 @@
+use formula_y::prelude::*;
+
+#[derive(YForm)]
+struct Tags {
+    tags: Option<Vec<String>>,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use formula_y::prelude::*;
+
+#[derive(YForm)]
+struct Line {
+    quantity: String,
+    unit_price: String,
+    #[yform(output)]
+    total: String,
+}
+
+fn main() {}
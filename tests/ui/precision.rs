@@ -0,0 +1,11 @@
+use formula_y::prelude::*;
+
+#[derive(YForm)]
+struct Order {
+    #[yform(precision = 2)]
+    total: f64,
+    #[yform(precision = 2)]
+    quantity: u32,
+}
+
+fn main() {}
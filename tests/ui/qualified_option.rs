@@ -0,0 +1,8 @@
+use formula_y::prelude::*;
+
+#[derive(YForm)]
+struct Profile {
+    nickname: std::option::Option<String>,
+}
+
+fn main() {}
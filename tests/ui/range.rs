@@ -0,0 +1,19 @@
+use formula_y::prelude::*;
+
+#[derive(YForm)]
+struct Volume {
+    #[yform(widget = "slider")]
+    level: u8,
+    #[yform(widget = "range", no_spinner)]
+    bass: u8,
+    #[yform(max = 10)]
+    treble: u8,
+    #[yform(widget = "range", min = "low")]
+    balance: i8,
+    #[yform(throttle_ms = 100)]
+    muted: bool,
+    #[yform(widget = "range", throttle_ms = 0)]
+    fade: u8,
+}
+
+fn main() {}
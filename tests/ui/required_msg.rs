@@ -0,0 +1,11 @@
+use formula_y::prelude::*;
+
+#[derive(YForm)]
+struct Signup {
+    #[yform(required_msg = "Please accept the terms")]
+    accept_terms: bool,
+    #[yform(required_msg = "Tell us your company")]
+    company: Option<String>,
+}
+
+fn main() {}
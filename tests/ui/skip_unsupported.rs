@@ -0,0 +1,12 @@
+#![deny(deprecated)]
+
+use formula_y::prelude::*;
+
+#[derive(Clone, PartialEq, YForm)]
+#[yform(skip_unsupported)]
+struct Settings {
+    name: String,
+    initial: char,
+}
+
+fn main() {}
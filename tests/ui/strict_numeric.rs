@@ -0,0 +1,9 @@
+use formula_y::prelude::*;
+
+#[derive(YForm)]
+struct Order {
+    #[yform(strict_numeric)]
+    reference: String,
+}
+
+fn main() {}
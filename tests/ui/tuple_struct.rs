@@ -0,0 +1,6 @@
+use formula_y::prelude::*;
+
+#[derive(YForm)]
+struct Point(i32, i32);
+
+fn main() {}
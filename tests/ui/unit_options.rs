@@ -0,0 +1,21 @@
+use formula_y::prelude::*;
+
+#[derive(YForm)]
+struct Parcel {
+    #[yform(unit_options("kg", "lb"), unit_factors(1, 0.45359237))]
+    count: u32,
+    #[yform(unit_options("kg", "lb"))]
+    weight: f64,
+    #[yform(unit_options("kg", "lb"), unit_factors(1))]
+    gross: f64,
+    #[yform(unit_options("cm", "in"), unit_factors(1, 0))]
+    width: f64,
+    #[yform(unit_options("cm", "cm"), unit_factors(1, 1))]
+    depth: f64,
+    #[yform(unit_options("cm", "in"), unit_factors(1, 2.54), format = "thousands")]
+    length: f64,
+    #[yform(unit_options("lb", "kg"), unit_factors(2.2, 1))]
+    mass: f64,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use formula_y::prelude::*;
+
+#[derive(YForm)]
+struct Settings {
+    initial: char,
+    position: (i32, i32),
+    labels: std::collections::HashMap<String, String>,
+}
+
+fn main() {}
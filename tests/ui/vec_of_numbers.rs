@@ -0,0 +1,8 @@
+use formula_y::prelude::*;
+
+#[derive(YForm)]
+struct Scores {
+    scores: Vec<u32>,
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use formula_y::prelude::*;
+
+#[derive(YForm)]
+struct Preferences {
+    #[yform(event = "input")]
+    name: String,
+    #[yform(choices("light", "dark"), widget = "select", event = "click")]
+    theme: String,
+    #[yform(event = "hover")]
+    newsletter: bool,
+}
+
+#[derive(YForm)]
+#[yform(select_event = "click")]
+struct Filters {
+    sort: String,
+}
+
+fn main() {}
@@ -1,22 +1,48 @@
-use formula_y::YForm;
+use formula_y::prelude::*;
 use gloo::console::log;
-use wasm_bindgen::JsCast;
-use web_sys::HtmlInputElement;
-
-use yew::prelude::*;
 
 #[derive(Debug, Clone, PartialEq, YForm)]
+#[yform(action_bar)]
 pub struct Data {
     pub name: Option<String>,
+    #[yform(
+        trim,
+        transform = "lowercase",
+        input_type = "email",
+        placeholder = "you@example.com",
+        regex = r"[^@\s]+@[^@\s]+",
+        error = "Enter an email address"
+    )]
     pub email: String,
     pub agree_to_terms: bool,
+
+    // Renders as a number input, and blocks submitting until it parses
+    pub age: Option<u32>,
+
+    // Renders as a group of radios, one per variant
+    #[yform(widget = "radio")]
+    pub plan: Plan,
+
+    // Tucked away in a section which starts closed
+    #[yform(section = "Advanced", collapsed)]
     pub subscribe_to_updates: Option<bool>,
+
+    // Renders as a textarea which grows to fit what the user types
+    #[yform(autogrow)]
+    pub comments: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, YFormSelect)]
+pub enum Plan {
+    Free,
+    Pro,
+    #[yform(label = "Enterprise (billed yearly)")]
+    Enterprise,
 }
 
 #[function_component(Index)]
 pub fn index() -> Html {
-
-    // The onsubmit is the only required prop. It tells the form 
+    // The onsubmit is the only required prop. It tells the form
     // what to do when your user clicks the submit btn.
     let onsubmit = Callback::from(|data: Data| {
         let msg = format!("Data succesfully passed! {:?}", data);
@@ -25,23 +51,33 @@ pub fn index() -> Html {
 
     // Optional props
 
+    // Adds a "Save draft" button which hands over the form as it is,
+    // even if required fields are still empty
+    let onsavedraft = Callback::from(|data: Data| {
+        let msg = format!("Draft saved! {:?}", data);
+        log!(msg);
+    });
+
     // You can set an initial value for the inner struct.
     // Useful for setting up forms with defaults already in place
     let init = Data {
         name: Some("Ben".to_string()),
         email: "test@gmail.com".to_string(),
         agree_to_terms: false,
+        age: None,
+        plan: Plan::Free,
 
         // Have this checked by default
         subscribe_to_updates: Some(true),
+        comments: None,
     };
 
     // I dont really have a good reason not to provide this option,
     // and I can see use cases where it increases the reusability
     // of forms.
-    let enforce_required_fields = true;
+    let enforce_required_fields = RequiredPolicy::Enforce;
 
-    html! { <DataForm {onsubmit} {init} {enforce_required_fields} /> }
+    html! { <DataForm {onsubmit} {onsavedraft} {init} {enforce_required_fields} escape_reverts={true} /> }
 }
 
 fn main() {